@@ -1,14 +1,29 @@
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 
-use crate::models::UIElement;
+use crate::models::{FilterExplanation, InspectedTree, ScanOptions, UIElement};
+#[cfg(target_os = "macos")]
+pub(crate) use crate::platform::macos::display::get_screen_frames;
+use crate::services::inspector_service::save_dump;
+use crate::services::TreeInspector;
 use crate::state::AppState;
 use crate::error::AppError;
 use crate::Result;
 
+/// Fichero del volcado de `inspect_tree`, dentro del directorio de logs
+const INSPECTION_FILE: &str = "inspected_tree.json";
+
 #[tauri::command]
-pub async fn scan_elements(state: State<'_, AppState>) -> Result<Vec<UIElement>> {
+pub async fn scan_elements(
+    options: Option<ScanOptions>,
+    state: State<'_, AppState>,
+) -> Result<Vec<UIElement>> {
     tracing::info!("Scanning clickable elements");
-    match state.accessibility_service.scan_clickable_elements().await {
+    let options = options.unwrap_or_default();
+    match state
+        .accessibility_service
+        .scan_clickable_elements_with(&options)
+        .await
+    {
         Ok(elements) => {
             tracing::info!("Scan elements returnin {} elements", elements.len());
             Ok(elements)
@@ -20,6 +35,56 @@ pub async fn scan_elements(state: State<'_, AppState>) -> Result<Vec<UIElement>>
     }
 }
 
+/// Devuelve el árbol completo de la ventana activa con todos los atributos
+/// nativos y la decisión del filtro de cada nodo. Con `save`, el volcado
+/// también se guarda en `INSPECTION_FILE` dentro del directorio de logs de
+/// la app (sirve como fixture de tests); el frontend no elige la ruta.
+#[tauri::command]
+pub async fn inspect_tree(
+    save: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<InspectedTree> {
+    tracing::info!("Inspecting accessibility tree");
+
+    let (tree, attributes) = state.accessibility_service.inspect_tree().await?;
+    let stages = state
+        .accessibility_service
+        .clickable_stages(get_screen_frames());
+    let inspected = TreeInspector::with_stages(stages).inspect(&tree, attributes);
+
+    tracing::info!("Inspected {} nodes", inspected.nodes.len());
+
+    if save.unwrap_or(false) {
+        let dir = app.path().app_log_dir().map_err(|e| {
+            AppError::Internal(format!("No log directory for the tree dump: {}", e))
+        })?;
+        std::fs::create_dir_all(&dir)?;
+        save_dump(&inspected, &dir.join(INSPECTION_FILE))?;
+    }
+
+    Ok(inspected)
+}
+
+/// Escanea la ventana activa y explica, para cada elemento clickable, qué
+/// filtro lo conservó o descartó y por qué
+#[tauri::command]
+pub async fn explain_filter(state: State<'_, AppState>) -> Result<Vec<FilterExplanation>> {
+    tracing::info!("Explaining element filter decisions");
+
+    let tree = state
+        .accessibility_service
+        .scan_tree(&ScanOptions::default())
+        .await?;
+    let stages = state
+        .accessibility_service
+        .clickable_stages(get_screen_frames());
+    let explanations = TreeInspector::with_stages(stages).explain_clickable(&tree);
+
+    tracing::info!("Explained {} filter decisions", explanations.len());
+    Ok(explanations)
+}
+
 #[tauri::command]
 pub async fn check_permissions(state: State<'_, AppState>) -> Result<bool> {
     tracing::info!("Checking accessibility permissions");
@@ -52,6 +117,12 @@ pub async fn get_focused_app_pid(state: State<'_, AppState>) -> Result<Option<i3
     }
 }
 
+/// Sin pantallas conocidas las etapas no descartan nada por estar fuera
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn get_screen_frames() -> Vec<crate::models::LogicalRect> {
+    Vec::new()
+}
+
 /// Abre directamente la sección de Accesibilidad en Configuración (macOS)
 #[tauri::command]
 #[cfg(target_os = "macos")]
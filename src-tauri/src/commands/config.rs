@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use tauri::{AppHandle, State};
+
+use crate::models::{ClickAnchor, HintCharset, HintTheme, KeyboardLayout, LabelStrategy, Role};
+use crate::services::keyboard_layout_service::{detect_keyboard, hint_charset, SystemKeyboard};
+use crate::state::AppState;
+use crate::{AppError, Result};
+
+/// Devuelve el tema actual de los hints
+#[tauri::command]
+pub async fn get_hint_theme(state: State<'_, AppState>) -> Result<HintTheme> {
+    tracing::info!("Command: get_hint_theme");
+    Ok(state.config.lock().await.hint_theme.clone())
+}
+
+/// Reemplaza el tema de los hints; se aplica en el siguiente dibujado del overlay.
+/// Un tema con otra fuente o medidas invalidas se rechaza sin tocar el actual
+#[tauri::command]
+pub async fn set_hint_theme(theme: HintTheme, state: State<'_, AppState>) -> Result<()> {
+    tracing::info!("Command: set_hint_theme");
+
+    theme
+        .validate()
+        .map_err(|e| AppError::Config(format!("Invalid hint theme: {}", e)))?;
+
+    state.config.lock().await.hint_theme = theme.clone();
+    state.window_manager.lock().await.set_theme(theme);
+
+    Ok(())
+}
+
+/// Reemplaza el anclaje de click por rol; los roles que falten usan el
+/// centro. Se aplica desde el siguiente escaneo
+#[tauri::command]
+pub async fn set_click_anchors(
+    anchors: HashMap<Role, ClickAnchor>,
+    state: State<'_, AppState>,
+) -> Result<()> {
+    tracing::info!("Command: set_click_anchors ({} roles)", anchors.len());
+
+    let mut config = state.config.lock().await;
+    state.accessibility_service.set_click_anchors(anchors.clone());
+    config.click_anchors = anchors;
+
+    Ok(())
+}
+
+/// Layout de teclado del sistema, o `null` si no se pudo detectar
+#[tauri::command]
+pub async fn detect_keyboard_layout(app: AppHandle) -> Result<Option<KeyboardLayout>> {
+    tracing::info!("Command: detect_keyboard_layout");
+    Ok(system_keyboard(&app)?.layout)
+}
+
+/// Teclado activo leido en el hilo principal: Text Input Sources (macOS)
+/// no se puede llamar desde los hilos de los comandos
+pub(crate) fn system_keyboard(app: &AppHandle) -> Result<SystemKeyboard> {
+    let (tx, rx) = mpsc::channel();
+    app.run_on_main_thread(move || {
+        let _ = tx.send(detect_keyboard());
+    })
+    .map_err(|e| {
+        tracing::error!("Failed to detect the keyboard on main thread: {}", e);
+        AppError::Internal("Failed to detect the keyboard".to_string())
+    })?;
+
+    rx.recv()
+        .map_err(|_| AppError::Internal("Failed to detect the keyboard".to_string()))
+}
+
+/// Cambia de donde salen los caracteres de los hints. `layout` fija el
+/// layout (`null` = detectar) y `hint_chars` reemplaza los caracteres de
+/// los modos custom y physical. Devuelve el charset resultante
+#[tauri::command]
+pub async fn set_hint_charset(
+    charset: HintCharset,
+    layout: Option<KeyboardLayout>,
+    hint_chars: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String> {
+    tracing::info!("Command: set_hint_charset {:?} ({:?})", charset, layout);
+
+    let system = system_keyboard(&app)?;
+    let mut config = state.config.lock().await;
+    let mut updated = config.clone();
+    if let Some(hint_chars) = hint_chars {
+        updated.hint_chars = hint_chars;
+    }
+    updated.hint_charset = charset;
+    updated.keyboard_layout = layout;
+
+    // Con un solo caracter no hay labels de mas de un hint
+    let resolved = hint_charset(&updated, &system);
+    if resolved.chars().count() < 2 {
+        return Err(AppError::Config(format!(
+            "Hint charset '{}' needs at least 2 distinct characters",
+            resolved
+        )));
+    }
+
+    *config = updated;
+    Ok(resolved)
+}
+
+/// Elige como se reparten los labels en la siguiente activacion
+#[tauri::command]
+pub async fn set_label_strategy(strategy: LabelStrategy, state: State<'_, AppState>) -> Result<()> {
+    tracing::info!("Command: set_label_strategy {:?}", strategy);
+    state.config.lock().await.label_strategy = strategy;
+    Ok(())
+}
@@ -1,25 +1,134 @@
+use global_hotkey::GlobalHotKeyEvent;
+use std::sync::Arc;
 use std::thread;
-use tauri::{AppHandle, Emitter, State};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Mutex;
 
+use crate::models::{HotkeyAction, HotkeyBinding, HotkeyStatus, KeySequence};
+use crate::services::hotkey_service::GlobalHotkeyBackend;
+use crate::services::HotkeyService;
 use crate::state::AppState;
-use crate::Result;
+use crate::{AppError, Result};
 
+/// Asigna un combo a una accion y lo guarda en la config. Devuelve la
+/// asignacion con el combo en forma canonica
 #[tauri::command]
-pub async fn register_hotkey(
+pub async fn bind_hotkey(
+    action: HotkeyAction,
     key_combo: String,
-    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<()> {
-    let mut hotkeys = state.hotkey_service.lock().await;
+) -> Result<HotkeyBinding> {
+    tracing::info!("Command: bind_hotkey {:?} -> {}", action, key_combo);
 
-    let rx = hotkeys.register(&key_combo)?;
+    let combo = KeySequence::parse(&key_combo).map_err(AppError::Hotkey)?;
+    state
+        .hotkey_service
+        .lock()
+        .await
+        .bind(action, combo.clone())?;
 
-    // Escuchar el hotkey en un hilo dedicado y emitir evento a frontend
-    thread::spawn(move || {
-        for _event in rx.iter() {
-            let _ = app.emit("hotkey-activate", ());
-        }
-    });
+    let binding = HotkeyBinding::new(action, combo);
+    let mut config = state.config.lock().await;
+    config.hotkeys.retain(|b| b.action != action);
+    config.hotkeys.push(binding.clone());
+
+    Ok(binding)
+}
+
+/// Quita el combo de una accion
+#[tauri::command]
+pub async fn unbind_hotkey(action: HotkeyAction, state: State<'_, AppState>) -> Result<()> {
+    tracing::info!("Command: unbind_hotkey {:?}", action);
+
+    state.hotkey_service.lock().await.unbind(action)?;
+    state
+        .config
+        .lock()
+        .await
+        .hotkeys
+        .retain(|b| b.action != action);
 
     Ok(())
 }
+
+/// Estado en vivo del hotkey de cada accion, con el motivo si no esta
+/// registrado
+#[tauri::command]
+pub async fn list_hotkeys(state: State<'_, AppState>) -> Result<Vec<HotkeyStatus>> {
+    tracing::info!("Command: list_hotkeys");
+    Ok(state.hotkey_service.lock().await.statuses())
+}
+
+/// Emite `hotkey-registration-failed` con el estado de cada hotkey de la
+/// config que no se pudo registrar al arrancar. En `setup` el webview aun no
+/// escucha: lo llama el frontend tras suscribirse, y solo avisa una vez
+#[tauri::command]
+pub async fn report_startup_hotkeys(app: AppHandle, state: State<'_, AppState>) -> Result<()> {
+    tracing::info!("Command: report_startup_hotkeys");
+
+    for status in state.hotkey_service.lock().await.take_startup_failures() {
+        app.emit("hotkey-registration-failed", status)
+            .map_err(|e| AppError::Hotkey(format!("Failed to emit hotkey failure: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Conecta el servicio a global-hotkey, registra los hotkeys de la config
+/// y arranca el hilo que reparte los eventos. Se llama una vez desde
+/// `setup`, en el hilo principal
+pub fn start_hotkeys(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let hotkeys = Arc::clone(&state.hotkey_service);
+
+    {
+        let bindings = state.config.blocking_lock().hotkeys.clone();
+        let mut service = hotkeys.blocking_lock();
+
+        let handle = app.clone();
+        service.set_backend(Box::new(GlobalHotkeyBackend::new(Box::new(move |task| {
+            handle.run_on_main_thread(task).map_err(|e| {
+                AppError::Hotkey(format!("Failed to run hotkey task on main thread: {}", e))
+            })
+        }))));
+
+        // El webview aun no ha cargado: los fallos se emiten cuando lo pide
+        // con report_startup_hotkeys
+        service.bind_startup(bindings);
+    }
+
+    spawn_listener(app.clone(), hotkeys);
+}
+
+/// Un solo hilo para todos los hotkeys: traduce cada evento a su accion y
+/// emite el evento de esa accion al frontend. Con una secuencia a medias
+/// espera como mucho hasta su plazo para poder descartarla.
+fn spawn_listener(app: AppHandle, hotkeys: Arc<Mutex<HotkeyService>>) {
+    let rx = GlobalHotKeyEvent::receiver().clone();
+
+    thread::spawn(move || loop {
+        let deadline = hotkeys.blocking_lock().deadline();
+        let event = match deadline {
+            Some(deadline) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(event) => event,
+                    Err(e) if e.is_timeout() => {
+                        hotkeys.blocking_lock().expire(Instant::now());
+                        continue;
+                    }
+                    Err(_) => break,
+                }
+            }
+            None => match rx.recv() {
+                Ok(event) => event,
+                Err(_) => break,
+            },
+        };
+
+        let action = hotkeys.blocking_lock().handle_event(&event, Instant::now());
+        if let Some(action) = action {
+            tracing::debug!("Hotkey pressed: {:?}", action);
+            let _ = app.emit(action.event_name(), ());
+        }
+    });
+}
@@ -1,11 +1,19 @@
 pub mod accessibility;
+pub mod config;
 pub mod navigation;
 pub mod hotkey;
+pub mod usage;
 pub mod window;
 
 pub use accessibility::{
-    check_permissions, open_accessibility_settings, request_permissions, scan_elements,
+    check_permissions, explain_filter, inspect_tree, open_accessibility_settings,
+    request_permissions, scan_elements,
 };
-pub use navigation::{activate_navigation, deactivate_navigation};
-pub use hotkey::register_hotkey;
-pub use window::{perform_click, show_hints};
+pub use config::{
+    detect_keyboard_layout, get_hint_theme, set_click_anchors, set_hint_charset, set_hint_theme,
+    set_label_strategy,
+};
+pub use navigation::{activate_navigation, deactivate_navigation, navigate_elements, select_hint};
+pub use hotkey::{bind_hotkey, list_hotkeys, report_startup_hotkeys, start_hotkeys, unbind_hotkey};
+pub use usage::{get_usage_stats, load_usage_store, reset_usage_stats};
+pub use window::{perform_click, show_hints, update_hints};
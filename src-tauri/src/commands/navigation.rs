@@ -1,16 +1,26 @@
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, State};
 use std::sync::mpsc;
 
-use crate::models::Hint;
+use crate::models::{ElementTree, Hint, HintMode, UIElement};
+use crate::commands::accessibility::get_screen_frames;
+use crate::commands::config::system_keyboard;
+use crate::services::keyboard_layout_service::{effective_layout, hint_charset};
+use crate::services::usage_store::unix_now;
 use crate::services::{filter_elements, HintGenerator};
 use crate::state::AppState;
 use crate::Result;
 use crate::AppError;
 
-/// Comando que ejecuta el flujo completo: scan → filter → hints → overlay
+/// Comando que ejecuta el flujo completo: scan → filter → hints → overlay.
+/// `mode` elige el estilo de los hints (por defecto click izquierdo).
 #[tauri::command]
-pub async fn activate_navigation(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<Hint>> {
-    tracing::info!("Command: activate_navigation");
+pub async fn activate_navigation(
+    mode: Option<HintMode>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<Hint>> {
+    let mode = mode.unwrap_or_default();
+    tracing::info!("Command: activate_navigation ({:?})", mode);
 
     // 1. Verificar permisos
     state.accessibility_service.ensure_permissions()?;
@@ -22,14 +32,72 @@ pub async fn activate_navigation(app: AppHandle, state: State<'_, AppState>) ->
         .await?;
     tracing::info!("Scanned {} elements", elements.len());
 
+    show_navigation(&app, &state, elements, mode).await
+}
+
+/// Comando que activa la navegación sobre elementos dados por quien llama,
+/// sin escaneo de accesibilidad (en X11 no lo hay). Los elementos pasan por
+/// las mismas etapas que un escaneo: visibilidad, oclusión, límite y punto
+/// de click.
+#[tauri::command]
+pub async fn navigate_elements(
+    elements: Vec<UIElement>,
+    mode: Option<HintMode>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<Hint>> {
+    let mode = mode.unwrap_or_default();
+    tracing::info!(
+        "Command: navigate_elements ({} elements, {:?})",
+        elements.len(),
+        mode
+    );
+
+    let mut tree = ElementTree::new();
+    for element in elements {
+        tree.add_node(element, None);
+    }
+    let elements = state
+        .accessibility_service
+        .clickable_stages(get_screen_frames())
+        .clickable(&tree);
+
+    show_navigation(&app, &state, elements, mode).await
+}
+
+/// filter → hints → overlay (y en X11, la sesión de teclado)
+async fn show_navigation(
+    app: &AppHandle,
+    state: &AppState,
+    elements: Vec<UIElement>,
+    mode: HintMode,
+) -> Result<Vec<Hint>> {
     // 3. Filtrar elementos (tamaño mínimo, deduplicación)
     let filtered = filter_elements(elements);
     tracing::info!("Filtered to {} elements", filtered.len());
 
-    // 4. Generar hints
-    let hint_generator = HintGenerator::new();
-    let hints = hint_generator.generate(&filtered);
+    // 4. Generar hints con el charset y el layout activos (el charset lo
+    // necesita tambien la sesion de teclado de X11)
+    let system = system_keyboard(app)?;
+    let config = state.config.lock().await;
+    let layout = effective_layout(&config, &system);
+    let charset = hint_charset(&config, &system);
+    let hint_generator =
+        HintGenerator::with_charset(&charset).with_strategy(config.label_strategy, layout);
+    drop(config);
+
+    // Los elementos mas usados en esta app llevan los labels mas cortos
+    let hints = match state.accessibility_service.get_focused_application_name() {
+        Some(app_name) => {
+            let mut usage = state.usage_store.lock().await;
+            let scores = usage.scores(&app_name, &filtered, unix_now());
+            usage.begin_session(app_name, &filtered);
+            hint_generator.generate_with_usage(&filtered, &scores)
+        }
+        None => hint_generator.generate(&filtered),
+    };
     tracing::info!("Generated {} hints", hints.len());
+    *state.active_hints.lock().await = hints.clone();
 
     // 5. Mostrar overlay en hilo principal
     let wm = state.window_manager.clone();
@@ -40,7 +108,7 @@ pub async fn activate_navigation(app: AppHandle, state: State<'_, AppState>) ->
         .run_on_main_thread(move || {
             let res = tauri::async_runtime::block_on(async {
                 let mut guard = wm.lock().await;
-                guard.show_overlay(&hints_clone).await
+                guard.show_overlay(&hints_clone, mode).await
             });
             let _ = tx.send(res);
         })
@@ -49,19 +117,184 @@ pub async fn activate_navigation(app: AppHandle, state: State<'_, AppState>) ->
             AppError::Overlay("Failed to render overlay".to_string())
         })?;
 
-    let _render_res = rx
-        .recv()
+    rx.recv()
         .map_err(|_| AppError::Overlay("Failed to render overlay".to_string()))??;
 
+    // En X11 las teclas irian a la app con foco: capturar el teclado
+    // mientras se ven los hints
+    #[cfg(target_os = "linux")]
+    spawn_keyboard_grab(app.clone(), charset);
+
     tracing::info!("Navigation activated successfully");
     Ok(hints)
 }
 
+/// Sesion de teclado en X11: cada prefijo filtra el overlay y emite
+/// `navigation-prefix`. Al terminar (Esc, timeout, seleccion o error) suelta
+/// el teclado, oculta el overlay y emite `navigation-exit`; si quedo un solo
+/// hint, antes hace click en el y emite `navigation-selected` con su
+/// `element_id`.
+#[cfg(target_os = "linux")]
+fn spawn_keyboard_grab(app: AppHandle, charset: String) {
+    use crate::platform::linux::input_grab::KeyboardGrab;
+    use crate::services::navigation_input::NavigationInput;
+    use std::ops::ControlFlow;
+    use std::time::{Duration, Instant};
+    use tauri::Manager;
+
+    // Con el teclado capturado el usuario no puede hacer nada mas: la
+    // sesion no puede quedarse abierta indefinidamente
+    const GRAB_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    std::thread::spawn(move || {
+        let wm = app.state::<AppState>().window_manager.clone();
+        let mut input = NavigationInput::new(&charset, GRAB_IDLE_TIMEOUT, Instant::now());
+        let mut selected = None;
+
+        let result = KeyboardGrab::connect().and_then(|mut grab| {
+            grab.run(&mut input, |prefix| {
+                let _ = app.emit("navigation-prefix", prefix);
+                let visible = tauri::async_runtime::block_on(async {
+                    wm.lock().await.update_overlay(prefix).await
+                });
+                match visible {
+                    Ok(mut ids) if ids.len() == 1 => {
+                        selected = ids.pop();
+                        ControlFlow::Break(())
+                    }
+                    Ok(_) => ControlFlow::Continue(()),
+                    Err(e) => {
+                        tracing::warn!("Failed to filter hints: {}", e);
+                        ControlFlow::Continue(())
+                    }
+                }
+            })
+        });
+
+        let reason = match result {
+            Ok(reason) => {
+                tracing::info!("Keyboard session ended: {:?}", reason);
+                Some(reason)
+            }
+            Err(e) => {
+                tracing::error!("Keyboard session failed: {}", e);
+                None
+            }
+        };
+
+        let hidden =
+            tauri::async_runtime::block_on(async { wm.lock().await.hide_overlay().await });
+        if let Err(e) = hidden {
+            tracing::error!("Failed to hide overlay: {}", e);
+        }
+        // Con el overlay ya oculto, el click llega a la app
+        if let Some(element_id) = selected {
+            let state = app.state::<AppState>();
+            match tauri::async_runtime::block_on(click_hint(&state, &element_id)) {
+                Ok(()) => {
+                    let _ = app.emit("navigation-selected", &element_id);
+                }
+                Err(e) => tracing::error!("Failed to click hint {}: {}", element_id, e),
+            }
+        }
+        // `null` si la sesion acabo por un error
+        let _ = app.emit("navigation-exit", reason);
+    });
+}
+
+/// Elige el hint de `element_id` de la activacion en curso: oculta el
+/// overlay y hace click en el elemento (en X11 lo hace la sesion de teclado)
+#[tauri::command]
+pub async fn select_hint(
+    element_id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<()> {
+    tracing::info!("Command: select_hint {}", element_id);
+    complete_selection(&app, &state, &element_id).await
+}
+
+/// Termina la activacion con el hint elegido: oculta el overlay en el hilo
+/// principal, hace click, anota el uso y emite `navigation-selected`
+pub(crate) async fn complete_selection(
+    app: &AppHandle,
+    state: &AppState,
+    element_id: &str,
+) -> Result<()> {
+    if !is_active_hint(state, element_id).await {
+        return Err(not_active(element_id));
+    }
+
+    let wm = state.window_manager.clone();
+    let (tx, rx) = mpsc::channel();
+    app
+        .run_on_main_thread(move || {
+            let res = tauri::async_runtime::block_on(async { wm.lock().await.hide_overlay().await });
+            let _ = tx.send(res);
+        })
+        .map_err(|e| {
+            tracing::error!("Failed to hide overlay on main thread: {}", e);
+            AppError::Overlay("Failed to hide overlay".to_string())
+        })?;
+    rx.recv()
+        .map_err(|_| AppError::Overlay("Failed to hide overlay".to_string()))??;
+
+    click_hint(state, element_id).await?;
+    let _ = app.emit("navigation-selected", element_id);
+    Ok(())
+}
+
+/// Si `element_id` tiene hint en la activacion en curso
+pub(crate) async fn is_active_hint(state: &AppState, element_id: &str) -> bool {
+    state
+        .active_hints
+        .lock()
+        .await
+        .iter()
+        .any(|hint| hint.element_id == element_id)
+}
+
+fn not_active(element_id: &str) -> AppError {
+    AppError::Click(format!(
+        "Hint {} is not part of the current navigation",
+        element_id
+    ))
+}
+
+/// Hace click en un hint de la activacion en curso y anota su uso. La
+/// activacion termina aqui: un segundo click con el mismo id es un error
+async fn click_hint(state: &AppState, element_id: &str) -> Result<()> {
+    let hint = {
+        let mut active = state.active_hints.lock().await;
+        let hint = active
+            .iter()
+            .find(|hint| hint.element_id == element_id)
+            .cloned()
+            .ok_or_else(|| not_active(element_id))?;
+        active.clear();
+        hint
+    };
+
+    state.click_service.perform_click(hint.click_point)?;
+
+    let recorded = state
+        .usage_store
+        .lock()
+        .await
+        .record_selection(element_id, unix_now());
+    if let Err(e) = recorded {
+        tracing::warn!("Failed to record hint selection: {}", e);
+    }
+
+    Ok(())
+}
+
 /// Comando para desactivar la navegación y ocultar el overlay
 #[tauri::command]
 pub async fn deactivate_navigation(state: State<'_, AppState>) -> Result<()> {
     tracing::info!("Command: deactivate_navigation");
 
+    state.active_hints.lock().await.clear();
     let mut wm = state.window_manager.lock().await;
     wm.hide_overlay().await?;
 
@@ -0,0 +1,47 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::models::UsageEntry;
+use crate::services::usage_store::unix_now;
+use crate::services::UsageStore;
+use crate::state::AppState;
+use crate::Result;
+
+/// Fichero del uso aprendido, dentro del directorio de datos de la app
+const USAGE_FILE: &str = "usage.json";
+
+/// Uso aprendido de una app (o de todas), de mas a menos usado
+#[tauri::command]
+pub async fn get_usage_stats(
+    app: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<UsageEntry>> {
+    tracing::info!("Command: get_usage_stats {:?}", app);
+    Ok(state
+        .usage_store
+        .lock()
+        .await
+        .entries(app.as_deref(), unix_now()))
+}
+
+/// Borra el uso aprendido de una app, o todo con `app` null
+#[tauri::command]
+pub async fn reset_usage_stats(app: Option<String>, state: State<'_, AppState>) -> Result<()> {
+    tracing::info!("Command: reset_usage_stats {:?}", app);
+    state.usage_store.lock().await.reset(app.as_deref())
+}
+
+/// Abre el fichero de uso en el directorio de datos local de la app. Se
+/// llama una vez desde `setup`; si no hay directorio, el uso solo dura
+/// hasta cerrar la app
+pub fn load_usage_store(app: &AppHandle) {
+    let dir = match app.path().app_local_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            tracing::warn!("No local data dir, usage stats stay in memory: {}", e);
+            return;
+        }
+    };
+
+    let store = UsageStore::open(dir.join(USAGE_FILE));
+    *app.state::<AppState>().usage_store.blocking_lock() = store;
+}
@@ -1,7 +1,8 @@
 use tauri::{AppHandle, State};
 use std::sync::mpsc;
 
-use crate::models::Hint;
+use crate::commands::navigation::{complete_selection, is_active_hint};
+use crate::models::{Hint, HintMode, LogicalPoint};
 use crate::state::AppState;
 use crate::Result;
 use crate::AppError;
@@ -9,6 +10,7 @@ use crate::AppError;
 #[tauri::command]
 pub async fn show_hints(
     hints: Vec<Hint>,
+    mode: Option<HintMode>,
     app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<()> {
@@ -27,7 +29,7 @@ pub async fn show_hints(
         .run_on_main_thread(move || {
             let res = tauri::async_runtime::block_on(async {
                 let mut guard = wm.lock().await;
-                guard.show_overlay(&hints).await
+                guard.show_overlay(&hints, mode.unwrap_or_default()).await
             });
             let _ = tx.send(res);
         })
@@ -36,15 +38,58 @@ pub async fn show_hints(
             AppError::Overlay("Failed to render overlay".to_string())
         })?;
 
-    let _render_res = rx
-        .recv()
+    rx.recv()
         .map_err(|_| AppError::Overlay("Failed to render overlay".to_string()))??;
 
     Ok(())
 }
 
+/// Filtra los hints visibles por el prefijo escrito sin redibujar el overlay.
+/// Devuelve los `element_id` que siguen coincidiendo. Si solo queda un hint
+/// de la activacion en curso, la seleccion se completa aqui (click y uso)
+#[tauri::command]
+pub async fn update_hints(
+    prefix: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>> {
+    tracing::debug!("Command: update_hints with prefix '{}'", prefix);
+
+    let wm = state.window_manager.clone();
+    let (tx, rx) = mpsc::channel();
+
+    app
+        .run_on_main_thread(move || {
+            let res = tauri::async_runtime::block_on(async {
+                let mut guard = wm.lock().await;
+                guard.update_overlay(&prefix).await
+            });
+            let _ = tx.send(res);
+        })
+        .map_err(|e| {
+            tracing::error!("Failed to update overlay on main thread: {}", e);
+            AppError::Overlay("Failed to update overlay".to_string())
+        })?;
+
+    let ids = rx
+        .recv()
+        .map_err(|_| AppError::Overlay("Failed to update overlay".to_string()))??;
+
+    if let [element_id] = ids.as_slice() {
+        if is_active_hint(&state, element_id).await {
+            complete_selection(&app, &state, element_id).await?;
+        }
+    }
+
+    Ok(ids)
+}
+
 #[tauri::command]
-pub async fn perform_click(x: f64, y: f64, state: State<'_, AppState>) -> Result<()> {
-    tracing::info!("Command: perform_click at coordinates ({}, {})", x, y);
-    state.click_service.perform_click(x, y)
+pub async fn perform_click(point: LogicalPoint, state: State<'_, AppState>) -> Result<()> {
+    tracing::info!(
+        "Command: perform_click at coordinates ({}, {})",
+        point.x,
+        point.y
+    );
+    state.click_service.perform_click(point)
 }
@@ -1,6 +1,9 @@
+use serde::ser::SerializeStruct;
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::models::HotkeyConflict;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Accessibility error: {0}")]
@@ -9,12 +12,21 @@ pub enum AppError {
     #[error("Hotkey error: {0}")]
     Hotkey(String),
 
+    #[error("Hotkey {combo} {conflict}")]
+    HotkeyConflict {
+        combo: String,
+        conflict: HotkeyConflict,
+    },
+
     #[error("Overlay error: {0}")]
     Overlay(String),
 
     #[error("Click error: {0}")]
     Click(String),
 
+    #[error("Input error: {0}")]
+    Input(String),
+
     #[error("Window error: {0}")]
     Window(String),
 
@@ -31,12 +43,24 @@ pub enum AppError {
     Other(String),
 }
 
+/// Los errores llegan al frontend como texto, salvo los conflictos de
+/// hotkey, que van como `{ message, combo, conflict }` para que ajustes
+/// pueda explicar el motivo
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        match self {
+            AppError::HotkeyConflict { combo, conflict } => {
+                let mut payload = serializer.serialize_struct("HotkeyConflictError", 3)?;
+                payload.serialize_field("message", &self.to_string())?;
+                payload.serialize_field("combo", combo)?;
+                payload.serialize_field("conflict", conflict)?;
+                payload.end()
+            }
+            _ => serializer.serialize_str(&self.to_string()),
+        }
     }
 }
 
@@ -65,3 +89,26 @@ impl From<AppError> for String {
         error.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::HotkeyAction;
+
+    #[test]
+    fn test_hotkey_conflict_serializes_as_payload() {
+        let error = AppError::HotkeyConflict {
+            combo: "Cmd+J".to_string(),
+            conflict: HotkeyConflict::AlreadyBound {
+                action: HotkeyAction::ScrollMode,
+            },
+        };
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["combo"], "Cmd+J");
+        assert_eq!(json["conflict"]["kind"], "already_bound");
+        assert_eq!(json["message"], error.to_string());
+
+        let other = serde_json::to_value(AppError::Config("bad".to_string())).unwrap();
+        assert_eq!(other, "Config error: bad");
+    }
+}
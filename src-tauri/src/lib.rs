@@ -26,17 +26,38 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(AppState::new())
+        .setup(|app| {
+            start_hotkeys(app.handle());
+            load_usage_store(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             scan_elements,
+            inspect_tree,
+            explain_filter,
             check_permissions,
             request_permissions,
             open_accessibility_settings,
-            register_hotkey,
+            bind_hotkey,
+            unbind_hotkey,
+            list_hotkeys,
+            report_startup_hotkeys,
             get_focused_app_pid,
             show_hints,
+            update_hints,
             perform_click,
             activate_navigation,
+            navigate_elements,
             deactivate_navigation,
+            select_hint,
+            get_hint_theme,
+            set_hint_theme,
+            set_click_anchors,
+            detect_keyboard_layout,
+            set_hint_charset,
+            set_label_strategy,
+            get_usage_stats,
+            reset_usage_stats,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
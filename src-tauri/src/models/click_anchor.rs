@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{LogicalPoint, LogicalRect, Role};
+
+// Distancia al borde para los anclajes laterales
+const ANCHOR_INSET: f64 = 8.0;
+
+/// Punto preferido de click dentro de un elemento
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClickAnchor {
+    Center,
+    // Centro vertical pegado al borde izquierdo (ej: texto de un campo)
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl ClickAnchor {
+    /// Punto del anclaje dentro de `rect`; en elementos estrechos el margen
+    /// se reduce para no salir del centro
+    pub fn point_in(&self, rect: &LogicalRect) -> LogicalPoint {
+        let center = rect.center();
+        let inset_x = ANCHOR_INSET.min(rect.width / 2.0);
+        let inset_y = ANCHOR_INSET.min(rect.height / 2.0);
+
+        match self {
+            ClickAnchor::Center => center,
+            ClickAnchor::Left => LogicalPoint::new(rect.x + inset_x, center.y),
+            ClickAnchor::Right => LogicalPoint::new(rect.right() - inset_x, center.y),
+            ClickAnchor::Top => LogicalPoint::new(center.x, rect.y + inset_y),
+            ClickAnchor::Bottom => LogicalPoint::new(center.x, rect.bottom() - inset_y),
+        }
+    }
+
+    /// Anclajes por defecto: los campos de texto se clickan a la izquierda
+    /// (lejos del boton de borrar) y las casillas sobre la caja, no el label
+    pub fn defaults() -> HashMap<Role, ClickAnchor> {
+        HashMap::from([
+            (Role::TextField, ClickAnchor::Left),
+            (Role::TextArea, ClickAnchor::Left),
+            (Role::ComboBox, ClickAnchor::Left),
+            (Role::CheckBox, ClickAnchor::Left),
+            (Role::RadioButton, ClickAnchor::Left),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_points() {
+        let rect = LogicalRect::new(100.0, 50.0, 200.0, 20.0);
+        assert_eq!(
+            ClickAnchor::Center.point_in(&rect),
+            LogicalPoint::new(200.0, 60.0)
+        );
+        assert_eq!(
+            ClickAnchor::Left.point_in(&rect),
+            LogicalPoint::new(108.0, 60.0)
+        );
+        assert_eq!(
+            ClickAnchor::Bottom.point_in(&rect),
+            LogicalPoint::new(200.0, 62.0)
+        );
+
+        // Elemento mas estrecho que el margen: el anclaje cae en el centro
+        let narrow = LogicalRect::new(0.0, 0.0, 6.0, 6.0);
+        assert_eq!(ClickAnchor::Left.point_in(&narrow), narrow.center());
+    }
+}
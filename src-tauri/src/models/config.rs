@@ -1,10 +1,15 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{
+    ClickAnchor, HintCharset, HintTheme, HotkeyBinding, KeyboardLayout, LabelStrategy, Role,
+};
 
 // Configuracion principal de la aplicacion
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
-    // Hotkey para activar navegacion (ej: "Cmd+J")
-    pub hotkey: String,
+    // Hotkeys globales, uno por accion (ej: "Cmd+J" para activar hints)
+    pub hotkeys: Vec<HotkeyBinding>,
 
     // Profundidad maxima de escaneo del arbol de accesibilidad
     pub scan_depth: usize,
@@ -12,21 +17,43 @@ pub struct AppConfig {
     // Numero maximo de elementos a escanear
     pub max_elements: usize,
 
-    // Caracteres usados para generar hints (home row keys)
+    // Caracteres para los hints con `hint_charset` custom o physical (en
+    // physical son posiciones nombradas como en QWERTY)
     pub hint_chars: String,
 
+    // De donde salen los caracteres de los hints; por defecto la fila
+    // central del layout
+    pub hint_charset: HintCharset,
+
+    // Layout de teclado; None = detectar el del sistema
+    pub keyboard_layout: Option<KeyboardLayout>,
+
+    // Reparto de labels: base-N en orden o el mas facil de teclear primero
+    pub label_strategy: LabelStrategy,
+
     // Timeout en ms para la secuencia de hints
     pub hint_timeout: u64,
+
+    // Punto de click preferido por rol (el resto usa el centro)
+    pub click_anchors: HashMap<Role, ClickAnchor>,
+
+    // Colores, fuente y bordes de los hints, con ajustes por modo y rol
+    pub hint_theme: HintTheme,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
-            hotkey: "Cmd+J".to_string(),
+            hotkeys: HotkeyBinding::defaults(),
             scan_depth: 10,
             max_elements: 500,
             hint_chars: "asdfghjkl".to_string(),
+            hint_charset: HintCharset::HomeRow,
+            keyboard_layout: None,
+            label_strategy: LabelStrategy::BaseN,
             hint_timeout: 1000,
+            click_anchors: ClickAnchor::defaults(),
+            hint_theme: HintTheme::default(),
         }
     }
 }
@@ -34,14 +61,24 @@ impl Default for AppConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::HotkeyAction;
 
     #[test]
     fn test_default_config() {
         let config = AppConfig::default();
-        assert_eq!(config.hotkey, "Cmd+J");
+        let activate = config
+            .hotkeys
+            .iter()
+            .find(|b| b.action == HotkeyAction::ActivateHints)
+            .unwrap();
+        assert_eq!(activate.combo, "Cmd+J".parse().unwrap());
         assert_eq!(config.scan_depth, 10);
         assert_eq!(config.max_elements, 500);
         assert_eq!(config.hint_chars, "asdfghjkl");
+        assert_eq!(config.hint_charset, HintCharset::HomeRow);
+        assert_eq!(config.keyboard_layout, None);
+        assert_eq!(config.label_strategy, LabelStrategy::BaseN);
         assert_eq!(config.hint_timeout, 1000);
+        assert_eq!(config.click_anchors[&Role::TextField], ClickAnchor::Left);
     }
 }
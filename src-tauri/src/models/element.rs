@@ -1,34 +1,118 @@
 use serde::{Deserialize, Serialize};
 
+use super::{LogicalPoint, LogicalRect, Role};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UIElement {
     pub id: String,
-    pub role: String,
+    // Rol normalizado (comun a todos los backends)
+    pub role: Role,
+    // Rol nativo de la plataforma (ej: "AXButton")
+    pub raw_role: String,
     pub title: Option<String>,
-    pub x: f64,
-    pub y: f64,
-    pub width: f64,
-    pub height: f64,
+    // Frame del elemento en el espacio global logico
+    pub frame: LogicalRect,
+
+    // Atributos opcionales: solo se rellenan si se piden en `ScanOptions`
+    pub value: Option<String>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    pub focused: Option<bool>,
+    pub selected: Option<bool>,
+    pub actions: Option<Vec<String>>,
+    pub pid: Option<i32>,
+    pub window_id: Option<u32>,
+
+    // Parte visible del frame tras recortar con ventana, scroll areas y
+    // pantallas (None si no se ha calculado)
+    pub visible_rect: Option<LogicalRect>,
+
+    // Punto donde se hace click, dentro de la parte visible y fuera de los
+    // hijos accionables (None = centro de la parte visible)
+    pub click_point: Option<LogicalPoint>,
+
+    // Ruta en el arbol ("0/3/1"); la rellena `ElementTree::add_node`
+    #[serde(default)]
+    pub path: Option<String>,
+
+    // Roles nativos de los ancestros desde la raiz del escaneo (la ventana),
+    // sin posiciones: "AXWindow/AXToolbar". La rellena `ElementTree::add_node`
+    #[serde(default)]
+    pub role_path: Option<String>,
+
+    // Identificador que la app da al elemento (AXIdentifier en macOS)
+    #[serde(default)]
+    pub identifier: Option<String>,
 }
 
 impl UIElement {
-    pub fn new(
-        id: String,
-        role: String,
-        title: Option<String>,
-        x: f64,
-        y: f64,
-        width: f64,
-        height: f64,
-    ) -> Self {
+    // Los elementos se construyen en el escaneo AX de macOS; en el resto
+    // llegan deserializados
+    #[cfg(any(target_os = "macos", test))]
+    pub fn new(id: String, role: Role, title: Option<String>, frame: LogicalRect) -> Self {
         Self {
             id,
+            raw_role: role.as_str().to_string(),
             role,
             title,
-            x,
-            y,
-            width,
-            height,
+            frame,
+            value: None,
+            description: None,
+            enabled: None,
+            focused: None,
+            selected: None,
+            actions: None,
+            pid: None,
+            window_id: None,
+            visible_rect: None,
+            click_point: None,
+            path: None,
+            role_path: None,
+            identifier: None,
+        }
+    }
+
+    /// Parte visible del elemento (el frame completo si no se ha recortado)
+    pub fn visible_frame(&self) -> LogicalRect {
+        self.visible_rect.unwrap_or(self.frame)
+    }
+
+    /// Punto de click efectivo
+    pub fn click_point(&self) -> LogicalPoint {
+        self.click_point
+            .unwrap_or_else(|| self.visible_frame().center())
+    }
+
+    /// Clave que identifica al elemento entre escaneos de la misma app (el
+    /// `id` cambia en cada escaneo): rol nativo, identificador, titulo y ruta
+    /// de roles desde la ventana. La ruta de roles no cambia al aparecer
+    /// hermanos; solo si el elemento no tiene identificador ni titulo se usa
+    /// la ruta de posiciones, porque sus hermanos tendrian la misma clave
+    pub fn stable_key(&self) -> String {
+        let non_empty = |text: &Option<String>| text.clone().filter(|text| !text.is_empty());
+        let (identifier, title) = (non_empty(&self.identifier), non_empty(&self.title));
+
+        if identifier.is_none() && title.is_none() {
+            return format!(
+                "{}|@{}",
+                self.raw_role,
+                self.path.as_deref().unwrap_or_default()
+            );
         }
+
+        format!(
+            "{}|{}|{}|{}",
+            self.raw_role,
+            identifier.unwrap_or_default(),
+            title.unwrap_or_default(),
+            self.role_path.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Conserva el rol nativo del backend junto al rol normalizado
+    #[cfg(target_os = "macos")]
+    pub fn with_raw_role(mut self, raw_role: impl Into<String>) -> Self {
+        self.raw_role = raw_role.into();
+        self
     }
 }
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use super::UIElement;
+
+/// Nodo del arbol de accesibilidad con enlaces a padre e hijos
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeNode {
+    pub element: UIElement,
+    // Indice del padre dentro del arena (None para la raiz)
+    pub parent: Option<usize>,
+    // Indices de los hijos, en el orden que reporta el backend
+    pub children: Vec<usize>,
+    pub depth: usize,
+    // Posicion del nodo entre los hijos de su padre
+    pub index_in_parent: usize,
+}
+
+/// Arbol de elementos guardado como arena: los nodos se referencian por indice.
+///
+/// Los escaneos producen el arbol completo; las listas planas de elementos
+/// clickables son vistas sobre el.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElementTree {
+    nodes: Vec<TreeNode>,
+}
+
+impl ElementTree {
+    pub fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Añade un nodo como ultimo hijo de `parent` y devuelve su indice. El
+    /// elemento guarda su ruta en `path` y la de roles en `role_path`
+    pub fn add_node(&mut self, mut element: UIElement, parent: Option<usize>) -> usize {
+        let index = self.nodes.len();
+
+        let (depth, index_in_parent) = match parent.and_then(|p| self.nodes.get_mut(p)) {
+            Some(parent_node) => {
+                parent_node.children.push(index);
+                (parent_node.depth + 1, parent_node.children.len() - 1)
+            }
+            None => (0, 0),
+        };
+
+        let parent = parent.filter(|p| *p < index);
+        let mut path = parent.map(|p| self.path_key(p)).unwrap_or_default();
+        if parent.is_some() {
+            if !path.is_empty() {
+                path.push('/');
+            }
+            path.push_str(&index_in_parent.to_string());
+        }
+        element.path = Some(path);
+        element.role_path = Some(
+            parent
+                .and_then(|p| self.nodes.get(p))
+                .map(|parent| {
+                    let mut roles = parent.element.role_path.clone().unwrap_or_default();
+                    if !roles.is_empty() {
+                        roles.push('/');
+                    }
+                    roles.push_str(&parent.element.raw_role);
+                    roles
+                })
+                .unwrap_or_default(),
+        );
+
+        self.nodes.push(TreeNode {
+            element,
+            parent,
+            children: Vec::new(),
+            depth,
+            index_in_parent,
+        });
+
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn node(&self, index: usize) -> Option<&TreeNode> {
+        self.nodes.get(index)
+    }
+
+    pub fn nodes(&self) -> &[TreeNode] {
+        &self.nodes
+    }
+
+    /// Indices de los ancestros de un nodo, del padre hacia la raiz
+    pub fn ancestors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.node(index).and_then(|node| node.parent), |parent| {
+            self.node(*parent).and_then(|node| node.parent)
+        })
+    }
+
+    /// Ruta desde la raiz como posiciones entre hermanos (la raiz es `[]`)
+    pub fn path(&self, index: usize) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current = self.node(index);
+
+        while let Some(node) = current {
+            let Some(parent) = node.parent else {
+                break;
+            };
+            path.push(node.index_in_parent);
+            current = self.node(parent);
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Clave estable de la ruta, ej: "0/3/1". Sobrevive a re-escaneos mientras
+    /// la estructura del arbol no cambie.
+    pub fn path_key(&self, index: usize) -> String {
+        self.path(index)
+            .iter()
+            .map(|position| position.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Vista de los nodos cuyo rol es clickable, en orden BFS
+    pub fn clickable(&self) -> impl Iterator<Item = (usize, &TreeNode)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.element.role.is_clickable())
+    }
+
+    /// Lista plana de elementos clickables (copia)
+    #[cfg(test)]
+    pub fn clickable_elements(&self) -> Vec<UIElement> {
+        self.clickable()
+            .map(|(_, node)| node.element.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LogicalRect, Role};
+
+    fn make_element(id: &str, role: Role) -> UIElement {
+        UIElement::new(
+            id.to_string(),
+            role,
+            None,
+            LogicalRect::new(0.0, 0.0, 10.0, 10.0),
+        )
+    }
+
+    // window
+    // ├── toolbar
+    // │   ├── btn-a
+    // │   └── btn-b
+    // └── group
+    //     └── link
+    fn sample_tree() -> ElementTree {
+        let mut tree = ElementTree::new();
+        let window = tree.add_node(make_element("window", Role::Window), None);
+        let toolbar = tree.add_node(make_element("toolbar", Role::Toolbar), Some(window));
+        let group = tree.add_node(make_element("group", Role::Group), Some(window));
+        tree.add_node(make_element("btn-a", Role::Button), Some(toolbar));
+        tree.add_node(make_element("btn-b", Role::Button), Some(toolbar));
+        tree.add_node(make_element("link", Role::Link), Some(group));
+        tree
+    }
+
+    #[test]
+    fn test_parent_children_and_depth() {
+        let tree = sample_tree();
+        assert_eq!(tree.len(), 6);
+
+        let root = tree.node(0).unwrap();
+        assert_eq!(root.parent, None);
+        assert_eq!(root.children, vec![1, 2]);
+        assert_eq!(root.depth, 0);
+
+        let btn_b = tree.node(4).unwrap();
+        assert_eq!(btn_b.parent, Some(1));
+        assert_eq!(btn_b.depth, 2);
+        assert_eq!(btn_b.index_in_parent, 1);
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let tree = sample_tree();
+        let ancestors: Vec<usize> = tree.ancestors(5).collect();
+        assert_eq!(ancestors, vec![2, 0]);
+        assert_eq!(tree.ancestors(0).count(), 0);
+    }
+
+    #[test]
+    fn test_path_keys() {
+        let tree = sample_tree();
+        assert_eq!(tree.path_key(0), "");
+        assert_eq!(tree.path(4), vec![0, 1]);
+        assert_eq!(tree.path_key(4), "0/1");
+        assert_eq!(tree.path_key(5), "1/0");
+
+        for (index, node) in tree.nodes().iter().enumerate() {
+            assert_eq!(node.element.path, Some(tree.path_key(index)));
+        }
+        assert_eq!(tree.node(4).unwrap().element.stable_key(), "button|@0/1");
+        assert_eq!(
+            tree.node(4).unwrap().element.role_path.as_deref(),
+            Some("window/toolbar")
+        );
+        assert_eq!(tree.node(0).unwrap().element.role_path.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_stable_key_survives_new_siblings() {
+        let titled = UIElement::new(
+            "send".to_string(),
+            Role::Button,
+            Some("Send".to_string()),
+            LogicalRect::new(0.0, 0.0, 10.0, 10.0),
+        );
+        let mut identified = make_element("reply", Role::Button);
+        identified.identifier = Some("reply-button".to_string());
+
+        let scan = |extra_sibling: bool| {
+            let mut tree = ElementTree::new();
+            let window = tree.add_node(make_element("window", Role::Window), None);
+            if extra_sibling {
+                tree.add_node(make_element("banner", Role::Group), Some(window));
+            }
+            let toolbar = tree.add_node(make_element("toolbar", Role::Toolbar), Some(window));
+            let send = tree.add_node(titled.clone(), Some(toolbar));
+            let reply = tree.add_node(identified.clone(), Some(toolbar));
+            let keys = |index: usize| tree.node(index).unwrap().element.stable_key();
+            (keys(send), keys(reply))
+        };
+
+        let (send, reply) = scan(false);
+        assert_eq!(scan(true), (send.clone(), reply.clone()));
+        assert_eq!(send, "button||Send|window/toolbar");
+        assert_eq!(reply, "button|reply-button||window/toolbar");
+    }
+
+    #[test]
+    fn test_clickable_view() {
+        let tree = sample_tree();
+        let ids: Vec<String> = tree
+            .clickable_elements()
+            .into_iter()
+            .map(|el| el.id)
+            .collect();
+        assert_eq!(ids, vec!["btn-a", "btn-b", "link"]);
+    }
+}
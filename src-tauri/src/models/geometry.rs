@@ -0,0 +1,321 @@
+// Espacios de coordenadas.
+//
+// Cada tipo dice en que espacio y en que unidades esta su valor; las
+// conversiones entre espacios pasan siempre por `Screen`/`ScreenLayout`
+// (`models/screen_layout.rs`), que conocen el origen y la escala de cada
+// pantalla.
+//
+// - `LogicalPoint`/`LogicalRect`: espacio global del escritorio en puntos
+//   logicos, origen arriba/izquierda de la pantalla principal. Es el de
+//   AX, CGEvent y los hints; las pantallas a la izquierda o encima de la
+//   principal tienen coordenadas negativas.
+// - `ScreenPoint`/`ScreenRect`: puntos logicos relativos a la esquina
+//   superior izquierda de una pantalla (capas de la ventana de overlay).
+// - `PhysicalPoint`/`PhysicalSize`: pixeles fisicos del buffer de una
+//   pantalla (logico * `scale_factor`).
+// - `CocoaPoint`/`CocoaRect`: puntos logicos con origen abajo/izquierda de
+//   la pantalla principal y la Y hacia arriba (frames de `NSWindow`).
+
+use serde::{Deserialize, Serialize};
+
+/// Punto global del escritorio en puntos logicos (origen arriba/izquierda
+/// de la pantalla principal)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LogicalPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl LogicalPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance_to(&self, other: LogicalPoint) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// Rectangulo global del escritorio en puntos logicos (origen
+/// arriba/izquierda de la pantalla principal)
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LogicalRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl LogicalRect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width.max(0.0) * self.height.max(0.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+
+    pub fn center(&self) -> LogicalPoint {
+        LogicalPoint::new(self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Borde izquierdo y superior incluidos, derecho e inferior excluidos
+    pub fn contains_point(&self, point: LogicalPoint) -> bool {
+        point.x >= self.x && point.x < self.right() && point.y >= self.y && point.y < self.bottom()
+    }
+
+    /// Interseccion de dos rectangulos (None si no se solapan)
+    pub fn intersection(&self, other: &LogicalRect) -> Option<LogicalRect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        if right > x && bottom > y {
+            Some(LogicalRect::new(x, y, right - x, bottom - y))
+        } else {
+            None
+        }
+    }
+
+    pub fn intersection_area(&self, other: &LogicalRect) -> f64 {
+        self.intersection(other).map_or(0.0, |r| r.area())
+    }
+
+    /// Interseccion sobre union (0 = disjuntos, 1 = identicos)
+    pub fn iou(&self, other: &LogicalRect) -> f64 {
+        let intersection = self.intersection_area(other);
+        let union = self.area() + other.area() - intersection;
+        if union <= 0.0 {
+            0.0
+        } else {
+            intersection / union
+        }
+    }
+
+    /// Partes de `self` que quedan fuera de `other` (hasta 4 rectangulos
+    /// disjuntos: bandas superior e inferior y laterales)
+    pub fn subtract(&self, other: &LogicalRect) -> Vec<LogicalRect> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let mut pieces = Vec::with_capacity(4);
+        if cut.y > self.y {
+            pieces.push(LogicalRect::new(self.x, self.y, self.width, cut.y - self.y));
+        }
+        if cut.bottom() < self.bottom() {
+            pieces.push(LogicalRect::new(
+                self.x,
+                cut.bottom(),
+                self.width,
+                self.bottom() - cut.bottom(),
+            ));
+        }
+        if cut.x > self.x {
+            pieces.push(LogicalRect::new(self.x, cut.y, cut.x - self.x, cut.height));
+        }
+        if cut.right() < self.right() {
+            pieces.push(LogicalRect::new(
+                cut.right(),
+                cut.y,
+                self.right() - cut.right(),
+                cut.height,
+            ));
+        }
+        pieces
+    }
+
+    /// Fraccion del area de `self` que queda dentro de `other`
+    pub fn fraction_inside(&self, other: &LogicalRect) -> f64 {
+        let area = self.area();
+        if area <= 0.0 {
+            0.0
+        } else {
+            self.intersection_area(other) / area
+        }
+    }
+}
+
+/// Punto logico relativo a la esquina superior izquierda de una pantalla
+#[cfg(any(target_os = "macos", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScreenPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[cfg(any(target_os = "macos", test))]
+impl ScreenPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Rectangulo logico relativo a la esquina superior izquierda de una pantalla
+#[cfg(any(target_os = "macos", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScreenRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[cfg(any(target_os = "macos", test))]
+impl ScreenRect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Pixel fisico del buffer de una pantalla (origen arriba/izquierda)
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhysicalPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[cfg(test)]
+impl PhysicalPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Tamaño en pixeles fisicos
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhysicalSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[cfg(test)]
+impl PhysicalSize {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+}
+
+/// Punto logico con origen abajo/izquierda de la pantalla principal y la Y
+/// hacia arriba (AppKit)
+#[cfg(any(target_os = "macos", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CocoaPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[cfg(any(target_os = "macos", test))]
+impl CocoaPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Rectangulo logico en el espacio de AppKit; el origen es su esquina
+/// inferior izquierda
+#[cfg(any(target_os = "macos", test))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CocoaRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[cfg(any(target_os = "macos", test))]
+impl CocoaRect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn top(&self) -> f64 {
+        self.y + self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection() {
+        let a = LogicalRect::new(0.0, 0.0, 10.0, 10.0);
+        let b = LogicalRect::new(5.0, 5.0, 10.0, 10.0);
+        assert_eq!(
+            a.intersection(&b),
+            Some(LogicalRect::new(5.0, 5.0, 5.0, 5.0))
+        );
+
+        // Rectangulos que solo comparten un borde no se solapan
+        let c = LogicalRect::new(10.0, 0.0, 5.0, 5.0);
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn test_iou() {
+        let a = LogicalRect::new(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(a.iou(&a), 1.0);
+
+        let b = LogicalRect::new(5.0, 0.0, 10.0, 10.0);
+        // 50 / (100 + 100 - 50)
+        assert!((a.iou(&b) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(a.iou(&LogicalRect::new(20.0, 20.0, 5.0, 5.0)), 0.0);
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = LogicalRect::new(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(a.subtract(&LogicalRect::new(20.0, 0.0, 5.0, 5.0)), vec![a]);
+        assert!(a
+            .subtract(&LogicalRect::new(-1.0, -1.0, 12.0, 12.0))
+            .is_empty());
+
+        // Un hueco en el centro deja cuatro piezas que suman el area restante
+        let pieces = a.subtract(&LogicalRect::new(4.0, 4.0, 2.0, 2.0));
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(pieces.iter().map(|r| r.area()).sum::<f64>(), 96.0);
+        assert!(pieces
+            .iter()
+            .all(|r| !r.contains_point(LogicalPoint::new(5.0, 5.0))));
+    }
+
+    #[test]
+    fn test_fraction_inside() {
+        let outer = LogicalRect::new(0.0, 0.0, 100.0, 20.0);
+        let inner = LogicalRect::new(10.0, 5.0, 20.0, 10.0);
+        assert_eq!(inner.fraction_inside(&outer), 1.0);
+        assert_eq!(outer.fraction_inside(&inner), 0.1);
+    }
+}
@@ -1,20 +1,51 @@
 use serde::{Deserialize, Serialize};
 
+use super::{LogicalPoint, Role};
+
+/// Como se reparten los labels entre los elementos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelStrategy {
+    // Labels base-N en orden ("aa", "as", "ad"...)
+    #[default]
+    BaseN,
+    // Los labels mas faciles de teclear para los primeros elementos,
+    // evitando labels parecidos en elementos cercanos
+    Ergonomic,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hint {
     pub label: String,
-    pub x: f64,
-    pub y: f64,
+    // Anclaje del label (centro de la parte visible del elemento)
+    pub anchor: LogicalPoint,
+    // Punto donde se hace click al elegir el hint
+    pub click_point: LogicalPoint,
     pub element_id: String,
+    // Rol del elemento, para los estilos por rol
+    #[serde(default)]
+    pub role: Option<Role>,
 }
 
 impl Hint {
-    pub fn new(label: String, x: f64, y: f64, element_id: String) -> Self {
+    /// Hint cuyo punto de click coincide con el anclaje del label
+    pub fn new(label: String, anchor: LogicalPoint, element_id: String) -> Self {
         Self {
             label,
-            x,
-            y,
+            anchor,
+            click_point: anchor,
             element_id,
+            role: None,
         }
     }
+
+    pub fn with_role(mut self, role: Role) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn with_click_point(mut self, point: LogicalPoint) -> Self {
+        self.click_point = point;
+        self
+    }
 }
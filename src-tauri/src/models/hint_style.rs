@@ -0,0 +1,380 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::Role;
+
+/// Familia de la fuente empaquetada con la app
+pub const BUNDLED_FONT_FAMILY: &str = "DejaVu Sans Mono";
+
+/// Opacidad de los caracteres ya escritos de un label (sobre la del texto)
+#[cfg(any(target_os = "macos", test))]
+pub const TYPED_ALPHA: f64 = 0.4;
+
+/// Borde extra del label que queda como unico match
+#[cfg(any(target_os = "macos", test))]
+pub const UNIQUE_BORDER_EXTRA: f64 = 2.0;
+
+/// Color RGBA; se serializa como "#RRGGBB" o "#RRGGBBAA"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        // from_str_radix acepta un signo: "+1" no debe pasar por un canal
+        if !matches!(digits.len(), 6 | 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!(
+                "Invalid color '{}': expected #RRGGBB or #RRGGBBAA",
+                hex
+            ));
+        }
+
+        let channel = |index: usize| {
+            u8::from_str_radix(&digits[index..index + 2], 16).expect("validated hex digits")
+        };
+
+        Ok(Self {
+            r: channel(0),
+            g: channel(2),
+            b: channel(4),
+            a: if digits.len() == 8 { channel(6) } else { 255 },
+        })
+    }
+
+    pub fn to_hex(self) -> String {
+        if self.a == 255 {
+            format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    /// Componentes normalizados a 0..1 (r, g, b, a)
+    pub fn components(self) -> (f64, f64, f64, f64) {
+        let unit = |c: u8| c as f64 / 255.0;
+        (unit(self.r), unit(self.g), unit(self.b), unit(self.a))
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Color::from_hex(&value)
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.to_hex()
+    }
+}
+
+/// Estilo visual de la caja de un hint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HintStyle {
+    pub background: Color,
+    pub foreground: Color,
+    pub border_color: Color,
+    pub border_width: f64,
+    // Solo se admite la fuente empaquetada: el layout mide con sus metricas
+    pub font_family: String,
+    pub font_size: f64,
+    pub padding: f64,
+    pub corner_radius: f64,
+    // Opacidad de toda la caja (0..1)
+    pub opacity: f64,
+}
+
+impl Default for HintStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::rgb(0xFF, 0xD4, 0x3B),
+            foreground: Color::rgb(0x1A, 0x1A, 0x1A),
+            border_color: Color::rgb(0xC4, 0x9A, 0x00),
+            border_width: 1.0,
+            font_family: BUNDLED_FONT_FAMILY.to_string(),
+            font_size: 14.0,
+            padding: 6.0,
+            corner_radius: 4.0,
+            opacity: 0.95,
+        }
+    }
+}
+
+/// Cambios parciales sobre un `HintStyle`; los campos ausentes se heredan
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HintStyleOverride {
+    pub background: Option<Color>,
+    pub foreground: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_width: Option<f64>,
+    pub font_family: Option<String>,
+    pub font_size: Option<f64>,
+    pub padding: Option<f64>,
+    pub corner_radius: Option<f64>,
+    pub opacity: Option<f64>,
+}
+
+impl HintStyleOverride {
+    pub fn apply(&self, style: &mut HintStyle) {
+        if let Some(background) = self.background {
+            style.background = background;
+        }
+        if let Some(foreground) = self.foreground {
+            style.foreground = foreground;
+        }
+        if let Some(border_color) = self.border_color {
+            style.border_color = border_color;
+        }
+        if let Some(border_width) = self.border_width {
+            style.border_width = border_width;
+        }
+        if let Some(font_family) = &self.font_family {
+            style.font_family = font_family.clone();
+        }
+        if let Some(font_size) = self.font_size {
+            style.font_size = font_size;
+        }
+        if let Some(padding) = self.padding {
+            style.padding = padding;
+        }
+        if let Some(corner_radius) = self.corner_radius {
+            style.corner_radius = corner_radius;
+        }
+        if let Some(opacity) = self.opacity {
+            style.opacity = opacity.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Rechaza lo que el layout no puede usar: una familia distinta de la
+    /// empaquetada, tamaño de fuente no positivo, padding, borde o radio
+    /// negativos y opacidad fuera de 0..1
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(font_family) = &self.font_family {
+            if font_family != BUNDLED_FONT_FAMILY {
+                return Err(format!(
+                    "font_family must be '{}', the bundled font, got '{}'",
+                    BUNDLED_FONT_FAMILY, font_family
+                ));
+            }
+        }
+        if let Some(font_size) = self.font_size {
+            if !(font_size > 0.0 && font_size.is_finite()) {
+                return Err(format!("font_size must be positive, got {}", font_size));
+            }
+        }
+        let lengths = [
+            ("padding", self.padding),
+            ("border_width", self.border_width),
+            ("corner_radius", self.corner_radius),
+        ];
+        for (name, value) in lengths {
+            if let Some(value) = value {
+                if !(value >= 0.0 && value.is_finite()) {
+                    return Err(format!("{} must not be negative, got {}", name, value));
+                }
+            }
+        }
+        if let Some(opacity) = self.opacity {
+            if !(0.0..=1.0).contains(&opacity) {
+                return Err(format!("opacity must be between 0 and 1, got {}", opacity));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&HintStyle> for HintStyleOverride {
+    fn from(style: &HintStyle) -> Self {
+        Self {
+            background: Some(style.background),
+            foreground: Some(style.foreground),
+            border_color: Some(style.border_color),
+            border_width: Some(style.border_width),
+            font_family: Some(style.font_family.clone()),
+            font_size: Some(style.font_size),
+            padding: Some(style.padding),
+            corner_radius: Some(style.corner_radius),
+            opacity: Some(style.opacity),
+        }
+    }
+}
+
+/// Modo de hints: decide que click se hara al elegir uno
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintMode {
+    // Click izquierdo (hints amarillos)
+    #[default]
+    Left,
+    // Click derecho / middle / modificadores (hints azules)
+    Right,
+}
+
+/// Tema de los hints: estilo base con ajustes por modo y por rol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HintTheme {
+    pub base: HintStyle,
+    pub modes: HashMap<HintMode, HintStyleOverride>,
+    pub roles: HashMap<Role, HintStyleOverride>,
+}
+
+impl HintTheme {
+    /// Estilo final de un hint: base, despues el modo y por ultimo el rol
+    pub fn resolve(&self, mode: HintMode, role: Option<&Role>) -> HintStyle {
+        let mut style = self.base.clone();
+        if let Some(mode_override) = self.modes.get(&mode) {
+            mode_override.apply(&mut style);
+        }
+        if let Some(role_override) = role.and_then(|role| self.roles.get(role)) {
+            role_override.apply(&mut style);
+        }
+        style
+    }
+
+    /// Valida el estilo base y cada ajuste por modo y por rol; el error dice
+    /// cual falla
+    pub fn validate(&self) -> Result<(), String> {
+        HintStyleOverride::from(&self.base)
+            .validate()
+            .map_err(|e| format!("base style: {}", e))?;
+        for (mode, style) in &self.modes {
+            style
+                .validate()
+                .map_err(|e| format!("{:?} mode style: {}", mode, e))?;
+        }
+        for (role, style) in &self.roles {
+            style
+                .validate()
+                .map_err(|e| format!("{:?} role style: {}", role, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for HintTheme {
+    fn default() -> Self {
+        let right = HintStyleOverride {
+            background: Some(Color::rgb(0x3B, 0x82, 0xF6)),
+            foreground: Some(Color::rgb(0xFF, 0xFF, 0xFF)),
+            border_color: Some(Color::rgb(0x1D, 0x4E, 0xD8)),
+            ..Default::default()
+        };
+
+        Self {
+            base: HintStyle::default(),
+            modes: HashMap::from([(HintMode::Right, right)]),
+            roles: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_hex_roundtrip() {
+        let color = Color::from_hex("#3b82f6").unwrap();
+        assert_eq!(color, Color::rgb(0x3B, 0x82, 0xF6));
+        assert_eq!(color.to_hex(), "#3B82F6");
+
+        let translucent = Color::from_hex("00000080").unwrap();
+        assert_eq!(translucent.a, 0x80);
+        assert_eq!(translucent.to_hex(), "#00000080");
+
+        assert!(Color::from_hex("#12345").is_err());
+        assert!(Color::from_hex("#GGGGGG").is_err());
+        assert!(Color::from_hex("#+1+2+3").is_err());
+    }
+
+    #[test]
+    fn test_modes_use_plan_colors() {
+        let theme = HintTheme::default();
+
+        let left = theme.resolve(HintMode::Left, None);
+        let right = theme.resolve(HintMode::Right, None);
+        assert_eq!(left.background, HintStyle::default().background);
+        assert_eq!(right.background, Color::rgb(0x3B, 0x82, 0xF6));
+        // Lo que el modo no toca se hereda
+        assert_eq!(right.font_size, left.font_size);
+    }
+
+    #[test]
+    fn test_role_override_applies_after_mode() {
+        let theme: HintTheme = serde_json::from_str(
+            r##"{
+                "roles": { "link": { "background": "#22C55E", "font_size": 12 } }
+            }"##,
+        )
+        .unwrap();
+
+        let style = theme.resolve(HintMode::Right, Some(&Role::Link));
+        assert_eq!(style.background, Color::rgb(0x22, 0xC5, 0x5E));
+        assert_eq!(style.foreground, Color::rgb(0xFF, 0xFF, 0xFF));
+        assert_eq!(style.font_size, 12.0);
+
+        let button = theme.resolve(HintMode::Right, Some(&Role::Button));
+        assert_eq!(button.background, Color::rgb(0x3B, 0x82, 0xF6));
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_metrics() {
+        assert!(HintTheme::default().validate().is_ok());
+
+        let mut theme = HintTheme::default();
+        theme.base.font_size = 0.0;
+        assert!(theme.validate().unwrap_err().contains("font_size"));
+
+        let mut theme = HintTheme::default();
+        theme.roles.insert(
+            Role::Link,
+            HintStyleOverride {
+                padding: Some(-1.0),
+                ..Default::default()
+            },
+        );
+        assert!(theme.validate().unwrap_err().contains("padding"));
+
+        let mut theme = HintTheme::default();
+        theme.modes.insert(
+            HintMode::Left,
+            HintStyleOverride {
+                opacity: Some(1.5),
+                ..Default::default()
+            },
+        );
+        assert!(theme.validate().unwrap_err().contains("opacity"));
+
+        // El layout no sabe medir otra fuente
+        let mut theme = HintTheme::default();
+        theme.roles.insert(
+            Role::Button,
+            HintStyleOverride {
+                font_family: Some("Helvetica".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(theme.validate().unwrap_err().contains("font_family"));
+    }
+
+    #[test]
+    fn test_invalid_color_fails_deserialization() {
+        let result: Result<HintStyleOverride, _> =
+            serde_json::from_str(r#"{ "background": "yellow" }"#);
+        assert!(result.is_err());
+    }
+}
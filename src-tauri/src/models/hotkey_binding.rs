@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::KeySequence;
+
+/// Accion que dispara un hotkey global
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    // Hints para click izquierdo
+    ActivateHints,
+    // Hints para click derecho
+    RightClickHints,
+    // Modo scroll
+    ScrollMode,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 3] = [
+        HotkeyAction::ActivateHints,
+        HotkeyAction::RightClickHints,
+        HotkeyAction::ScrollMode,
+    ];
+
+    /// Evento que se emite al frontend cuando se pulsa el hotkey
+    pub fn event_name(self) -> &'static str {
+        match self {
+            HotkeyAction::ActivateHints => "hotkey-activate-hints",
+            HotkeyAction::RightClickHints => "hotkey-right-click-hints",
+            HotkeyAction::ScrollMode => "hotkey-scroll-mode",
+        }
+    }
+}
+
+/// Combo asignado a una accion: un hotkey ("Cmd+J") o una secuencia con
+/// leader ("Cmd+J F")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    pub combo: KeySequence,
+}
+
+impl HotkeyBinding {
+    pub fn new(action: HotkeyAction, combo: KeySequence) -> Self {
+        Self { action, combo }
+    }
+
+    /// Atajos por defecto
+    pub fn defaults() -> Vec<HotkeyBinding> {
+        [
+            (HotkeyAction::ActivateHints, "Cmd+J"),
+            (HotkeyAction::RightClickHints, "Cmd+Shift+J"),
+            (HotkeyAction::ScrollMode, "Cmd+Alt+J"),
+        ]
+        .into_iter()
+        .filter_map(|(action, combo)| {
+            KeySequence::parse(combo)
+                .ok()
+                .map(|combo| HotkeyBinding::new(action, combo))
+        })
+        .collect()
+    }
+}
+
+/// Por que un combo no esta registrado
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HotkeyConflict {
+    // Ya lo tiene otra accion de la app
+    AlreadyBound { action: HotkeyAction },
+    // Lo usa el sistema (cambiar de app, Spotlight...); no se intenta registrar
+    Reserved { reason: &'static str },
+    // El SO lo rechazo, normalmente porque otra app ya lo tiene
+    RejectedByOs { message: String },
+}
+
+impl fmt::Display for HotkeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotkeyConflict::AlreadyBound { action } => {
+                write!(f, "already bound to {:?}", action)
+            }
+            HotkeyConflict::Reserved { reason } => write!(f, "reserved by the system ({})", reason),
+            HotkeyConflict::RejectedByOs { message } => {
+                write!(f, "rejected by the system: {}", message)
+            }
+        }
+    }
+}
+
+/// Estado en vivo del hotkey de una accion
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HotkeyStatus {
+    pub action: HotkeyAction,
+    // Combo asignado (o que se intento asignar); None si la accion no tiene
+    pub combo: Option<KeySequence>,
+    pub registered: bool,
+    // Por que fallo el ultimo intento de asignar `rejected_combo`. Si la
+    // accion conservo su combo anterior, `registered` sigue a true
+    pub conflict: Option<HotkeyConflict>,
+    pub rejected_combo: Option<KeySequence>,
+}
+
+impl HotkeyStatus {
+    pub fn registered(binding: HotkeyBinding) -> Self {
+        Self {
+            action: binding.action,
+            combo: Some(binding.combo),
+            registered: true,
+            conflict: None,
+            rejected_combo: None,
+        }
+    }
+
+    pub fn failed(binding: HotkeyBinding, conflict: HotkeyConflict) -> Self {
+        Self {
+            action: binding.action,
+            combo: Some(binding.combo.clone()),
+            registered: false,
+            conflict: Some(conflict),
+            rejected_combo: Some(binding.combo),
+        }
+    }
+
+    /// Anota un rebinding fallido sobre una accion que sigue registrada
+    pub fn with_rejected(mut self, combo: KeySequence, conflict: HotkeyConflict) -> Self {
+        self.conflict = Some(conflict);
+        self.rejected_combo = Some(combo);
+        self
+    }
+
+    pub fn unbound(action: HotkeyAction) -> Self {
+        Self {
+            action,
+            combo: None,
+            registered: false,
+            conflict: None,
+            rejected_combo: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_cover_every_action() {
+        let defaults = HotkeyBinding::defaults();
+        for action in HotkeyAction::ALL {
+            assert_eq!(
+                defaults.iter().filter(|b| b.action == action).count(),
+                1,
+                "{:?}",
+                action
+            );
+        }
+    }
+
+    #[test]
+    fn test_binding_serializes_combo_as_text() {
+        let binding = HotkeyBinding::new(
+            HotkeyAction::RightClickHints,
+            KeySequence::parse("Ctrl+Shift+J").unwrap(),
+        );
+        let json = serde_json::to_string(&binding).unwrap();
+        assert_eq!(
+            json,
+            r#"{"action":"right_click_hints","combo":"Ctrl+Shift+J"}"#
+        );
+    }
+
+    #[test]
+    fn test_failed_status_serializes_conflict() {
+        let binding = HotkeyBinding::new(
+            HotkeyAction::ScrollMode,
+            KeySequence::parse("Ctrl+Alt+J").unwrap(),
+        );
+        let status = HotkeyStatus::failed(
+            binding,
+            HotkeyConflict::AlreadyBound {
+                action: HotkeyAction::ActivateHints,
+            },
+        );
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["registered"], false);
+        assert_eq!(json["conflict"]["kind"], "already_bound");
+        assert_eq!(json["conflict"]["action"], "activate_hints");
+    }
+}
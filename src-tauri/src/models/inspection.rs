@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+use super::ElementTree;
+use super::UIElement;
+
+/// Decision del filtro sobre un elemento: se conserva o se descarta por una regla
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "lowercase")]
+pub enum FilterDecision {
+    Kept,
+    Dropped {
+        // Regla que descarto el elemento (ej: "role", "size")
+        rule: String,
+        // Explicacion legible (ej: "too small 4x4 < MIN_SIZE")
+        reason: String,
+    },
+}
+
+impl FilterDecision {
+    pub fn dropped(rule: &str, reason: impl Into<String>) -> Self {
+        FilterDecision::Dropped {
+            rule: rule.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Explicacion del filtro para un elemento concreto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterExplanation {
+    pub element: UIElement,
+    pub decision: FilterDecision,
+}
+
+/// Nodo del arbol con todos sus atributos nativos y la decision del filtro
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InspectedNode {
+    pub element: UIElement,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub depth: usize,
+    // Clave de ruta en el arbol (ej: "0/3/1")
+    pub path: String,
+    // Nombre de atributo nativo -> valor legible (None si no se pudo representar)
+    pub attributes: BTreeMap<String, Option<String>>,
+    pub decision: FilterDecision,
+}
+
+/// Volcado completo del arbol de la ventana activa.
+///
+/// Se serializa a JSON para depurar por que un elemento no recibe hint y
+/// el mismo archivo sirve como fixture para tests sin accesibilidad real.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InspectedTree {
+    pub nodes: Vec<InspectedNode>,
+}
+
+impl InspectedTree {
+    /// Reconstruye el `ElementTree` a partir del volcado
+    #[cfg(test)]
+    pub fn to_element_tree(&self) -> ElementTree {
+        let mut tree = ElementTree::new();
+        for node in &self.nodes {
+            tree.add_node(node.element.clone(), node.parent);
+        }
+        tree
+    }
+}
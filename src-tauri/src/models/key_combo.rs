@@ -0,0 +1,576 @@
+use keyboard_types::Code;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Tecla modificadora, en el orden en que se escribe en texto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModifierKey {
+    Control,
+    Alt,
+    Shift,
+    // Cmd en macOS, Super/Win en el resto
+    Meta,
+}
+
+/// Lado del teclado de un modificador
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeySide {
+    Either,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifier {
+    pub key: ModifierKey,
+    pub side: KeySide,
+}
+
+/// Notacion para mostrar un combo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyNotation {
+    // Simbolos de macOS: "⌘⇧J"
+    Mac,
+    // Texto: "Ctrl+Shift+J"
+    Pc,
+}
+
+impl KeyNotation {
+    /// Notacion de la plataforma actual
+    pub fn native() -> Self {
+        if cfg!(target_os = "macos") {
+            KeyNotation::Mac
+        } else {
+            KeyNotation::Pc
+        }
+    }
+}
+
+/// Combinacion de teclas: modificadores y una sola tecla.
+///
+/// La tecla es un `Code` W3C (posicion fisica, independiente del layout).
+/// Se serializa como texto canonico ("Ctrl+Shift+J"), que vuelve a
+/// parsearse igual en cualquier plataforma.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyCombo {
+    // Ordenados por `ModifierKey`, sin repetidos
+    modifiers: Vec<Modifier>,
+    key: Code,
+}
+
+// Teclas con nombre: (codigo, nombre en texto, simbolo macOS, alias en minusculas)
+const NAMED_KEYS: &[(Code, &str, &str, &[&str])] = &[
+    (Code::Space, "Space", "Space", &["space", "spacebar"]),
+    (Code::Tab, "Tab", "⇥", &["tab", "⇥"]),
+    (Code::Escape, "Esc", "⎋", &["esc", "escape", "⎋"]),
+    (Code::Enter, "Enter", "↩", &["enter", "return", "↩"]),
+    (Code::Backspace, "Backspace", "⌫", &["backspace", "⌫"]),
+    (Code::Delete, "Delete", "⌦", &["delete", "del", "⌦"]),
+    (Code::Insert, "Insert", "Insert", &["insert", "ins"]),
+    (Code::Home, "Home", "↖", &["home", "↖"]),
+    (Code::End, "End", "↘", &["end", "↘"]),
+    (Code::PageUp, "PageUp", "⇞", &["pageup", "pgup", "⇞"]),
+    (Code::PageDown, "PageDown", "⇟", &["pagedown", "pgdn", "⇟"]),
+    (Code::ArrowUp, "Up", "↑", &["up", "arrowup", "↑"]),
+    (Code::ArrowDown, "Down", "↓", &["down", "arrowdown", "↓"]),
+    (Code::ArrowLeft, "Left", "←", &["left", "arrowleft", "←"]),
+    (
+        Code::ArrowRight,
+        "Right",
+        "→",
+        &["right", "arrowright", "→"],
+    ),
+    (Code::Minus, "-", "-", &["-", "minus"]),
+    (Code::Equal, "=", "=", &["=", "equal", "equals"]),
+    (Code::BracketLeft, "[", "[", &["[", "bracketleft"]),
+    (Code::BracketRight, "]", "]", &["]", "bracketright"]),
+    (Code::Backslash, "\\", "\\", &["\\", "backslash"]),
+    (Code::Semicolon, ";", ";", &[";", "semicolon"]),
+    (Code::Quote, "'", "'", &["'", "quote", "apostrophe"]),
+    (Code::Comma, ",", ",", &[",", "comma"]),
+    (Code::Period, ".", ".", &[".", "period", "dot"]),
+    (Code::Slash, "/", "/", &["/", "slash"]),
+    (
+        Code::Backquote,
+        "`",
+        "`",
+        &["`", "backquote", "grave", "backtick"],
+    ),
+];
+
+// Modificadores: (tecla, alias en minusculas, simbolo macOS)
+const MODIFIER_NAMES: &[(ModifierKey, &[&str], char)] = &[
+    (ModifierKey::Control, &["ctrl", "control"], '⌃'),
+    (ModifierKey::Alt, &["alt", "option", "opt"], '⌥'),
+    (ModifierKey::Shift, &["shift"], '⇧'),
+    (
+        ModifierKey::Meta,
+        &["cmd", "command", "meta", "super", "win"],
+        '⌘',
+    ),
+];
+
+// Orden de los simbolos en notacion Mac ("⌘⇧J")
+const MAC_ORDER: [ModifierKey; 4] = [
+    ModifierKey::Control,
+    ModifierKey::Alt,
+    ModifierKey::Meta,
+    ModifierKey::Shift,
+];
+
+// Teclas de funcion soportadas (F1..F24)
+const MAX_FUNCTION_KEY: u8 = 24;
+
+impl KeyCombo {
+    pub fn new(mut modifiers: Vec<Modifier>, key: Code) -> Self {
+        modifiers.sort_by_key(|modifier| modifier.key);
+        modifiers.dedup_by_key(|modifier| modifier.key);
+        Self { modifiers, key }
+    }
+
+    /// Parsea "Cmd+Shift+J", "ctrl + alt + F5", "RightShift+Space" o "⌘⇧J".
+    ///
+    /// Sin distinguir mayusculas. Los modificadores admiten prefijo de lado
+    /// ("Left"/"Right", "L"/"R"). Los tokens desconocidos, los modificadores
+    /// repetidos o una segunda tecla son error y el mensaje dice cual.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut modifiers: Vec<Modifier> = Vec::new();
+        let mut key: Option<(Code, &str)> = None;
+
+        for (index, raw) in input.split('+').enumerate() {
+            let position = index + 1;
+            let (glyphs, token) = split_modifier_symbols(raw.trim());
+            if glyphs.is_empty() && token.is_empty() {
+                return Err(format!(
+                    "Invalid hotkey '{}': empty token at position {}",
+                    input, position
+                ));
+            }
+
+            let named = parse_modifier(token);
+            for modifier in glyphs.into_iter().chain(named) {
+                if modifiers.iter().any(|m| m.key == modifier.key) {
+                    return Err(format!(
+                        "Invalid hotkey '{}': duplicate modifier '{}' at position {}",
+                        input,
+                        raw.trim(),
+                        position
+                    ));
+                }
+                modifiers.push(modifier);
+            }
+            if token.is_empty() || named.is_some() {
+                continue;
+            }
+
+            let code = parse_key(token).ok_or_else(|| {
+                format!(
+                    "Invalid hotkey '{}': unknown key '{}' at position {}",
+                    input, token, position
+                )
+            })?;
+            if let Some((_, first)) = key {
+                return Err(format!(
+                    "Invalid hotkey '{}': second key '{}' at position {} (already has '{}')",
+                    input, token, position, first
+                ));
+            }
+            key = Some((code, token));
+        }
+
+        let (key, _) = key.ok_or_else(|| format!("Invalid hotkey '{}': no key found", input))?;
+        Ok(Self::new(modifiers, key))
+    }
+
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    pub fn key(&self) -> Code {
+        self.key
+    }
+
+    /// Texto para mostrar: "⌘⇧J" en notacion Mac, "Ctrl+Shift+J" en Pc
+    pub fn format(&self, notation: KeyNotation) -> String {
+        match notation {
+            KeyNotation::Mac => {
+                let mut text = String::new();
+                for key in MAC_ORDER {
+                    if let Some(modifier) = self.modifiers.iter().find(|m| m.key == key) {
+                        text.push_str(side_prefix(modifier.side, "L", "R"));
+                        text.push(modifier_symbol(key));
+                    }
+                }
+                text.push_str(&key_name(self.key, KeyNotation::Mac));
+                text
+            }
+            KeyNotation::Pc => {
+                let mut parts: Vec<String> = self
+                    .modifiers
+                    .iter()
+                    .map(|modifier| {
+                        format!(
+                            "{}{}",
+                            side_prefix(modifier.side, "Left", "Right"),
+                            modifier_text(modifier.key)
+                        )
+                    })
+                    .collect();
+                parts.push(key_name(self.key, KeyNotation::Pc));
+                parts.join("+")
+            }
+        }
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        KeyCombo::parse(input)
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(KeyNotation::native()))
+    }
+}
+
+impl TryFrom<String> for KeyCombo {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        KeyCombo::parse(&value)
+    }
+}
+
+impl From<KeyCombo> for String {
+    fn from(combo: KeyCombo) -> Self {
+        combo.format(KeyNotation::Pc)
+    }
+}
+
+/// Separa los simbolos de modificador pegados al principio ("⌘⇧J"). Cada
+/// simbolo admite delante "L" o "R" para el lado, como los escribe `format`
+/// ("L⌥R⇧J"); una "L" o "R" sin simbolo detras es la tecla
+fn split_modifier_symbols(token: &str) -> (Vec<Modifier>, &str) {
+    let mut modifiers = Vec::new();
+    let mut rest = token;
+    loop {
+        let mut chars = rest.chars();
+        let Some(first) = chars.next() else {
+            break;
+        };
+        let side = match first {
+            'L' | 'l' => KeySide::Left,
+            'R' | 'r' => KeySide::Right,
+            _ => KeySide::Either,
+        };
+        let symbol = if side == KeySide::Either {
+            Some(first)
+        } else {
+            chars.next()
+        };
+        let Some(&(key, _, _)) = MODIFIER_NAMES
+            .iter()
+            .find(|(_, _, glyph)| Some(*glyph) == symbol)
+        else {
+            break;
+        };
+        modifiers.push(Modifier { key, side });
+        rest = chars.as_str().trim_start();
+    }
+    (modifiers, rest)
+}
+
+fn parse_modifier(token: &str) -> Option<Modifier> {
+    let lower = token.to_lowercase();
+    let (side, name) = [
+        ("left", KeySide::Left),
+        ("right", KeySide::Right),
+        ("l", KeySide::Left),
+        ("r", KeySide::Right),
+    ]
+    .iter()
+    .find_map(|(prefix, side)| {
+        let name = lower.strip_prefix(prefix)?;
+        let name = name.strip_prefix(['-', '_', ' ']).unwrap_or(name);
+        find_modifier(name).map(|_| (*side, name.to_string()))
+    })
+    .unwrap_or((KeySide::Either, lower));
+
+    find_modifier(&name).map(|key| Modifier { key, side })
+}
+
+fn find_modifier(name: &str) -> Option<ModifierKey> {
+    MODIFIER_NAMES
+        .iter()
+        .find(|(_, aliases, _)| aliases.contains(&name))
+        .map(|(key, _, _)| *key)
+}
+
+fn parse_key(token: &str) -> Option<Code> {
+    let lower = token.to_lowercase();
+
+    if let Some(&(code, _, _, _)) = NAMED_KEYS
+        .iter()
+        .find(|(_, _, _, aliases)| aliases.contains(&lower.as_str()))
+    {
+        return Some(code);
+    }
+
+    let mut chars = token.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if ch.is_ascii_alphabetic() {
+            return Code::from_str(&format!("Key{}", ch.to_ascii_uppercase())).ok();
+        }
+        if ch.is_ascii_digit() {
+            return Code::from_str(&format!("Digit{}", ch)).ok();
+        }
+    }
+
+    if let Some(number) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        if (1..=MAX_FUNCTION_KEY).contains(&number) {
+            return Code::from_str(&format!("F{}", number)).ok();
+        }
+        return None;
+    }
+
+    // Nombre W3C exacto ("NumpadAdd", "KeyJ"); los modificadores no son tecla
+    Code::from_str(token)
+        .ok()
+        .filter(|code| !is_modifier_code(*code) && *code != Code::Unidentified)
+}
+
+fn is_modifier_code(code: Code) -> bool {
+    matches!(
+        code,
+        Code::ShiftLeft
+            | Code::ShiftRight
+            | Code::ControlLeft
+            | Code::ControlRight
+            | Code::AltLeft
+            | Code::AltRight
+            | Code::MetaLeft
+            | Code::MetaRight
+    )
+}
+
+fn key_name(code: Code, notation: KeyNotation) -> String {
+    if let Some((_, text, symbol, _)) = NAMED_KEYS.iter().find(|(named, ..)| *named == code) {
+        return match notation {
+            KeyNotation::Mac => symbol.to_string(),
+            KeyNotation::Pc => text.to_string(),
+        };
+    }
+
+    // "KeyJ" -> "J", "Digit1" -> "1"; el resto con su nombre W3C
+    let name = code.to_string();
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .filter(|rest| rest.len() == 1)
+        .map(str::to_string)
+        .unwrap_or(name)
+}
+
+fn modifier_symbol(key: ModifierKey) -> char {
+    MODIFIER_NAMES
+        .iter()
+        .find(|(named, _, _)| *named == key)
+        .map_or('?', |(_, _, symbol)| *symbol)
+}
+
+fn modifier_text(key: ModifierKey) -> &'static str {
+    match key {
+        ModifierKey::Control => "Ctrl",
+        ModifierKey::Alt => "Alt",
+        ModifierKey::Shift => "Shift",
+        ModifierKey::Meta => {
+            if cfg!(target_os = "macos") {
+                "Cmd"
+            } else {
+                "Super"
+            }
+        }
+    }
+}
+
+fn side_prefix<'a>(side: KeySide, left: &'a str, right: &'a str) -> &'a str {
+    match side {
+        KeySide::Either => "",
+        KeySide::Left => left,
+        KeySide::Right => right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(input: &str) -> KeyCombo {
+        KeyCombo::parse(input).unwrap()
+    }
+
+    fn has_modifier(combo: &KeyCombo, key: ModifierKey) -> bool {
+        combo.modifiers().iter().any(|modifier| modifier.key == key)
+    }
+
+    #[test]
+    fn test_parses_letters_and_modifiers() {
+        let parsed = combo("Cmd+Shift+J");
+        assert!(has_modifier(&parsed, ModifierKey::Meta));
+        assert!(has_modifier(&parsed, ModifierKey::Shift));
+        assert!(!has_modifier(&parsed, ModifierKey::Control));
+        assert_eq!(parsed.key(), Code::KeyJ);
+
+        // Sin distinguir mayusculas ni espacios, en cualquier orden
+        assert_eq!(combo(" shift + j + command "), parsed);
+        assert_eq!(combo("J").modifiers(), &[]);
+    }
+
+    #[test]
+    fn test_parse_hotkey_single_modifier() {
+        let parsed = combo("Cmd+K");
+        assert!(has_modifier(&parsed, ModifierKey::Meta));
+        assert!(!has_modifier(&parsed, ModifierKey::Shift));
+        assert_eq!(parsed.key(), Code::KeyK);
+    }
+
+    #[test]
+    fn test_parse_hotkey_invalid() {
+        let result = KeyCombo::parse("Invalid");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_hotkey_creates_valid_hotkey() {
+        // Just verify that parsing doesn't panic and returns Ok
+        let result = KeyCombo::parse("Cmd+Shift+J");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parses_full_key_grammar() {
+        let keys = [
+            ("Ctrl+1", Code::Digit1),
+            ("Alt+F5", Code::F5),
+            ("Ctrl+F24", Code::F24),
+            ("Cmd+Space", Code::Space),
+            ("Alt+Tab", Code::Tab),
+            ("Esc", Code::Escape),
+            ("Shift+Up", Code::ArrowUp),
+            ("Ctrl+ArrowLeft", Code::ArrowLeft),
+            ("Cmd+,", Code::Comma),
+            ("Ctrl+/", Code::Slash),
+            ("Ctrl+Minus", Code::Minus),
+            ("Alt+`", Code::Backquote),
+            ("Ctrl+PgDn", Code::PageDown),
+            ("Ctrl+NumpadAdd", Code::NumpadAdd),
+        ];
+        for (input, code) in keys {
+            assert_eq!(combo(input).key(), code, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_side_specific_modifiers() {
+        let parsed = combo("RightShift+LCtrl+K");
+        assert_eq!(
+            parsed.modifiers(),
+            &[
+                Modifier {
+                    key: ModifierKey::Control,
+                    side: KeySide::Left,
+                },
+                Modifier {
+                    key: ModifierKey::Shift,
+                    side: KeySide::Right,
+                },
+            ]
+        );
+        // "Right" solo es la flecha, no un lado
+        assert_eq!(combo("Cmd+Right").key(), Code::ArrowRight);
+    }
+
+    #[test]
+    fn test_errors_point_at_bad_token() {
+        let error = KeyCombo::parse("Cmd+Foo+J").unwrap_err();
+        assert!(
+            error.contains("unknown key 'Foo' at position 2"),
+            "{}",
+            error
+        );
+
+        let error = KeyCombo::parse("Cmd+J+K").unwrap_err();
+        assert!(error.contains("second key 'K' at position 3"), "{}", error);
+
+        let error = KeyCombo::parse("Ctrl+Control+J").unwrap_err();
+        assert!(
+            error.contains("duplicate modifier 'Control' at position 2"),
+            "{}",
+            error
+        );
+
+        let error = KeyCombo::parse("Cmd++J").unwrap_err();
+        assert!(error.contains("empty token at position 2"), "{}", error);
+
+        assert!(KeyCombo::parse("Cmd+Shift").is_err());
+        assert!(KeyCombo::parse("Ctrl+F25").is_err());
+        assert!(KeyCombo::parse("Ctrl+ShiftLeft").is_err());
+    }
+
+    #[test]
+    fn test_canonical_format() {
+        let parsed = combo("shift+cmd+j");
+        assert_eq!(parsed.format(KeyNotation::Mac), "⌘⇧J");
+        assert!(parsed.format(KeyNotation::Pc).ends_with("+J"));
+        assert_eq!(
+            combo("shift+ctrl+j").format(KeyNotation::Pc),
+            "Ctrl+Shift+J"
+        );
+        assert_eq!(combo("alt+up").format(KeyNotation::Mac), "⌥↑");
+        assert_eq!(
+            combo("rshift+f12").format(KeyNotation::Pc),
+            "RightShift+F12"
+        );
+    }
+
+    #[test]
+    fn test_formats_parse_back() {
+        for input in [
+            "Cmd+Shift+J",
+            "Ctrl+Alt+Delete",
+            "LeftAlt+Space",
+            "Ctrl+;",
+            "Super+9",
+            "RightShift+Cmd+R",
+            "Ctrl+RightAlt+L",
+        ] {
+            let parsed = combo(input);
+            for notation in [KeyNotation::Pc, KeyNotation::Mac] {
+                let text = parsed.format(notation);
+                assert_eq!(combo(&text), parsed, "{} -> {}", input, text);
+            }
+        }
+        assert_eq!(combo("LeftAlt+Space").format(KeyNotation::Mac), "L⌥Space");
+        assert_eq!(combo("L⌥R⇧J"), combo("LeftAlt+RightShift+J"));
+        // Sin simbolo detras, "L" y "R" son teclas
+        assert_eq!(combo("⌘R"), combo("Cmd+R"));
+        // Los simbolos tambien se parsean, pegados o separados por '+'
+        assert_eq!(combo("⌘⇧J"), combo("Cmd+Shift+J"));
+        assert_eq!(combo("⌃+⌥+→"), combo("Ctrl+Alt+Right"));
+    }
+
+    #[test]
+    fn test_serde_uses_text_form() {
+        let parsed = combo("Ctrl+Shift+J");
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert_eq!(json, "\"Ctrl+Shift+J\"");
+        let back: KeyCombo = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, parsed);
+        assert!(serde_json::from_str::<KeyCombo>("\"Cmd+Foo\"").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::{KeyCombo, KeyNotation};
+
+/// Pasos maximos de una secuencia (leader incluido)
+const MAX_STEPS: usize = 4;
+
+/// Secuencia de combos estilo Emacs: "Cmd+J F" es pulsar Cmd+J y luego F.
+///
+/// El primer paso es el leader, el unico que se registra como hotkey
+/// global; un solo paso es un hotkey normal. Se serializa como texto con
+/// los pasos separados por espacios ("Cmd+J F").
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeySequence {
+    // Nunca vacio
+    steps: Vec<KeyCombo>,
+}
+
+impl KeySequence {
+    /// Parsea los pasos separados por espacios: "Cmd+J F", "⌘J S" o
+    /// "ctrl + alt + J F" (los espacios junto a un `+` no separan pasos)
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut tokens: Vec<String> = Vec::new();
+        for token in input.split_whitespace() {
+            match tokens.last_mut() {
+                Some(last) if last.ends_with('+') || token.starts_with('+') => last.push_str(token),
+                _ => tokens.push(token.to_string()),
+            }
+        }
+
+        if tokens.is_empty() {
+            return Err(format!("Invalid hotkey sequence '{}': empty", input));
+        }
+        if tokens.len() > MAX_STEPS {
+            return Err(format!(
+                "Invalid hotkey sequence '{}': {} steps (max {})",
+                input,
+                tokens.len(),
+                MAX_STEPS
+            ));
+        }
+
+        let steps = tokens
+            .iter()
+            .enumerate()
+            .map(|(index, token)| {
+                KeyCombo::parse(token).map_err(|e| {
+                    format!(
+                        "Invalid hotkey sequence '{}': step {}: {}",
+                        input,
+                        index + 1,
+                        e
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { steps })
+    }
+
+    /// Pasos en orden; el primero es el leader
+    pub fn steps(&self) -> &[KeyCombo] {
+        &self.steps
+    }
+
+    /// Texto para mostrar, con cada paso en la notacion dada
+    pub fn format(&self, notation: KeyNotation) -> String {
+        self.steps
+            .iter()
+            .map(|step| step.format(notation))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl From<KeyCombo> for KeySequence {
+    fn from(combo: KeyCombo) -> Self {
+        Self { steps: vec![combo] }
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        KeySequence::parse(input)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(KeyNotation::native()))
+    }
+}
+
+impl TryFrom<String> for KeySequence {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        KeySequence::parse(&value)
+    }
+}
+
+impl From<KeySequence> for String {
+    fn from(sequence: KeySequence) -> Self {
+        sequence.format(KeyNotation::Pc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_leader_sequence() {
+        let sequence = KeySequence::parse("Cmd+J f").unwrap();
+        assert_eq!(sequence.steps().len(), 2);
+        assert_eq!(sequence.steps()[0], KeyCombo::parse("Cmd+J").unwrap());
+        assert_eq!(sequence.steps()[1], KeyCombo::parse("F").unwrap());
+        assert_eq!(sequence.format(KeyNotation::Mac), "⌘J F");
+    }
+
+    #[test]
+    fn test_spaces_around_plus_stay_in_one_step() {
+        let sequence = KeySequence::parse("ctrl + alt + J  s").unwrap();
+        assert_eq!(sequence.steps()[0], KeyCombo::parse("Ctrl+Alt+J").unwrap());
+        assert_eq!(sequence.format(KeyNotation::Pc), "Ctrl+Alt+J S");
+    }
+
+    #[test]
+    fn test_single_combo_is_a_sequence() {
+        let sequence = KeySequence::parse("Ctrl+Shift+J").unwrap();
+        assert_eq!(
+            sequence,
+            KeySequence::from(KeyCombo::parse("Ctrl+Shift+J").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        assert!(KeySequence::parse("   ").unwrap_err().contains("empty"));
+        assert!(KeySequence::parse("Cmd+J Foo")
+            .unwrap_err()
+            .contains("step 2: Invalid hotkey 'Foo': unknown key 'Foo'"));
+        assert!(KeySequence::parse("Cmd+J A B C D")
+            .unwrap_err()
+            .contains("5 steps (max 4)"));
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+
+use keyboard_types::Code;
+use serde::{Deserialize, Serialize};
+
+/// Posiciones fisicas de las tres filas de letras, de izquierda a derecha
+const ROW_CODES: [[Code; 10]; 3] = [
+    [
+        Code::KeyQ,
+        Code::KeyW,
+        Code::KeyE,
+        Code::KeyR,
+        Code::KeyT,
+        Code::KeyY,
+        Code::KeyU,
+        Code::KeyI,
+        Code::KeyO,
+        Code::KeyP,
+    ],
+    [
+        Code::KeyA,
+        Code::KeyS,
+        Code::KeyD,
+        Code::KeyF,
+        Code::KeyG,
+        Code::KeyH,
+        Code::KeyJ,
+        Code::KeyK,
+        Code::KeyL,
+        Code::Semicolon,
+    ],
+    [
+        Code::KeyZ,
+        Code::KeyX,
+        Code::KeyC,
+        Code::KeyV,
+        Code::KeyB,
+        Code::KeyN,
+        Code::KeyM,
+        Code::Comma,
+        Code::Period,
+        Code::Slash,
+    ],
+];
+
+/// Layouts de teclado con preset de hints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    // Aleman, suizo, checo...
+    Qwertz,
+    // Frances, belga
+    Azerty,
+    Dvorak,
+    Colemak,
+    ColemakDh,
+    Workman,
+    // Dvorak frances
+    Bepo,
+}
+
+impl KeyboardLayout {
+    pub const ALL: [KeyboardLayout; 8] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Dvorak,
+        KeyboardLayout::Colemak,
+        KeyboardLayout::ColemakDh,
+        KeyboardLayout::Workman,
+        KeyboardLayout::Bepo,
+    ];
+
+    /// Caracter (sin modificadores) de cada posicion de `ROW_CODES`
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            KeyboardLayout::Qwerty => ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            KeyboardLayout::Qwertz => ["qwertzuiop", "asdfghjklö", "yxcvbnm,.-"],
+            KeyboardLayout::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+            KeyboardLayout::Dvorak => ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+            KeyboardLayout::Colemak => ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"],
+            KeyboardLayout::ColemakDh => ["qwfpbjluy;", "arstgmneio", "zxcdvkh,./"],
+            KeyboardLayout::Workman => ["qdrwbjfup;", "ashtgyneoi", "zxmcvkl,./"],
+            KeyboardLayout::Bepo => ["bépoè^vdlj", "auie,ctsrn", "àyx.k'qghf"],
+        }
+    }
+
+    /// Caracter que produce la tecla en la posicion `code`
+    pub fn char_at(self, code: Code) -> Option<char> {
+        ROW_CODES
+            .iter()
+            .zip(self.rows())
+            .find_map(|(codes, chars)| {
+                let index = codes.iter().position(|c| *c == code)?;
+                chars.chars().nth(index)
+            })
+    }
+
+    /// Posicion de la tecla que produce `ch`
+    pub fn position_of(self, ch: char) -> Option<Code> {
+        let key = self.key_of(ch)?;
+        Some(ROW_CODES[key.row][key.column])
+    }
+
+    /// Fila y columna de la tecla que produce `ch`
+    pub fn key_of(self, ch: char) -> Option<KeyPosition> {
+        let ch = ch.to_lowercase().next().unwrap_or(ch);
+        self.rows().iter().enumerate().find_map(|(row, chars)| {
+            let column = chars.chars().position(|c| c == ch)?;
+            Some(KeyPosition { row, column })
+        })
+    }
+
+    /// Letras de la fila central, en orden: el charset de hints por defecto
+    pub fn home_row(self) -> String {
+        self.rows()[1]
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .collect()
+    }
+
+    /// Layout a partir de la config XKB (`setxkbmap -query`): layout "us",
+    /// "de", "fr"... y variante "dvorak", "colemak_dh", "bepo"...
+    pub fn from_xkb(layout: &str, variant: &str) -> Self {
+        let variant = variant.to_lowercase();
+        if variant.contains("bepo") {
+            return KeyboardLayout::Bepo;
+        }
+        if variant.contains("dvorak") {
+            return KeyboardLayout::Dvorak;
+        }
+        if variant.contains("colemak_dh") || variant.contains("colemak-dh") {
+            return KeyboardLayout::ColemakDh;
+        }
+        if variant.contains("colemak") {
+            return KeyboardLayout::Colemak;
+        }
+        if variant.contains("workman") {
+            return KeyboardLayout::Workman;
+        }
+
+        match layout.to_lowercase().as_str() {
+            "fr" | "be" => KeyboardLayout::Azerty,
+            "de" | "at" | "ch" | "cz" | "sk" | "hu" | "si" | "hr" if variant != "qwerty" => {
+                KeyboardLayout::Qwertz
+            }
+            // Variantes QWERTZ de layouts QWERTY ("pl(qwertz)")
+            _ if variant == "qwertz" => KeyboardLayout::Qwertz,
+            _ => KeyboardLayout::Qwerty,
+        }
+    }
+
+    /// Layout a partir del id de la fuente de entrada de macOS
+    /// ("com.apple.keylayout.Dvorak", "com.apple.keylayout.German"...)
+    pub fn from_mac_source_id(source_id: &str) -> Self {
+        let name = source_id
+            .rsplit('.')
+            .next()
+            .unwrap_or(source_id)
+            .to_lowercase();
+
+        if name.contains("dvorak") {
+            KeyboardLayout::Dvorak
+        } else if name.contains("colemak") {
+            KeyboardLayout::Colemak
+        } else if name.contains("french") || name.contains("belgian") {
+            KeyboardLayout::Azerty
+        } else if [
+            "german",
+            "swiss",
+            "austrian",
+            "czech",
+            "slovak",
+            "hungarian",
+        ]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+            && !name.contains("qwerty")
+        {
+            KeyboardLayout::Qwertz
+        } else {
+            KeyboardLayout::Qwerty
+        }
+    }
+}
+
+/// Mano que pulsa una tecla
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Tecla en la rejilla de `ROW_CODES`: fila 0 arriba, 1 central, 2 abajo;
+/// columnas 0-9 de izquierda a derecha
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPosition {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl KeyPosition {
+    pub fn hand(self) -> Hand {
+        if self.column < 5 {
+            Hand::Left
+        } else {
+            Hand::Right
+        }
+    }
+
+    /// Dedo con tecleo de diez dedos: 0 menique, 1 anular, 2 corazon,
+    /// 3 indice (que cubre tambien la columna interior)
+    pub fn finger(self) -> usize {
+        let from_edge = match self.hand() {
+            Hand::Left => self.column,
+            Hand::Right => 9 - self.column,
+        };
+        from_edge.min(3)
+    }
+
+    /// Columnas interiores (T/G/B e Y/H/N en QWERTY): el indice se estira
+    pub fn is_inner_column(self) -> bool {
+        self.column == 4 || self.column == 5
+    }
+}
+
+/// Lo que escribe cada posicion de `ROW_CODES` (sin modificadores) con el
+/// keymap activo del sistema. Lo traduce la plataforma (XKB, UCKeyTranslate);
+/// cubre tambien layouts sin preset
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeymapChars {
+    chars: HashMap<Code, char>,
+}
+
+impl KeymapChars {
+    /// Traduce cada posicion con `translate`; las que no escriben un
+    /// caracter (teclas muertas, sin asignar) quedan fuera
+    pub fn translate(mut translate: impl FnMut(Code) -> Option<char>) -> Self {
+        let chars = ROW_CODES
+            .iter()
+            .flatten()
+            .filter_map(|code| Some((*code, translate(*code)?)))
+            .collect();
+        Self { chars }
+    }
+
+    pub fn char_at(&self, code: Code) -> Option<char> {
+        self.chars.get(&code).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+}
+
+/// De donde salen los caracteres de los hints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintCharset {
+    // Fila central del layout activo
+    #[default]
+    HomeRow,
+    // `hint_chars` tal cual
+    Custom,
+    // `hint_chars` son posiciones (nombradas como en QWERTY): se muestra lo
+    // que el keymap activo escribe en cada una
+    Physical,
+}
+
+impl HintCharset {
+    /// Charset final para el layout activo. En modo physical cada posicion
+    /// se traduce con `keymap`; el preset de `layout` solo cubre las
+    /// posiciones que el keymap no traduce (o todas, si no se pudo leer)
+    pub fn resolve(
+        self,
+        hint_chars: &str,
+        layout: KeyboardLayout,
+        keymap: Option<&KeymapChars>,
+    ) -> String {
+        let chars: String = match self {
+            HintCharset::HomeRow => layout.home_row(),
+            HintCharset::Custom => hint_chars.to_string(),
+            HintCharset::Physical => hint_chars
+                .chars()
+                .map(|ch| {
+                    KeyboardLayout::Qwerty
+                        .position_of(ch)
+                        .and_then(|code| {
+                            keymap
+                                .and_then(|keymap| keymap.char_at(code))
+                                .or_else(|| layout.char_at(code))
+                        })
+                        .unwrap_or(ch)
+                })
+                .collect(),
+        };
+
+        // Sin repetidos: dos hints no pueden compartir tecla
+        let mut unique = String::new();
+        for ch in chars.chars() {
+            if !unique.contains(ch) {
+                unique.push(ch);
+            }
+        }
+        unique
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_home_rows() {
+        assert_eq!(KeyboardLayout::Qwerty.home_row(), "asdfghjkl");
+        assert_eq!(KeyboardLayout::Qwertz.home_row(), "asdfghjklö");
+        assert_eq!(KeyboardLayout::Azerty.home_row(), "qsdfghjklm");
+        assert_eq!(KeyboardLayout::Dvorak.home_row(), "aoeuidhtns");
+        assert_eq!(KeyboardLayout::Colemak.home_row(), "arstdhneio");
+        assert_eq!(KeyboardLayout::ColemakDh.home_row(), "arstgmneio");
+        assert_eq!(KeyboardLayout::Workman.home_row(), "ashtgyneoi");
+        assert_eq!(KeyboardLayout::Bepo.home_row(), "auiectsrn");
+    }
+
+    #[test]
+    fn test_every_layout_covers_every_position() {
+        for layout in KeyboardLayout::ALL {
+            for code in ROW_CODES.iter().flatten() {
+                let ch = layout.char_at(*code).unwrap();
+                assert_eq!(layout.position_of(ch), Some(*code), "{:?} {}", layout, ch);
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_positions() {
+        let f = KeyboardLayout::Qwerty.key_of('f').unwrap();
+        assert_eq!(f, KeyPosition { row: 1, column: 3 });
+        assert_eq!((f.hand(), f.finger()), (Hand::Left, 3));
+
+        // En Dvorak la "t" esta bajo el corazon derecho de la fila central
+        let t = KeyboardLayout::Dvorak.key_of('T').unwrap();
+        assert_eq!((t.row, t.hand(), t.finger()), (1, Hand::Right, 2));
+        assert!(KeyboardLayout::Qwerty
+            .key_of('g')
+            .unwrap()
+            .is_inner_column());
+        assert_eq!(KeyboardLayout::Qwerty.key_of('p').unwrap().finger(), 0);
+        assert_eq!(KeyboardLayout::Qwerty.key_of('1'), None);
+    }
+
+    #[test]
+    fn test_physical_charset_follows_positions() {
+        // Las teclas de "asdf" en QWERTY escriben "aoeu" en Dvorak
+        assert_eq!(
+            HintCharset::Physical.resolve("asdf", KeyboardLayout::Dvorak, None),
+            "aoeu"
+        );
+        assert_eq!(
+            HintCharset::Physical.resolve("asdf", KeyboardLayout::Azerty, None),
+            "qsdf"
+        );
+        assert_eq!(
+            HintCharset::Custom.resolve("asdf", KeyboardLayout::Dvorak, None),
+            "asdf"
+        );
+        assert_eq!(
+            HintCharset::HomeRow.resolve("asdf", KeyboardLayout::Colemak, None),
+            "arstdhneio"
+        );
+        assert_eq!(
+            HintCharset::Custom.resolve("aabb", KeyboardLayout::Qwerty, None),
+            "ab"
+        );
+    }
+
+    #[test]
+    fn test_physical_charset_uses_active_keymap() {
+        // Keymap ruso: lo que escribe la tecla manda sobre el preset QWERTY
+        let keymap = KeymapChars::translate(|code| match code {
+            Code::KeyA => Some('ф'),
+            Code::KeyS => Some('ы'),
+            Code::KeyD => Some('в'),
+            _ => None,
+        });
+        assert_eq!(
+            HintCharset::Physical.resolve("asdf", KeyboardLayout::Qwerty, Some(&keymap)),
+            "фывf"
+        );
+        // Sin keymap, solo el preset
+        assert_eq!(
+            HintCharset::Physical.resolve("asdf", KeyboardLayout::Qwerty, None),
+            "asdf"
+        );
+        // Los otros modos no dependen del keymap
+        assert_eq!(
+            HintCharset::HomeRow.resolve("", KeyboardLayout::Qwerty, Some(&keymap)),
+            "asdfghjkl"
+        );
+    }
+
+    #[test]
+    fn test_detect_from_xkb() {
+        assert_eq!(KeyboardLayout::from_xkb("us", ""), KeyboardLayout::Qwerty);
+        assert_eq!(
+            KeyboardLayout::from_xkb("us", "dvorak"),
+            KeyboardLayout::Dvorak
+        );
+        assert_eq!(
+            KeyboardLayout::from_xkb("us", "colemak_dh"),
+            KeyboardLayout::ColemakDh
+        );
+        assert_eq!(KeyboardLayout::from_xkb("de", ""), KeyboardLayout::Qwertz);
+        assert_eq!(KeyboardLayout::from_xkb("fr", ""), KeyboardLayout::Azerty);
+        assert_eq!(KeyboardLayout::from_xkb("fr", "bepo"), KeyboardLayout::Bepo);
+        assert_eq!(KeyboardLayout::from_xkb("pl", ""), KeyboardLayout::Qwerty);
+        assert_eq!(
+            KeyboardLayout::from_xkb("pl", "qwertz"),
+            KeyboardLayout::Qwertz
+        );
+    }
+
+    #[test]
+    fn test_detect_from_mac_source_id() {
+        assert_eq!(
+            KeyboardLayout::from_mac_source_id("com.apple.keylayout.US"),
+            KeyboardLayout::Qwerty
+        );
+        assert_eq!(
+            KeyboardLayout::from_mac_source_id("com.apple.keylayout.Dvorak-QWERTYCMD"),
+            KeyboardLayout::Dvorak
+        );
+        assert_eq!(
+            KeyboardLayout::from_mac_source_id("com.apple.keylayout.German"),
+            KeyboardLayout::Qwertz
+        );
+        assert_eq!(
+            KeyboardLayout::from_mac_source_id("com.apple.keylayout.French-numerical"),
+            KeyboardLayout::Azerty
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use super::{HintStyle, LogicalPoint, LogicalRect};
+
+/// Linea guia entre un label desplazado y su elemento
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LeaderLine {
+    // Anclaje del hint (dentro del elemento)
+    pub from: LogicalPoint,
+    // Punto del borde de la caja mas cercano al anclaje
+    pub to: LogicalPoint,
+}
+
+/// Caja de un label ya colocada por el motor de layout; los renderers solo
+/// tienen que dibujarla
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelBox {
+    pub label: String,
+    pub element_id: String,
+    // Caja en coordenadas globales (origen arriba/izquierda)
+    pub rect: LogicalRect,
+    // Tamaño de fuente final (menor que el del estilo si hubo que encoger)
+    pub font_size: f64,
+    pub leader: Option<LeaderLine>,
+    // Estilo resuelto para el modo y el rol del hint
+    pub style: HintStyle,
+}
+
+/// Estado de un label mientras se escribe un prefijo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum LabelState {
+    // Sin prefijo escrito: se dibuja tal cual
+    Idle,
+    // Empieza por el prefijo; `typed` caracteres ya escritos
+    Matching { typed: usize },
+    // Unico label que sigue coincidiendo
+    Unique { typed: usize },
+    // No coincide con el prefijo
+    Hidden,
+}
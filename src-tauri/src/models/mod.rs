@@ -1,6 +1,47 @@
-pub mod element;
+pub mod click_anchor;
 pub mod config;
+pub mod element;
+pub mod element_tree;
+pub mod geometry;
 pub mod hint;
+pub mod hint_style;
+pub mod hotkey_binding;
+pub mod inspection;
+pub mod key_combo;
+pub mod key_sequence;
+pub mod keyboard_layout;
+pub mod label_box;
+pub mod role;
+pub mod scan_options;
+pub mod scene;
+// Solo la usan el overlay de macOS y los tests del renderer CPU
+#[cfg(any(target_os = "macos", test))]
+pub mod screen_layout;
+pub mod usage;
+pub mod window_stack;
 
+pub use click_anchor::ClickAnchor;
 pub use element::UIElement;
-pub use hint::Hint;
+pub use element_tree::ElementTree;
+pub use geometry::{LogicalPoint, LogicalRect};
+#[cfg(test)]
+pub use geometry::{PhysicalPoint, PhysicalSize};
+#[cfg(any(target_os = "macos", test))]
+pub use geometry::{CocoaPoint, CocoaRect, ScreenPoint, ScreenRect};
+pub use hint::{Hint, LabelStrategy};
+#[cfg(any(target_os = "macos", test))]
+pub use hint_style::Color;
+pub use hint_style::{HintMode, HintStyle, HintTheme};
+pub use hotkey_binding::{HotkeyAction, HotkeyBinding, HotkeyConflict, HotkeyStatus};
+pub use inspection::{FilterDecision, FilterExplanation, InspectedNode, InspectedTree};
+pub use key_combo::{KeyCombo, KeyNotation, KeySide, ModifierKey};
+pub use key_sequence::KeySequence;
+pub use keyboard_layout::{HintCharset, KeyboardLayout, KeymapChars};
+pub use label_box::{LabelBox, LabelState, LeaderLine};
+pub use role::Role;
+pub use scan_options::ScanOptions;
+pub use scene::{SceneNode, SceneOp};
+#[cfg(any(target_os = "macos", test))]
+pub use screen_layout::{Screen, ScreenLayout};
+pub use usage::UsageEntry;
+pub use window_stack::{StackedWindow, WindowStack};
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+/// Rol normalizado de un elemento, comun a todas las plataformas.
+///
+/// Cada backend (AX en macOS, AT-SPI en Linux, UIA en Windows) traduce sus
+/// roles nativos a esta enum; el rol original se conserva en `UIElement::raw_role`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Button,
+    Link,
+    TextField,
+    TextArea,
+    CheckBox,
+    RadioButton,
+    MenuItem,
+    MenuButton,
+    PopUpButton,
+    ComboBox,
+    Tab,
+    TabGroup,
+    Slider,
+    Incrementor,
+    ColorWell,
+    DisclosureTriangle,
+    Row,
+    Cell,
+    Window,
+    Group,
+    ScrollArea,
+    Toolbar,
+    List,
+    Table,
+    StaticText,
+    Image,
+    /// Rol nativo sin equivalente normalizado (se serializa tal cual)
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl Role {
+    /// Indica si el rol representa un elemento sobre el que se puede hacer click
+    pub fn is_clickable(&self) -> bool {
+        matches!(
+            self,
+            Role::Button
+                | Role::Link
+                | Role::TextField
+                | Role::TextArea
+                | Role::CheckBox
+                | Role::RadioButton
+                | Role::MenuItem
+                | Role::MenuButton
+                | Role::PopUpButton
+                | Role::ComboBox
+                | Role::Tab
+                | Role::TabGroup
+                | Role::Slider
+                | Role::Incrementor
+                | Role::ColorWell
+                | Role::DisclosureTriangle
+        )
+    }
+
+    /// Nombre normalizado del rol (el mismo que se usa al serializar)
+    pub fn as_str(&self) -> &str {
+        match self {
+            Role::Button => "button",
+            Role::Link => "link",
+            Role::TextField => "textfield",
+            Role::TextArea => "textarea",
+            Role::CheckBox => "checkbox",
+            Role::RadioButton => "radiobutton",
+            Role::MenuItem => "menuitem",
+            Role::MenuButton => "menubutton",
+            Role::PopUpButton => "popupbutton",
+            Role::ComboBox => "combobox",
+            Role::Tab => "tab",
+            Role::TabGroup => "tabgroup",
+            Role::Slider => "slider",
+            Role::Incrementor => "incrementor",
+            Role::ColorWell => "colorwell",
+            Role::DisclosureTriangle => "disclosuretriangle",
+            Role::Row => "row",
+            Role::Cell => "cell",
+            Role::Window => "window",
+            Role::Group => "group",
+            Role::ScrollArea => "scrollarea",
+            Role::Toolbar => "toolbar",
+            Role::List => "list",
+            Role::Table => "table",
+            Role::StaticText => "statictext",
+            Role::Image => "image",
+            Role::Other(raw) => raw,
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clickable_roles() {
+        assert!(Role::Button.is_clickable());
+        assert!(Role::TextField.is_clickable());
+        assert!(!Role::Group.is_clickable());
+        assert!(!Role::Other("AXUnknown".to_string()).is_clickable());
+    }
+
+    #[test]
+    fn test_serialize_known_role() {
+        let json = serde_json::to_string(&Role::CheckBox).unwrap();
+        assert_eq!(json, "\"checkbox\"");
+        let role: Role = serde_json::from_str("\"menuitem\"").unwrap();
+        assert_eq!(role, Role::MenuItem);
+    }
+
+    #[test]
+    fn test_serialize_other_role_roundtrip() {
+        let role = Role::Other("AXSplitter".to_string());
+        let json = serde_json::to_string(&role).unwrap();
+        assert_eq!(json, "\"AXSplitter\"");
+        let back: Role = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, role);
+    }
+}
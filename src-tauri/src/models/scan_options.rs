@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Atributos opcionales a leer durante un escaneo.
+///
+/// Cada atributo extra implica llamadas adicionales al backend de
+/// accesibilidad por elemento, asi que todos estan desactivados por defecto.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanOptions {
+    // Valor del elemento (texto de un campo, URL de un link, estado de un slider)
+    pub value: bool,
+
+    // Descripcion accesible o texto de ayuda
+    pub description: bool,
+
+    // Si el elemento esta habilitado
+    pub enabled: bool,
+
+    // Si el elemento tiene el foco de teclado
+    pub focused: bool,
+
+    // Si el elemento esta seleccionado
+    pub selected: bool,
+
+    // Lista de acciones soportadas (ej: AXPress)
+    pub actions: bool,
+
+    // PID de la aplicacion duena del elemento
+    pub pid: bool,
+
+    // Identificador de la ventana que contiene el elemento
+    pub window: bool,
+}
+
+impl ScanOptions {
+    /// Activa todos los atributos opcionales
+    #[cfg(target_os = "macos")]
+    pub fn all() -> Self {
+        Self {
+            value: true,
+            description: true,
+            enabled: true,
+            focused: true,
+            selected: true,
+            actions: true,
+            pid: true,
+            window: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_opt_in() {
+        let options = ScanOptions::default();
+        assert_eq!(options, serde_json::from_str("{}").unwrap());
+        assert!(!options.value && !options.actions && !options.window);
+    }
+
+    #[test]
+    fn test_partial_deserialize() {
+        let options: ScanOptions = serde_json::from_str(r#"{"enabled": true}"#).unwrap();
+        assert!(options.enabled);
+        assert!(!options.focused);
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use super::{LabelBox, LabelState, LeaderLine, LogicalRect};
+
+/// Un hint dentro de la escena: su caja colocada y su estado actual
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneNode {
+    pub label_box: LabelBox,
+    pub state: LabelState,
+}
+
+impl SceneNode {
+    pub fn element_id(&self) -> &str {
+        &self.label_box.element_id
+    }
+}
+
+/// Operacion que un renderer aplica sobre lo que ya tiene dibujado. Los
+/// nodos se identifican por `element_id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum SceneOp {
+    // Nodo nuevo: hay que crear sus primitivas
+    Add {
+        node: SceneNode,
+    },
+    // Solo cambia la geometria (caja, fuente encogida o linea guia)
+    Move {
+        element_id: String,
+        rect: LogicalRect,
+        font_size: f64,
+        leader: Option<LeaderLine>,
+    },
+    // Cambia el texto, el estilo o el estado; el nodo trae todo, geometria
+    // incluida
+    Update {
+        node: SceneNode,
+    },
+    // El nodo ya no esta en la escena
+    Remove {
+        element_id: String,
+    },
+}
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use super::{CocoaPoint, CocoaRect, LogicalPoint, LogicalRect, ScreenPoint, ScreenRect};
+#[cfg(test)]
+use super::{PhysicalPoint, PhysicalSize};
+
+/// Una pantalla fisica
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Screen {
+    // Identificador del sistema (CGDirectDisplayID en macOS)
+    pub id: u32,
+    // Frame en coordenadas globales (puntos, origen arriba/izquierda de la
+    // pantalla principal); puede tener origen negativo
+    pub frame: LogicalRect,
+    // Pixeles por punto (2.0 en Retina)
+    pub scale_factor: f64,
+}
+
+impl Screen {
+    pub fn new(id: u32, frame: LogicalRect, scale_factor: f64) -> Self {
+        Self {
+            id,
+            frame,
+            scale_factor,
+        }
+    }
+
+    /// Punto global a coordenadas locales de la pantalla
+    pub fn to_local(self, point: LogicalPoint) -> ScreenPoint {
+        ScreenPoint::new(point.x - self.frame.x, point.y - self.frame.y)
+    }
+
+    pub fn rect_to_local(self, rect: &LogicalRect) -> ScreenRect {
+        let origin = self.to_local(LogicalPoint::new(rect.x, rect.y));
+        ScreenRect::new(origin.x, origin.y, rect.width, rect.height)
+    }
+
+    /// Punto global a pixeles fisicos del buffer de la pantalla
+    #[cfg(test)]
+    pub fn to_physical(self, point: LogicalPoint) -> PhysicalPoint {
+        let local = self.to_local(point);
+        PhysicalPoint::new(local.x * self.scale_factor, local.y * self.scale_factor)
+    }
+
+    /// Pixel fisico del buffer de la pantalla a punto global
+    #[cfg(test)]
+    pub fn to_logical(self, pixel: PhysicalPoint) -> LogicalPoint {
+        LogicalPoint::new(
+            self.frame.x + pixel.x / self.scale_factor,
+            self.frame.y + pixel.y / self.scale_factor,
+        )
+    }
+
+    /// Tamaño del buffer de la pantalla en pixeles fisicos
+    #[cfg(test)]
+    pub fn physical_size(self) -> PhysicalSize {
+        PhysicalSize::new(
+            (self.frame.width * self.scale_factor).round() as u32,
+            (self.frame.height * self.scale_factor).round() as u32,
+        )
+    }
+}
+
+/// Disposicion de las pantallas en el espacio global.
+///
+/// El espacio global es el de AX/CoreGraphics: puntos con origen en la
+/// esquina superior izquierda de la pantalla principal y la Y hacia abajo.
+/// Las pantallas a la izquierda o encima de la principal tienen origen
+/// negativo. La primera pantalla de la lista es la principal.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenLayout {
+    screens: Vec<Screen>,
+}
+
+impl ScreenLayout {
+    pub fn new(screens: Vec<Screen>) -> Self {
+        Self { screens }
+    }
+
+    pub fn screens(&self) -> &[Screen] {
+        &self.screens
+    }
+
+    /// Pantalla principal (la del menu bar)
+    pub fn primary(&self) -> Option<&Screen> {
+        self.screens.first()
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn frames(&self) -> Vec<LogicalRect> {
+        self.screens.iter().map(|screen| screen.frame).collect()
+    }
+
+    /// Pantalla que contiene el punto
+    pub fn screen_at(&self, point: LogicalPoint) -> Option<&Screen> {
+        self.screens
+            .iter()
+            .find(|screen| screen.frame.contains_point(point))
+    }
+
+    /// Pantalla donde cae la mayor parte del rectangulo; si no toca
+    /// ninguna, la mas cercana a su centro
+    pub fn screen_for(&self, rect: &LogicalRect) -> Option<&Screen> {
+        let best = self
+            .screens
+            .iter()
+            .map(|screen| (screen, screen.frame.intersection_area(rect)))
+            .filter(|(_, area)| *area > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(screen, _)| screen);
+
+        best.or_else(|| {
+            let center = rect.center();
+            self.screens.iter().min_by(|a, b| {
+                distance_to_rect(&a.frame, center).total_cmp(&distance_to_rect(&b.frame, center))
+            })
+        })
+    }
+
+    /// Punto global a coordenadas Cocoa
+    pub fn to_cocoa(&self, point: LogicalPoint) -> CocoaPoint {
+        CocoaPoint::new(point.x, self.primary_height() - point.y)
+    }
+
+    /// Inversa de `to_cocoa`
+    #[cfg(test)]
+    pub fn cocoa_to_logical(&self, point: CocoaPoint) -> LogicalPoint {
+        LogicalPoint::new(point.x, self.primary_height() - point.y)
+    }
+
+    /// Rectangulo global a Cocoa (el origen pasa a ser la esquina inferior)
+    pub fn rect_to_cocoa(&self, rect: &LogicalRect) -> CocoaRect {
+        let origin = self.to_cocoa(LogicalPoint::new(rect.x, rect.bottom()));
+        CocoaRect::new(origin.x, origin.y, rect.width, rect.height)
+    }
+
+    /// Inversa de `rect_to_cocoa`
+    #[cfg(test)]
+    pub fn cocoa_rect_to_logical(&self, rect: &CocoaRect) -> LogicalRect {
+        let origin = self.cocoa_to_logical(CocoaPoint::new(rect.x, rect.top()));
+        LogicalRect::new(origin.x, origin.y, rect.width, rect.height)
+    }
+
+    fn primary_height(&self) -> f64 {
+        self.primary().map_or(0.0, |screen| screen.frame.height)
+    }
+}
+
+fn distance_to_rect(rect: &LogicalRect, point: LogicalPoint) -> f64 {
+    let dx = (rect.x - point.x).max(point.x - rect.right()).max(0.0);
+    let dy = (rect.y - point.y).max(point.y - rect.bottom()).max(0.0);
+    dx.hypot(dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Principal 1440x900 Retina, otra de 1920x1080 a la izquierda y
+    /// bajada 200pt, y una tercera encima de la principal
+    fn layout() -> ScreenLayout {
+        ScreenLayout::new(vec![
+            Screen::new(1, LogicalRect::new(0.0, 0.0, 1440.0, 900.0), 2.0),
+            Screen::new(2, LogicalRect::new(-1920.0, 200.0, 1920.0, 1080.0), 1.0),
+            Screen::new(3, LogicalRect::new(0.0, -1080.0, 1920.0, 1080.0), 1.0),
+        ])
+    }
+
+    #[test]
+    fn test_screen_at_negative_origins() {
+        let layout = layout();
+        assert_eq!(
+            layout
+                .screen_at(LogicalPoint::new(10.0, 10.0))
+                .map(|s| s.id),
+            Some(1)
+        );
+        assert_eq!(
+            layout
+                .screen_at(LogicalPoint::new(-10.0, 300.0))
+                .map(|s| s.id),
+            Some(2)
+        );
+        assert_eq!(
+            layout
+                .screen_at(LogicalPoint::new(100.0, -1.0))
+                .map(|s| s.id),
+            Some(3)
+        );
+        // Hueco a la izquierda por encima de la pantalla 2
+        assert_eq!(layout.screen_at(LogicalPoint::new(-10.0, 100.0)), None);
+    }
+
+    #[test]
+    fn test_screen_for_uses_largest_overlap() {
+        let layout = layout();
+        // Caja a caballo entre la 2 y la principal, mas dentro de la principal
+        let rect = LogicalRect::new(-10.0, 300.0, 40.0, 20.0);
+        assert_eq!(layout.screen_for(&rect).map(|s| s.id), Some(1));
+        // Fuera de todas: la mas cercana
+        let outside = LogicalRect::new(-100.0, -50.0, 10.0, 10.0);
+        assert_eq!(layout.screen_for(&outside).map(|s| s.id), Some(3));
+    }
+
+    #[test]
+    fn test_local_and_pixel_conversions() {
+        let layout = layout();
+        let left = layout.screens()[1];
+        let point = LogicalPoint::new(-1900.0, 250.0);
+
+        assert_eq!(left.to_local(point), ScreenPoint::new(20.0, 50.0));
+        assert_eq!(
+            left.rect_to_local(&LogicalRect::new(-1900.0, 250.0, 30.0, 10.0)),
+            ScreenRect::new(20.0, 50.0, 30.0, 10.0)
+        );
+
+        let primary = layout.screens()[0];
+        let pixel = primary.to_physical(LogicalPoint::new(100.5, 20.0));
+        assert_eq!(pixel, PhysicalPoint::new(201.0, 40.0));
+        assert_eq!(primary.to_logical(pixel), LogicalPoint::new(100.5, 20.0));
+        assert_eq!(primary.physical_size(), PhysicalSize::new(2880, 1800));
+
+        let above = layout.screens()[2];
+        assert_eq!(
+            above.to_physical(LogicalPoint::new(5.0, -1080.0)),
+            PhysicalPoint::new(5.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_cocoa_conversions() {
+        let layout = layout();
+
+        // La esquina superior izquierda de la principal es y=900 en Cocoa
+        assert_eq!(
+            layout.to_cocoa(LogicalPoint::new(0.0, 0.0)),
+            CocoaPoint::new(0.0, 900.0)
+        );
+
+        // La pantalla de la izquierda acaba 380pt por debajo de la principal
+        let left = layout.rect_to_cocoa(&layout.screens()[1].frame);
+        assert_eq!(left, CocoaRect::new(-1920.0, -380.0, 1920.0, 1080.0));
+
+        // La de arriba empieza justo donde acaba la principal
+        let above = layout.rect_to_cocoa(&layout.screens()[2].frame);
+        assert_eq!(above, CocoaRect::new(0.0, 900.0, 1920.0, 1080.0));
+
+        let rect = LogicalRect::new(-50.0, -30.0, 20.0, 10.0);
+        assert_eq!(
+            layout.cocoa_rect_to_logical(&layout.rect_to_cocoa(&rect)),
+            rect
+        );
+        let point = LogicalPoint::new(-1234.5, 777.25);
+        assert_eq!(layout.cocoa_to_logical(layout.to_cocoa(point)), point);
+    }
+}
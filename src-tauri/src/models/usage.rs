@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+/// Uso aprendido de un elemento, tal como se muestra en ajustes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageEntry {
+    // Nombre de la app (ej: "Safari")
+    pub app: String,
+    // `UIElement::stable_key` del elemento
+    pub key: String,
+    // Puntuacion actual, ya con el decaimiento aplicado
+    pub score: f64,
+    // Veces que se ha elegido
+    pub count: u32,
+    // Ultimo uso, en segundos Unix
+    pub last_used: u64,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use super::LogicalRect;
+
+/// Ventana en pantalla tal como la reporta el backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StackedWindow {
+    // Identificador nativo (CGWindowID en macOS, XID en X11)
+    pub id: u32,
+    pub pid: Option<i32>,
+    pub frame: LogicalRect,
+}
+
+/// Ventanas en pantalla ordenadas de delante hacia atras.
+///
+/// Lo rellenan `platform::macos::window_list` (CGWindowList) y
+/// `platform::linux::window_list` (`_NET_CLIENT_LIST_STACKING` en X11).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowStack {
+    pub windows: Vec<StackedWindow>,
+}
+
+impl WindowStack {
+    pub fn new(windows: Vec<StackedWindow>) -> Self {
+        Self { windows }
+    }
+
+    /// Posicion de la ventana en el apilado (0 = la de delante)
+    pub fn position(&self, window_id: u32) -> Option<usize> {
+        self.windows
+            .iter()
+            .position(|window| window.id == window_id)
+    }
+}
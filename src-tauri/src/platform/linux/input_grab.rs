@@ -0,0 +1,217 @@
+use std::ops::ControlFlow;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt as _, GrabMode, GrabStatus, Window};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::CURRENT_TIME;
+
+use super::keymap::{input_error, XkbKeymap};
+use crate::services::navigation_input::{ExitReason, NavEvent, NavigationInput};
+use crate::{AppError, Result};
+
+/// Cada cuanto se mira si hay eventos o vencio el plazo
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Intentos de grab: justo tras el hotkey otra app (o el WM) puede tener
+/// el teclado unos milisegundos
+const GRAB_ATTEMPTS: u32 = 20;
+const GRAB_RETRY: Duration = Duration::from_millis(10);
+
+/// Captura del teclado en X11 durante una sesion de navegacion: mientras
+/// dura, las teclas no llegan a la app con foco
+pub struct KeyboardGrab {
+    conn: RustConnection,
+    root: Window,
+    keymap: XkbKeymap,
+}
+
+/// Suelta el grab al salir de `run` por cualquier camino (Esc, timeout,
+/// seleccion, error o panic del callback)
+struct GrabGuard<'a> {
+    conn: &'a RustConnection,
+}
+
+impl Drop for GrabGuard<'_> {
+    fn drop(&mut self) {
+        let released = self
+            .conn
+            .ungrab_keyboard(CURRENT_TIME)
+            .map_err(input_error)
+            .and_then(|_| self.conn.flush().map_err(input_error));
+        if let Err(e) = released {
+            tracing::error!("Failed to release keyboard grab: {}", e);
+        }
+    }
+}
+
+impl KeyboardGrab {
+    /// Conecta al display de `DISPLAY` y carga su mapa de teclado
+    pub fn connect() -> Result<Self> {
+        let (conn, screen) = x11rb::connect(None).map_err(input_error)?;
+        let root = conn.setup().roots[screen].root;
+        let keymap = XkbKeymap::load(&conn)?;
+
+        Ok(Self { conn, root, keymap })
+    }
+
+    /// Captura el teclado y pasa cada tecla por `input` hasta Esc, timeout
+    /// o hasta que `on_prefix` devuelva `Break` (un solo hint). El grab se
+    /// suelta siempre, tambien si hay error.
+    pub fn run<F>(&mut self, input: &mut NavigationInput, mut on_prefix: F) -> Result<ExitReason>
+    where
+        F: FnMut(&str) -> ControlFlow<()>,
+    {
+        self.grab()?;
+        let _guard = GrabGuard { conn: &self.conn };
+        tracing::info!("Keyboard grabbed for navigation");
+
+        loop {
+            let now = Instant::now();
+            if let Some(reason) = input.expire(now) {
+                return Ok(reason);
+            }
+
+            let Some(event) = self.conn.poll_for_event().map_err(input_error)? else {
+                thread::sleep(POLL_INTERVAL.min(input.deadline() - now));
+                continue;
+            };
+
+            match event {
+                Event::KeyPress(press) => {
+                    let Some(key) = self.keymap.nav_key(press.detail, u16::from(press.state))
+                    else {
+                        continue;
+                    };
+                    match input.press(key, Instant::now()) {
+                        NavEvent::Prefix(prefix) => {
+                            if on_prefix(&prefix).is_break() {
+                                return Ok(ExitReason::Selected);
+                            }
+                        }
+                        NavEvent::Exit(reason) => return Ok(reason),
+                        NavEvent::Ignored => {}
+                    }
+                }
+                // Cambio de layout durante la sesion
+                Event::MappingNotify(_) => {
+                    self.keymap = XkbKeymap::load(&self.conn)?;
+                }
+                Event::Error(e) => return Err(input_error(format!("{:?}", e))),
+                _ => {}
+            }
+        }
+    }
+
+    fn grab(&self) -> Result<()> {
+        let mut status = GrabStatus::SUCCESS;
+        for _ in 0..GRAB_ATTEMPTS {
+            status = self
+                .conn
+                .grab_keyboard(
+                    false,
+                    self.root,
+                    CURRENT_TIME,
+                    GrabMode::ASYNC,
+                    GrabMode::ASYNC,
+                )
+                .map_err(input_error)?
+                .reply()
+                .map_err(input_error)?
+                .status;
+            if status == GrabStatus::SUCCESS {
+                return Ok(());
+            }
+            thread::sleep(GRAB_RETRY);
+        }
+
+        Err(AppError::Input(format!(
+            "Could not grab the keyboard: {:?}",
+            status
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use x11rb::protocol::xproto::{KEY_PRESS_EVENT, KEY_RELEASE_EVENT};
+    use x11rb::protocol::xtest::ConnectionExt as _;
+
+    const XK_ESCAPE: u32 = 0xff1b;
+
+    /// Manda las teclas con XTest desde otra conexion, como un teclado real
+    fn type_keysyms(keysyms: Vec<u32>) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let (conn, screen) = x11rb::connect(None).unwrap();
+            let root = conn.setup().roots[screen].root;
+            let keymap = XkbKeymap::load(&conn).unwrap();
+
+            // Dar tiempo a que la otra conexion haga el grab
+            thread::sleep(Duration::from_millis(200));
+            for keysym in keysyms {
+                let keycode = (8..=255u8)
+                    .find(|code| keymap.keysym(*code, 0) == Some(keysym))
+                    .unwrap();
+                for kind in [KEY_PRESS_EVENT, KEY_RELEASE_EVENT] {
+                    conn.xtest_fake_input(kind, keycode, CURRENT_TIME, root, 0, 0, 0)
+                        .unwrap();
+                }
+                conn.flush().unwrap();
+            }
+        })
+    }
+
+    #[test]
+    #[ignore = "necesita un servidor X: xvfb-run cargo test -- --ignored"]
+    fn test_grab_reads_synthesized_keys_until_escape() {
+        let mut grab = KeyboardGrab::connect().unwrap();
+        let typing = type_keysyms(vec!['a' as u32, 'x' as u32, 's' as u32, XK_ESCAPE]);
+
+        let mut input = NavigationInput::new("asdf", Duration::from_secs(5), Instant::now());
+        let mut prefixes = Vec::new();
+        let reason = grab
+            .run(&mut input, |prefix| {
+                prefixes.push(prefix.to_string());
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+        typing.join().unwrap();
+
+        assert_eq!(reason, ExitReason::Escape);
+        assert_eq!(prefixes, ["a", "as"]);
+    }
+
+    /// Otra conexion solo puede capturar el teclado si nadie lo tiene
+    fn keyboard_is_free() -> bool {
+        let (conn, screen) = x11rb::connect(None).unwrap();
+        let root = conn.setup().roots[screen].root;
+        let status = conn
+            .grab_keyboard(false, root, CURRENT_TIME, GrabMode::ASYNC, GrabMode::ASYNC)
+            .unwrap()
+            .reply()
+            .unwrap()
+            .status;
+        status == GrabStatus::SUCCESS
+    }
+
+    #[test]
+    #[ignore = "necesita un servidor X: xvfb-run cargo test -- --ignored"]
+    fn test_grab_is_released_on_timeout_and_selection() {
+        let mut grab = KeyboardGrab::connect().unwrap();
+
+        let mut input = NavigationInput::new("asdf", Duration::from_millis(100), Instant::now());
+        let reason = grab.run(&mut input, |_| ControlFlow::Continue(())).unwrap();
+        assert_eq!(reason, ExitReason::Timeout);
+        assert!(keyboard_is_free());
+
+        let typing = type_keysyms(vec!['d' as u32]);
+        let mut input = NavigationInput::new("asdf", Duration::from_secs(5), Instant::now());
+        let reason = grab.run(&mut input, |_| ControlFlow::Break(())).unwrap();
+        typing.join().unwrap();
+        assert_eq!(reason, ExitReason::Selected);
+        assert!(keyboard_is_free());
+    }
+}
@@ -0,0 +1,163 @@
+use keyboard_types::Code;
+use x11rb::connection::Connection;
+use x11rb::protocol::xkb::{ConnectionExt as _, ID};
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, Keycode};
+
+use super::keymap::{input_error, keysym_to_char, XkbKeymap};
+use crate::models::{KeyboardLayout, KeymapChars};
+use crate::Result;
+
+/// Propiedad de la ventana raiz con la config XKB activa (la que escribe
+/// `setxkbmap`)
+const XKB_RULES_NAMES: &[u8] = b"_XKB_RULES_NAMES";
+
+/// Layout del grupo XKB activo en el display de `DISPLAY`
+pub fn current_layout() -> Result<KeyboardLayout> {
+    let (conn, screen) = x11rb::connect(None).map_err(input_error)?;
+    let root = conn.setup().roots[screen].root;
+
+    let atom = conn
+        .intern_atom(true, XKB_RULES_NAMES)
+        .map_err(input_error)?
+        .reply()
+        .map_err(input_error)?
+        .atom;
+    let names = conn
+        .get_property(false, root, atom, AtomEnum::STRING, 0, 1024)
+        .map_err(input_error)?
+        .reply()
+        .map_err(input_error)?
+        .value;
+
+    conn.xkb_use_extension(1, 0)
+        .map_err(input_error)?
+        .reply()
+        .map_err(input_error)?;
+    let group = active_group(&conn)?;
+
+    Ok(layout_for_group(&names, group.into()))
+}
+
+/// Lo que escribe cada tecla de letras en el grupo XKB activo
+pub fn current_keymap() -> Result<KeymapChars> {
+    let (conn, _) = x11rb::connect(None).map_err(input_error)?;
+    let keymap = XkbKeymap::load(&conn)?;
+    let group = active_group(&conn)?;
+    Ok(keymap_chars(&keymap, group))
+}
+
+fn active_group(conn: &impl Connection) -> Result<u8> {
+    let state = conn
+        .xkb_get_state(ID::USE_CORE_KBD.into())
+        .map_err(input_error)?
+        .reply()
+        .map_err(input_error)?;
+    Ok(u8::from(state.group))
+}
+
+fn keymap_chars(keymap: &XkbKeymap, group: u8) -> KeymapChars {
+    // Grupo en los bits 13-14 del estado, sin modificadores
+    let state = u16::from(group & 0x3) << 13;
+    KeymapChars::translate(|code| {
+        keymap
+            .keysym(x11_keycode(code)?, state)
+            .and_then(keysym_to_char)
+    })
+}
+
+/// Keycode X11 de una posicion: codigo evdev + 8 (igual en todos los
+/// servidores con el driver evdev/libinput)
+fn x11_keycode(code: Code) -> Option<Keycode> {
+    let evdev = match code {
+        Code::KeyQ => 16,
+        Code::KeyW => 17,
+        Code::KeyE => 18,
+        Code::KeyR => 19,
+        Code::KeyT => 20,
+        Code::KeyY => 21,
+        Code::KeyU => 22,
+        Code::KeyI => 23,
+        Code::KeyO => 24,
+        Code::KeyP => 25,
+        Code::KeyA => 30,
+        Code::KeyS => 31,
+        Code::KeyD => 32,
+        Code::KeyF => 33,
+        Code::KeyG => 34,
+        Code::KeyH => 35,
+        Code::KeyJ => 36,
+        Code::KeyK => 37,
+        Code::KeyL => 38,
+        Code::Semicolon => 39,
+        Code::KeyZ => 44,
+        Code::KeyX => 45,
+        Code::KeyC => 46,
+        Code::KeyV => 47,
+        Code::KeyB => 48,
+        Code::KeyN => 49,
+        Code::KeyM => 50,
+        Code::Comma => 51,
+        Code::Period => 52,
+        Code::Slash => 53,
+        _ => return None,
+    };
+    Some(evdev + 8)
+}
+
+/// Layout de un grupo a partir de `_XKB_RULES_NAMES`: rules, model,
+/// layouts, variantes y opciones separados por `\0`; layouts y variantes
+/// son listas por grupo separadas por comas ("us,de" y "dvorak,")
+fn layout_for_group(rules_names: &[u8], group: usize) -> KeyboardLayout {
+    let names = String::from_utf8_lossy(rules_names);
+    let mut fields = names.split('\0');
+    let (_rules, _model) = (fields.next(), fields.next());
+    let layouts = fields.next().unwrap_or_default();
+    let variants = fields.next().unwrap_or_default();
+
+    let layout = layouts.split(',').nth(group).unwrap_or_default();
+    let variant = variants.split(',').nth(group).unwrap_or_default();
+    KeyboardLayout::from_xkb(layout, variant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_for_active_group() {
+        let names = b"evdev\0pc105\0us,de\0dvorak,\0grp:alt_shift_toggle\0";
+        assert_eq!(layout_for_group(names, 0), KeyboardLayout::Dvorak);
+        assert_eq!(layout_for_group(names, 1), KeyboardLayout::Qwertz);
+        // Grupo sin layout configurado
+        assert_eq!(layout_for_group(names, 2), KeyboardLayout::Qwerty);
+        assert_eq!(layout_for_group(b"", 0), KeyboardLayout::Qwerty);
+    }
+
+    #[test]
+    fn test_keymap_chars_follow_active_group() {
+        use x11rb::protocol::xkb::{KeySymMap, KeyType};
+        use x11rb::protocol::xproto::ModMask;
+
+        // Solo la tecla A (keycode 38): "a" en el grupo 1, "ф" en el 2
+        let one_level = KeyType {
+            mods_mask: ModMask::from(0u16),
+            mods_mods: ModMask::from(0u16),
+            mods_vmods: 0u16.into(),
+            num_levels: 1,
+            has_preserve: false,
+            map: Vec::new(),
+            preserve: Vec::new(),
+        };
+        let key_a = KeySymMap {
+            kt_index: [0; 4],
+            group_info: 2,
+            width: 1,
+            syms: vec!['a' as u32, 0x0100_0444],
+        };
+        let keymap = XkbKeymap::new(38, vec![one_level], vec![key_a]);
+
+        assert_eq!(keymap_chars(&keymap, 0).char_at(Code::KeyA), Some('a'));
+        assert_eq!(keymap_chars(&keymap, 1).char_at(Code::KeyA), Some('ф'));
+        assert_eq!(keymap_chars(&keymap, 1).char_at(Code::KeyS), None);
+    }
+}
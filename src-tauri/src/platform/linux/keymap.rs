@@ -0,0 +1,223 @@
+use x11rb::connection::Connection;
+use x11rb::protocol::xkb::{ConnectionExt as _, KeySymMap, KeyType, MapPart, ID};
+use x11rb::protocol::xproto::{Keycode, Keysym};
+
+use crate::services::navigation_input::NavKey;
+use crate::{AppError, Result};
+
+const XK_BACKSPACE: Keysym = 0xff08;
+const XK_ESCAPE: Keysym = 0xff1b;
+
+/// Tabla de XKB para traducir keycode + estado a keysym: tipo de cada
+/// tecla (que modificadores eligen el nivel) y keysyms por grupo y nivel
+pub struct XkbKeymap {
+    first_keycode: Keycode,
+    types: Vec<KeyType>,
+    keys: Vec<KeySymMap>,
+}
+
+impl XkbKeymap {
+    pub fn new(first_keycode: Keycode, types: Vec<KeyType>, keys: Vec<KeySymMap>) -> Self {
+        Self {
+            first_keycode,
+            types,
+            keys,
+        }
+    }
+
+    /// Pide al servidor el mapa del teclado principal
+    pub fn load(conn: &impl Connection) -> Result<Self> {
+        let version = conn
+            .xkb_use_extension(1, 0)
+            .map_err(input_error)?
+            .reply()
+            .map_err(input_error)?;
+        if !version.supported {
+            return Err(AppError::Input(
+                "XKB extension not supported by the X server".to_string(),
+            ));
+        }
+
+        // Con `full` el servidor manda todos los tipos y teclas e ignora los rangos
+        let reply = conn
+            .xkb_get_map(
+                ID::USE_CORE_KBD.into(),
+                MapPart::KEY_TYPES | MapPart::KEY_SYMS,
+                MapPart::from(0u16),
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0u16.into(),
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+            )
+            .map_err(input_error)?
+            .reply()
+            .map_err(input_error)?;
+
+        let (Some(types), Some(keys)) = (reply.map.types_rtrn, reply.map.syms_rtrn) else {
+            return Err(AppError::Input(
+                "XKB map without key types or syms".to_string(),
+            ));
+        };
+        Ok(Self::new(reply.first_key_sym, types, keys))
+    }
+
+    /// Keysym de una tecla con el estado del evento (modificadores en los
+    /// bits 0-7, grupo XKB en los 13-14)
+    pub fn keysym(&self, keycode: Keycode, state: u16) -> Option<Keysym> {
+        let key = self
+            .keys
+            .get(usize::from(keycode.checked_sub(self.first_keycode)?))?;
+        let groups = key.group_info & 0x0f;
+        if groups == 0 || key.width == 0 {
+            return None;
+        }
+
+        // Grupo fuera de rango: XKB lo vuelve a meter en rango dando la vuelta
+        let group = ((state >> 13) & 0x3) as u8 % groups;
+        let key_type = self
+            .types
+            .get(usize::from(key.kt_index[usize::from(group)]))?;
+        let mods = state & 0xff & u16::from(key_type.mods_mask);
+        let level = key_type
+            .map
+            .iter()
+            .find(|entry| entry.active && u16::from(entry.mods_mask) == mods)
+            .map_or(0, |entry| entry.level);
+        if level >= key.width {
+            return None;
+        }
+
+        let index = usize::from(group) * usize::from(key.width) + usize::from(level);
+        key.syms.get(index).copied().filter(|sym| *sym != 0)
+    }
+
+    /// Tecla de navegacion para un keycode; None para las que no cuentan
+    /// (modificadores, flechas...)
+    pub fn nav_key(&self, keycode: Keycode, state: u16) -> Option<NavKey> {
+        keysym_to_nav_key(self.keysym(keycode, state)?)
+    }
+}
+
+fn keysym_to_nav_key(keysym: Keysym) -> Option<NavKey> {
+    match keysym {
+        XK_ESCAPE => Some(NavKey::Escape),
+        XK_BACKSPACE => Some(NavKey::Backspace),
+        _ => keysym_to_char(keysym).map(NavKey::Char),
+    }
+}
+
+/// Caracter que escribe un keysym; None para teclas muertas y de control
+pub fn keysym_to_char(keysym: Keysym) -> Option<char> {
+    match keysym {
+        // Latin-1: el keysym es el propio codigo
+        0x20..=0x7e | 0xa0..=0xff => char::from_u32(keysym),
+        // Keysyms Unicode: 0x01000000 + codigo
+        0x0100_0000..=0x0110_ffff => char::from_u32(keysym - 0x0100_0000),
+        _ => None,
+    }
+}
+
+pub(crate) fn input_error(error: impl std::fmt::Display) -> AppError {
+    AppError::Input(format!("X11: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use x11rb::protocol::xkb::KTMapEntry;
+    use x11rb::protocol::xproto::ModMask;
+
+    const SHIFT: u16 = 1;
+    const LOCK: u16 = 2;
+
+    fn key_type(mods_mask: u16, levels: &[(u16, u8)]) -> KeyType {
+        KeyType {
+            mods_mask: ModMask::from(mods_mask),
+            mods_mods: ModMask::from(mods_mask),
+            mods_vmods: 0u16.into(),
+            num_levels: levels.len() as u8 + 1,
+            has_preserve: false,
+            map: levels
+                .iter()
+                .map(|(mods, level)| KTMapEntry {
+                    active: true,
+                    mods_mask: ModMask::from(*mods),
+                    level: *level,
+                    mods_mods: ModMask::from(*mods),
+                    mods_vmods: 0u16.into(),
+                })
+                .collect(),
+            preserve: Vec::new(),
+        }
+    }
+
+    fn key(kt_index: u8, groups: u8, width: u8, syms: &[Keysym]) -> KeySymMap {
+        KeySymMap {
+            kt_index: [kt_index; 4],
+            group_info: groups,
+            width,
+            syms: syms.to_vec(),
+        }
+    }
+
+    /// Teclado minimo: keycode 8 = Escape (un nivel), 9 = a/A (Shift),
+    /// 10 = s/S en el grupo 1 y una letra cirilica en el grupo 2
+    fn keymap() -> XkbKeymap {
+        XkbKeymap::new(
+            8,
+            vec![
+                key_type(0, &[]),
+                key_type(SHIFT | LOCK, &[(SHIFT, 1), (LOCK, 1)]),
+            ],
+            vec![
+                key(0, 1, 1, &[XK_ESCAPE]),
+                key(1, 1, 2, &['a' as u32, 'A' as u32]),
+                key(1, 2, 2, &['s' as u32, 'S' as u32, 0x0100_044b, 0x0100_042b]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_modifiers_select_level() {
+        let keymap = keymap();
+        assert_eq!(keymap.keysym(9, 0), Some('a' as u32));
+        assert_eq!(keymap.keysym(9, SHIFT), Some('A' as u32));
+        assert_eq!(keymap.keysym(9, LOCK), Some('A' as u32));
+        // Shift+Lock no tiene entrada en el tipo: nivel base
+        assert_eq!(keymap.keysym(9, SHIFT | LOCK), Some('a' as u32));
+        // Control no forma parte del tipo y no cambia el nivel
+        assert_eq!(keymap.keysym(9, 4), Some('a' as u32));
+    }
+
+    #[test]
+    fn test_group_selects_layout() {
+        let keymap = keymap();
+        let second_group = 1 << 13;
+        assert_eq!(keymap.nav_key(10, 0), Some(NavKey::Char('s')));
+        assert_eq!(keymap.nav_key(10, second_group), Some(NavKey::Char('ы')));
+        // Tecla con un solo grupo: el grupo 2 vuelve al 1
+        assert_eq!(keymap.nav_key(9, second_group), Some(NavKey::Char('a')));
+    }
+
+    #[test]
+    fn test_special_and_unknown_keys() {
+        let keymap = keymap();
+        assert_eq!(keymap.nav_key(8, 0), Some(NavKey::Escape));
+        assert_eq!(keymap.nav_key(7, 0), None);
+        assert_eq!(keymap.nav_key(200, 0), None);
+        assert_eq!(keysym_to_nav_key(XK_BACKSPACE), Some(NavKey::Backspace));
+        // Flecha arriba: no es tecla de navegacion por hints
+        assert_eq!(keysym_to_nav_key(0xff52), None);
+    }
+}
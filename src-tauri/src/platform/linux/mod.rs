@@ -0,0 +1,5 @@
+pub mod input_grab;
+pub mod keyboard_layout;
+pub mod keymap;
+pub mod pointer;
+pub mod window_list;
@@ -0,0 +1,58 @@
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    ConnectionExt as _, BUTTON_PRESS_EVENT, BUTTON_RELEASE_EVENT, MOTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::xtest::ConnectionExt as _;
+use x11rb::CURRENT_TIME;
+
+use crate::models::LogicalPoint;
+use crate::{AppError, Result};
+
+/// Boton izquierdo en X11
+const LEFT_BUTTON: u8 = 1;
+
+/// Click izquierdo en un punto global con XTest. En X11 no hay escala
+/// entre puntos y pixeles: se redondea al pixel mas cercano
+pub fn post_mouse_click(point: LogicalPoint) -> Result<()> {
+    tracing::info!("Simulating mouse click at ({}, {})", point.x, point.y);
+
+    let (conn, screen) = x11rb::connect(None).map_err(click_error)?;
+    let root = conn.setup().roots[screen].root;
+    let x = point.x.round() as i16;
+    let y = point.y.round() as i16;
+
+    conn.xtest_fake_input(MOTION_NOTIFY_EVENT, 0, CURRENT_TIME, root, x, y, 0)
+        .map_err(click_error)?;
+    for kind in [BUTTON_PRESS_EVENT, BUTTON_RELEASE_EVENT] {
+        conn.xtest_fake_input(kind, LEFT_BUTTON, CURRENT_TIME, root, 0, 0, 0)
+            .map_err(click_error)?;
+    }
+    // Esperar una respuesta garantiza que el servidor proceso los eventos
+    // antes de cerrar la conexion
+    conn.get_input_focus()
+        .map_err(click_error)?
+        .reply()
+        .map_err(click_error)?;
+
+    Ok(())
+}
+
+fn click_error(error: impl std::fmt::Display) -> AppError {
+    AppError::Click(format!("X11: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "necesita un servidor X: xvfb-run cargo test -- --ignored"]
+    fn test_click_moves_pointer_to_point() {
+        post_mouse_click(LogicalPoint::new(40.4, 25.6)).unwrap();
+
+        let (conn, screen) = x11rb::connect(None).unwrap();
+        let root = conn.setup().roots[screen].root;
+        let pointer = conn.query_pointer(root).unwrap().reply().unwrap();
+        assert_eq!((pointer.root_x, pointer.root_y), (40, 26));
+    }
+}
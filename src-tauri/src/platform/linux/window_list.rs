@@ -0,0 +1,223 @@
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, MapState, Window};
+
+use crate::models::{LogicalRect, StackedWindow, WindowStack};
+use crate::{AppError, Result};
+
+/// Ventanas gestionadas de atras hacia delante (EWMH)
+const NET_CLIENT_LIST_STACKING: &[u8] = b"_NET_CLIENT_LIST_STACKING";
+const NET_ACTIVE_WINDOW: &[u8] = b"_NET_ACTIVE_WINDOW";
+const NET_WM_PID: &[u8] = b"_NET_WM_PID";
+
+/// Ventanas en pantalla ordenadas de delante hacia atras.
+///
+/// El orden de las ventanas gestionadas sale de `_NET_CLIENT_LIST_STACKING`.
+/// Menus, popups y tooltips son override-redirect: el gestor de ventanas no
+/// los lista, asi que se toman de los hijos de la raiz y van delante de
+/// todo. Se omiten las ventanas sin mapear y las de esta aplicacion.
+pub fn get_window_stack() -> Result<WindowStack> {
+    let (conn, screen) = x11rb::connect(None).map_err(window_error)?;
+    let root = conn.setup().roots[screen].root;
+
+    let mut managed = get_windows(&conn, root, NET_CLIENT_LIST_STACKING)?;
+    managed.reverse();
+
+    let mut popups: Vec<Window> = Vec::new();
+    let children = conn
+        .query_tree(root)
+        .map_err(window_error)?
+        .reply()
+        .map_err(window_error)?
+        .children;
+    // Los hijos de la raiz vienen de atras hacia delante
+    for &window in children.iter().rev() {
+        let Ok(attributes) = conn
+            .get_window_attributes(window)
+            .map_err(window_error)?
+            .reply()
+        else {
+            continue;
+        };
+        if attributes.override_redirect && attributes.map_state == MapState::VIEWABLE {
+            popups.push(window);
+        }
+    }
+
+    let pid_atom = intern(&conn, NET_WM_PID)?;
+    let own_pid = std::process::id() as i32;
+    let mut windows = Vec::new();
+
+    for window in popups.into_iter().chain(managed) {
+        let Some(stacked) = stacked_window(&conn, root, window, pid_atom)? else {
+            continue;
+        };
+        if stacked.pid != Some(own_pid) {
+            windows.push(stacked);
+        }
+    }
+
+    Ok(WindowStack::new(windows))
+}
+
+/// XID de la ventana activa segun `_NET_ACTIVE_WINDOW`
+pub fn get_active_window() -> Result<u32> {
+    let (conn, screen) = x11rb::connect(None).map_err(window_error)?;
+    let root = conn.setup().roots[screen].root;
+
+    get_windows(&conn, root, NET_ACTIVE_WINDOW)?
+        .into_iter()
+        .find(|&window| window != x11rb::NONE)
+        .ok_or_else(|| AppError::Window("X11: no active window".to_string()))
+}
+
+/// Lista de ventanas guardada en una propiedad de la raiz (vacia si el
+/// gestor de ventanas no la publica)
+fn get_windows(conn: &impl Connection, root: Window, name: &[u8]) -> Result<Vec<Window>> {
+    let atom = intern(conn, name)?;
+    let property = conn
+        .get_property(false, root, atom, AtomEnum::WINDOW, 0, u32::MAX)
+        .map_err(window_error)?
+        .reply()
+        .map_err(window_error)?;
+
+    Ok(property
+        .value32()
+        .map(|windows| windows.collect())
+        .unwrap_or_default())
+}
+
+/// Frame global y PID de una ventana; None si no esta a la vista
+fn stacked_window(
+    conn: &impl Connection,
+    root: Window,
+    window: Window,
+    pid_atom: u32,
+) -> Result<Option<StackedWindow>> {
+    // La lista puede incluir ventanas minimizadas o ya destruidas
+    let Ok(attributes) = conn
+        .get_window_attributes(window)
+        .map_err(window_error)?
+        .reply()
+    else {
+        return Ok(None);
+    };
+    if attributes.map_state != MapState::VIEWABLE {
+        return Ok(None);
+    }
+
+    let geometry = conn
+        .get_geometry(window)
+        .map_err(window_error)?
+        .reply()
+        .map_err(window_error)?;
+    let origin = conn
+        .translate_coordinates(window, root, 0, 0)
+        .map_err(window_error)?
+        .reply()
+        .map_err(window_error)?;
+    let pid = conn
+        .get_property(false, window, pid_atom, AtomEnum::CARDINAL, 0, 1)
+        .map_err(window_error)?
+        .reply()
+        .map_err(window_error)?
+        .value32()
+        .and_then(|mut values| values.next())
+        .map(|pid| pid as i32);
+
+    Ok(Some(StackedWindow {
+        id: window,
+        pid,
+        frame: LogicalRect::new(
+            origin.dst_x.into(),
+            origin.dst_y.into(),
+            geometry.width.into(),
+            geometry.height.into(),
+        ),
+    }))
+}
+
+fn intern(conn: &impl Connection, name: &[u8]) -> Result<u32> {
+    Ok(conn
+        .intern_atom(false, name)
+        .map_err(window_error)?
+        .reply()
+        .map_err(window_error)?
+        .atom)
+}
+
+fn window_error(error: impl std::fmt::Display) -> AppError {
+    AppError::Window(format!("X11: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use x11rb::protocol::xproto::{CreateWindowAux, PropMode, WindowClass};
+    use x11rb::wrapper::ConnectionExt as _;
+    use x11rb::COPY_DEPTH_FROM_PARENT;
+
+    fn create_window(
+        conn: &impl Connection,
+        root: Window,
+        frame: (i16, i16, u16, u16),
+        override_redirect: bool,
+    ) -> Window {
+        let window = conn.generate_id().unwrap();
+        let (x, y, width, height) = frame;
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            window,
+            root,
+            x,
+            y,
+            width,
+            height,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            0,
+            &CreateWindowAux::new().override_redirect(u32::from(override_redirect)),
+        )
+        .unwrap();
+        window
+    }
+
+    #[test]
+    #[ignore = "necesita un servidor X: xvfb-run cargo test -- --ignored"]
+    fn test_stack_puts_popups_and_top_windows_first() {
+        let (conn, screen) = x11rb::connect(None).unwrap();
+        let root = conn.setup().roots[screen].root;
+
+        let back = create_window(&conn, root, (0, 0, 800, 600), false);
+        let front = create_window(&conn, root, (100, 100, 200, 100), false);
+        let menu = create_window(&conn, root, (120, 120, 80, 200), true);
+        // Listada pero sin mapear (ej: minimizada)
+        let hidden = create_window(&conn, root, (0, 0, 10, 10), false);
+        for window in [back, front, menu] {
+            conn.map_window(window).unwrap();
+        }
+
+        // Sin gestor de ventanas: se publican las propiedades a mano
+        let stacking = intern(&conn, NET_CLIENT_LIST_STACKING).unwrap();
+        let active = intern(&conn, NET_ACTIVE_WINDOW).unwrap();
+        conn.change_property32(
+            PropMode::REPLACE,
+            root,
+            stacking,
+            AtomEnum::WINDOW,
+            &[hidden, back, front],
+        )
+        .unwrap();
+        conn.change_property32(PropMode::REPLACE, root, active, AtomEnum::WINDOW, &[back])
+            .unwrap();
+        conn.get_input_focus().unwrap().reply().unwrap();
+
+        let stack = get_window_stack().unwrap();
+        let ids: Vec<u32> = stack.windows.iter().map(|window| window.id).collect();
+        assert_eq!(ids, vec![menu, front, back]);
+        assert_eq!(
+            stack.windows[1].frame,
+            LogicalRect::new(100.0, 100.0, 200.0, 100.0)
+        );
+        assert_eq!(get_active_window().unwrap(), back);
+    }
+}
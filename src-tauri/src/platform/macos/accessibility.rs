@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 use std::ffi::c_void;
 
 use crate::error::AppError;
+use crate::models::{LogicalRect, Role};
 use crate::Result;
 use core_foundation::array::CFArray;
 use core_foundation::base::{CFRelease, CFType, TCFType};
@@ -9,19 +10,12 @@ use core_foundation::boolean::CFBoolean;
 use core_foundation::dictionary::CFDictionary;
 use core_foundation::number::CFNumber;
 use core_foundation::string::CFString;
+use core_foundation::url::CFURL;
 
 // Tipos opacos de AXUIElement
 pub type AXUIElementRef = *const c_void;
 pub type AXValueRef = *const c_void;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Rect {
-    pub x: f64,
-    pub y: f64,
-    pub width: f64,
-    pub height: f64,
-}
-
 // FFI declaration para Accessibility API
 #[link(name = "ApplicationServices", kind = "framework")]
 extern "C" {
@@ -33,6 +27,11 @@ extern "C" {
         value: *mut *const c_void,
     ) -> i32;
     fn AXUIElementCopyAttributeNames(element: AXUIElementRef, names: *mut *const c_void) -> i32;
+    fn AXUIElementCopyActionNames(element: AXUIElementRef, names: *mut *const c_void) -> i32;
+    fn AXUIElementGetPid(element: AXUIElementRef, pid: *mut i32) -> i32;
+    // API privada (estable desde 10.x) usada por gestores de ventanas para
+    // obtener el CGWindowID de un AXUIElement de tipo ventana
+    fn _AXUIElementGetWindow(element: AXUIElementRef, window_id: *mut u32) -> i32;
     fn AXValueGetValue(value: AXValueRef, value_type: u32, value_ptr: *mut c_void) -> bool;
     fn CFRetain(cf: *const c_void) -> *const c_void;
 }
@@ -51,26 +50,14 @@ const K_AX_POSITION_ATTRIBUTE: &str = "AXPosition";
 const K_AX_SIZE_ATTRIBUTE: &str = "AXSize";
 const K_AX_ROLE_ATTRIBUTE: &str = "AXRole";
 const K_AX_CHILDREN_ATTRIBUTE: &str = "AXChildren";
-
-// Rolers clickeables
-const CLICKABLE_ROLES: &[&str] = &[
-    "AXButton",
-    "AXLink",
-    "AXMenuItem",
-    "AXMenuButton",
-    "AXPopUpButton",
-    "AXCheckBox",
-    "AXRadioButton",
-    "AXTextField",
-    "AXTextArea",
-    "AXComboBox",
-    "AXSlider",
-    "AXIncrementor",
-    "AXColorWell",
-    "AXDisclosureTriangle",
-    "AXTab",
-    "AXTabGroup",
-];
+const K_AX_VALUE_ATTRIBUTE: &str = "AXValue";
+const K_AX_URL_ATTRIBUTE: &str = "AXURL";
+const K_AX_DESCRIPTION_ATTRIBUTE: &str = "AXDescription";
+const K_AX_HELP_ATTRIBUTE: &str = "AXHelp";
+const K_AX_ENABLED_ATTRIBUTE: &str = "AXEnabled";
+const K_AX_FOCUSED_ATTRIBUTE: &str = "AXFocused";
+const K_AX_SELECTED_ATTRIBUTE: &str = "AXSelected";
+const K_AX_IDENTIFIER_ATTRIBUTE: &str = "AXIdentifier";
 
 // =============================================================================
 // RAII Wrapper para AXUIElementRef
@@ -264,6 +251,18 @@ pub fn get_focused_application() -> Result<Option<i32>> {
     }
 }
 
+/// Nombre de la aplicacion enfocada (su AXTitle, ej: "Safari")
+pub fn get_focused_application_name() -> Option<String> {
+    let system_wide = create_system_wide();
+    let focused_app =
+        copy_attribute_value(system_wide.as_ptr(), K_AX_FOCUSED_APPLICATION_ATTRIBUTE)
+            .map(AXElement::new_owned)
+            .map_err(|e| tracing::debug!("Failed to get focused application: {}", e))
+            .ok()?;
+
+    get_element_title(focused_app.as_ptr())
+}
+
 /// Obtiene la ventana activa del sistema (para escaneo de elementos)
 ///
 /// # Warning
@@ -290,8 +289,9 @@ pub fn get_active_window() -> Result<AXUIElementRef> {
     }
 }
 
-// Obtiene el rectangulo (posicion y tamaño) e un elemento
-pub fn get_element_rect(element: AXUIElementRef) -> Result<Rect> {
+// Obtiene el rectangulo (posicion y tamaño) e un elemento. AX da el frame
+// en el espacio global logico (puntos, origen arriba/izquierda)
+pub fn get_element_rect(element: AXUIElementRef) -> Result<LogicalRect> {
     // Obtener posicion
     let position_value = copy_attribute_value(element, K_AX_POSITION_ATTRIBUTE)?;
 
@@ -342,12 +342,7 @@ pub fn get_element_rect(element: AXUIElementRef) -> Result<Rect> {
         ));
     }
 
-    Ok(Rect {
-        x: point[0],
-        y: point[1],
-        width: size[0],
-        height: size[1],
-    })
+    Ok(LogicalRect::new(point[0], point[1], size[0], size[1]))
 }
 
 // Obtiene el rol de un elemento (ej: AXButton, AXLink, etc.)
@@ -377,6 +372,142 @@ pub fn get_element_title(element: AXUIElementRef) -> Option<String> {
     }
 }
 
+/// Convierte un valor CF en texto legible (string, numero, booleano o URL)
+fn cf_value_to_string(value: &CFType) -> Option<String> {
+    if let Some(string) = value.downcast::<CFString>() {
+        return Some(string.to_string());
+    }
+    if let Some(number) = value.downcast::<CFNumber>() {
+        return number
+            .to_i64()
+            .map(|n| n.to_string())
+            .or_else(|| number.to_f64().map(|n| n.to_string()));
+    }
+    if let Some(boolean) = value.downcast::<CFBoolean>() {
+        return Some(bool::from(boolean).to_string());
+    }
+    if let Some(url) = value.downcast::<CFURL>() {
+        return Some(url.get_string().to_string());
+    }
+    None
+}
+
+/// Obtiene un atributo como texto, sea cual sea su tipo CF
+fn get_attribute_as_text(element: AXUIElementRef, attribute: &str) -> Option<String> {
+    let value = copy_attribute_value(element, attribute).ok()?;
+
+    // SAFETY: wrap_under_create_rule toma ownership del valor copiado
+    let cf_value = unsafe { CFType::wrap_under_create_rule(value as *const _) };
+
+    cf_value_to_string(&cf_value).filter(|text| !text.is_empty())
+}
+
+/// Obtiene un atributo booleano (AXEnabled, AXFocused, AXSelected...)
+pub fn get_attribute_as_bool(element: AXUIElementRef, attribute: &str) -> Option<bool> {
+    let value = copy_attribute_value(element, attribute).ok()?;
+
+    // SAFETY: wrap_under_create_rule toma ownership del valor copiado
+    let cf_value = unsafe { CFType::wrap_under_create_rule(value as *const _) };
+
+    if let Some(boolean) = cf_value.downcast::<CFBoolean>() {
+        return Some(boolean.into());
+    }
+
+    // Algunas apps devuelven el booleano como CFNumber 0/1
+    cf_value
+        .downcast::<CFNumber>()
+        .and_then(|number| number.to_i32())
+        .map(|number| number != 0)
+}
+
+// Obtiene el valor de un elemento (texto, numero, URL de un link...)
+pub fn get_element_value(element: AXUIElementRef) -> Option<String> {
+    get_attribute_as_text(element, K_AX_VALUE_ATTRIBUTE)
+        .or_else(|| get_attribute_as_text(element, K_AX_URL_ATTRIBUTE))
+}
+
+// Obtiene el identificador que la app da al elemento (estable entre
+// ejecuciones; muchas apps no lo ponen)
+pub fn get_element_identifier(element: AXUIElementRef) -> Option<String> {
+    get_attribute_as_text(element, K_AX_IDENTIFIER_ATTRIBUTE)
+}
+
+// Obtiene la descripcion accesible, o el texto de ayuda si no tiene
+pub fn get_element_description(element: AXUIElementRef) -> Option<String> {
+    get_attribute_as_text(element, K_AX_DESCRIPTION_ATTRIBUTE)
+        .or_else(|| get_attribute_as_text(element, K_AX_HELP_ATTRIBUTE))
+}
+
+pub fn is_element_enabled(element: AXUIElementRef) -> Option<bool> {
+    get_attribute_as_bool(element, K_AX_ENABLED_ATTRIBUTE)
+}
+
+pub fn is_element_focused(element: AXUIElementRef) -> Option<bool> {
+    get_attribute_as_bool(element, K_AX_FOCUSED_ATTRIBUTE)
+}
+
+pub fn is_element_selected(element: AXUIElementRef) -> Option<bool> {
+    get_attribute_as_bool(element, K_AX_SELECTED_ATTRIBUTE)
+}
+
+/// Obtiene los nombres de las acciones soportadas (ej: AXPress, AXShowMenu)
+pub fn get_element_actions(element: AXUIElementRef) -> Result<Vec<String>> {
+    let mut names_ref: *const c_void = std::ptr::null();
+
+    // SAFETY: AXUIElementCopyActionNames es segura si element es válido
+    let result = unsafe { AXUIElementCopyActionNames(element, &mut names_ref) };
+
+    if result != 0 {
+        return Err(AppError::Accessibility(format!(
+            "Failed to get action names: AX error code {}",
+            result
+        )));
+    }
+
+    if names_ref.is_null() {
+        return Ok(Vec::new());
+    }
+
+    // SAFETY: wrap_under_create_rule toma ownership del CFArrayRef
+    let cf_array = unsafe { CFArray::<CFString>::wrap_under_create_rule(names_ref as *const _) };
+
+    Ok(cf_array.iter().map(|name| name.to_string()).collect())
+}
+
+/// Obtiene el PID de la aplicacion duena del elemento
+pub fn get_element_pid(element: AXUIElementRef) -> Result<i32> {
+    let mut pid: i32 = 0;
+
+    // SAFETY: AXUIElementGetPid solo escribe en `pid` si element es válido
+    let result = unsafe { AXUIElementGetPid(element, &mut pid) };
+
+    if result != 0 {
+        return Err(AppError::Accessibility(format!(
+            "Failed to get element PID: AX error code {}",
+            result
+        )));
+    }
+
+    Ok(pid)
+}
+
+/// Obtiene el CGWindowID de un elemento de tipo ventana
+pub fn get_window_id(window: AXUIElementRef) -> Result<u32> {
+    let mut window_id: u32 = 0;
+
+    // SAFETY: _AXUIElementGetWindow solo escribe en `window_id` si window es válido
+    let result = unsafe { _AXUIElementGetWindow(window, &mut window_id) };
+
+    if result != 0 {
+        return Err(AppError::Accessibility(format!(
+            "Failed to get window id: AX error code {}",
+            result
+        )));
+    }
+
+    Ok(window_id)
+}
+
 // Obtiene los hijos de un elemento
 pub fn get_children(element: AXUIElementRef) -> Result<Vec<AXUIElementRef>> {
     let children_value = match copy_attribute_value(element, K_AX_CHILDREN_ATTRIBUTE) {
@@ -406,85 +537,109 @@ pub fn get_children(element: AXUIElementRef) -> Result<Vec<AXUIElementRef>> {
     Ok(children)
 }
 
-// Verifica si un rol es clickeable
+/// Traduce un rol AX nativo al rol normalizado
+pub fn map_role(ax_role: &str) -> Role {
+    match ax_role {
+        "AXButton" => Role::Button,
+        "AXLink" => Role::Link,
+        "AXTextField" | "AXSearchField" => Role::TextField,
+        "AXTextArea" => Role::TextArea,
+        "AXCheckBox" => Role::CheckBox,
+        "AXRadioButton" => Role::RadioButton,
+        "AXMenuItem" | "AXMenuBarItem" => Role::MenuItem,
+        "AXMenuButton" => Role::MenuButton,
+        "AXPopUpButton" => Role::PopUpButton,
+        "AXComboBox" => Role::ComboBox,
+        "AXTab" => Role::Tab,
+        "AXTabGroup" => Role::TabGroup,
+        "AXSlider" => Role::Slider,
+        "AXIncrementor" => Role::Incrementor,
+        "AXColorWell" => Role::ColorWell,
+        "AXDisclosureTriangle" => Role::DisclosureTriangle,
+        "AXRow" | "AXOutlineRow" => Role::Row,
+        "AXCell" => Role::Cell,
+        "AXWindow" => Role::Window,
+        "AXGroup" | "AXSplitGroup" | "AXRadioGroup" => Role::Group,
+        "AXScrollArea" => Role::ScrollArea,
+        "AXToolbar" => Role::Toolbar,
+        "AXList" => Role::List,
+        "AXTable" | "AXOutline" => Role::Table,
+        "AXStaticText" => Role::StaticText,
+        "AXImage" => Role::Image,
+        other => Role::Other(other.to_string()),
+    }
+}
+
+// Verifica si un rol AX nativo es clickeable
 pub fn is_clickable_role(role: &str) -> bool {
-    CLICKABLE_ROLES.contains(&role)
+    map_role(role).is_clickable()
+}
+
+/// Nodo visitado durante un recorrido que conserva la estructura del árbol
+#[derive(Debug, Clone, Copy)]
+pub struct TraversedNode {
+    pub element: AXUIElementRef,
+    // Indice del padre en el vector devuelto (None para la raíz)
+    pub parent: Option<usize>,
+    pub depth: usize,
 }
 
-/// Recorre el árbol de accesibilidad usando BFS limitada
-/// Devuelve elementos clickeables encontrados
-pub fn traverse_accessibility_tree(
+/// Recorre el árbol de accesibilidad usando BFS limitada conservando los
+/// enlaces padre-hijo. El orden BFS garantiza que cada padre aparece antes
+/// que sus hijos.
+///
+/// # Warning
+/// Todos los elementos devueltos (incluida la raíz) están retenidos y el
+/// caller es responsable de liberarlos
+pub fn traverse_accessibility_tree_nodes(
     root: AXUIElementRef,
     max_depth: usize,
-    max_elements: usize,
-) -> Vec<AXUIElementRef> {
+    max_nodes: usize,
+) -> Vec<TraversedNode> {
     tracing::trace!(
-        "Traversing accessibility tree (max_depth: {}, max_elements: {})",
+        "Traversing accessibility tree with parents (max_depth: {}, max_nodes: {})",
         max_depth,
-        max_elements
+        max_nodes
     );
 
-    let mut clickable_elements = Vec::new();
-    let mut queue: VecDeque<(AXUIElementRef, usize)> = VecDeque::new();
-    let mut _visited_count = 0usize;
+    let mut nodes: Vec<TraversedNode> = Vec::new();
 
-    queue.push_back((root, 0));
+    if root.is_null() {
+        return nodes;
+    }
 
-    while let Some((element, depth)) = queue.pop_front() {
-        _visited_count += 1;
+    let mut queue: VecDeque<(AXUIElementRef, Option<usize>, usize)> = VecDeque::new();
 
-        // Límite de profundidad
-        if depth > max_depth {
-            continue;
-        }
+    // Retener la raíz para que todos los nodos tengan el mismo ownership
+    unsafe { CFRetain(root) };
+    queue.push_back((root, None, 0));
 
-        // Límite de elementos encontrados
-        if clickable_elements.len() >= max_elements {
-            tracing::trace!("Reached max elements limit: {}", max_elements);
-            break;
+    while let Some((element, parent, depth)) = queue.pop_front() {
+        if nodes.len() >= max_nodes {
+            tracing::trace!("Reached max nodes limit: {}", max_nodes);
+            unsafe { CFRelease(element) };
+            continue;
         }
 
-        // Verificar si el elemento es clickeable
-        if let Ok(role) = get_element_role(element) {
-            if is_clickable_role(&role) {
-                // Verificar que el elemento tiene un rectángulo válido
-                if let Ok(rect) = get_element_rect(element) {
-                    if rect.width > 0.0 && rect.height > 0.0 {
-                        tracing::trace!(
-                            "Found clickable element: {} at ({}, {}) {}x{}",
-                            role,
-                            rect.x,
-                            rect.y,
-                            rect.width,
-                            rect.height
-                        );
-                        unsafe { CFRetain(element) };
-                        clickable_elements.push(element);
-                    }
-                }
-            }
-        }
+        let index = nodes.len();
+        nodes.push(TraversedNode {
+            element,
+            parent,
+            depth,
+        });
 
-        // Añadir hijos a la cola
         if depth < max_depth {
             if let Ok(children) = get_children(element) {
                 for child in children {
-                    queue.push_back((child, depth + 1));
+                    queue.push_back((child, Some(index), depth + 1));
                 }
             }
         }
-        // Liberar el elemento actual si no es el root
-        if depth > 0 {
-            unsafe { CFRelease(element) };
-        }
     }
 
-    tracing::info!(
-        "Traversal complete: found {} clickable elements",
-        clickable_elements.len()
-    );
+    tracing::debug!("Traversal complete: visited {} nodes", nodes.len());
 
-    clickable_elements
+    nodes
 }
 
 /// Helper para obtener el valor de un atributo como String
@@ -497,7 +652,6 @@ pub fn get_attribute_as_string(element: AXUIElementRef, attribute: &str) -> Resu
     Ok(cf_string.to_string())
 }
 /// Obtiene los nombres de todos los atributos disponibles del elemento
-#[allow(dead_code)]
 pub fn get_attribute_names(element: AXUIElementRef) -> Result<Vec<String>> {
     let mut names_ref: *const c_void = std::ptr::null();
 
@@ -532,6 +686,22 @@ pub fn get_attribute_names(element: AXUIElementRef) -> Result<Vec<String>> {
     Ok(names)
 }
 
+/// Representa el valor de cualquier atributo como texto (para inspección)
+pub fn describe_attribute_value(element: AXUIElementRef, attribute: &str) -> Option<String> {
+    let value = copy_attribute_value(element, attribute).ok()?;
+
+    // SAFETY: wrap_under_create_rule toma ownership del valor copiado
+    let cf_value = unsafe { CFType::wrap_under_create_rule(value as *const _) };
+
+    // Los arrays (AXChildren, etc.) pueden ser enormes: solo su tamaño
+    if let Some(array) = cf_value.downcast::<CFArray>() {
+        return Some(format!("[{} items]", array.len()));
+    }
+
+    // AXValue (CGPoint, CGSize...), AXUIElement y demás: descripción de CoreFoundation
+    cf_value_to_string(&cf_value).or_else(|| Some(format!("{:?}", cf_value)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -543,6 +713,15 @@ mod tests {
         assert!(!K_AX_PID_ATTRIBUTE.is_empty());
     }
 
+    #[test]
+    fn test_rect_default() {
+        let rect = LogicalRect::new(10.0, 20.0, 100.0, 50.0);
+        assert_eq!(rect.x, 10.0);
+        assert_eq!(rect.y, 20.0);
+        assert_eq!(rect.width, 100.0);
+        assert_eq!(rect.height, 50.0);
+    }
+
     #[test]
     fn test_clickable_roles() {
         assert!(is_clickable_role("AXButton"));
@@ -552,17 +731,14 @@ mod tests {
     }
 
     #[test]
-    fn test_rect_default() {
-        let rect = Rect {
-            x: 10.0,
-            y: 20.0,
-            width: 100.0,
-            height: 50.0,
-        };
-        assert_eq!(rect.x, 10.0);
-        assert_eq!(rect.y, 20.0);
-        assert_eq!(rect.width, 100.0);
-        assert_eq!(rect.height, 50.0);
+    fn test_map_role() {
+        assert_eq!(map_role("AXButton"), Role::Button);
+        assert_eq!(map_role("AXSearchField"), Role::TextField);
+        assert_eq!(map_role("AXMenuBarItem"), Role::MenuItem);
+        assert_eq!(
+            map_role("AXSplitter"),
+            Role::Other("AXSplitter".to_string())
+        );
     }
 
     #[test]
@@ -573,9 +749,8 @@ mod tests {
     }
 
     #[test]
-    fn test_traverse_empty_returns_empty() {
-        let result = traverse_accessibility_tree(std::ptr::null(), 10, 100);
-        // Con un puntero nulo, debería devolver vacío o fallar graciosamente
+    fn test_traverse_nodes_empty_returns_empty() {
+        let result = traverse_accessibility_tree_nodes(std::ptr::null(), 10, 100);
         assert!(result.is_empty());
     }
 }
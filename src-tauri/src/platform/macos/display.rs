@@ -0,0 +1,50 @@
+use core_graphics::display::CGDisplay;
+
+use crate::models::{LogicalRect, Screen, ScreenLayout};
+
+/// Pantallas activas en coordenadas globales (origen arriba/izquierda de la
+/// pantalla principal, igual que los frames AX), con la principal primero
+pub fn get_screen_layout() -> ScreenLayout {
+    let mut displays = match CGDisplay::active_displays() {
+        Ok(displays) => displays,
+        Err(e) => {
+            tracing::warn!("Failed to list active displays: {}", e);
+            return ScreenLayout::default();
+        }
+    };
+
+    let main = CGDisplay::main().id;
+    displays.sort_by_key(|&id| id != main);
+
+    let screens = displays
+        .into_iter()
+        .map(|id| {
+            let display = CGDisplay::new(id);
+            let bounds = display.bounds();
+            // Pixeles del modo actual por punto (2.0 en Retina)
+            let scale_factor = if bounds.size.width > 0.0 {
+                display.pixels_wide() as f64 / bounds.size.width
+            } else {
+                1.0
+            };
+
+            Screen::new(
+                id,
+                LogicalRect::new(
+                    bounds.origin.x,
+                    bounds.origin.y,
+                    bounds.size.width,
+                    bounds.size.height,
+                ),
+                scale_factor,
+            )
+        })
+        .collect();
+
+    ScreenLayout::new(screens)
+}
+
+/// Frames de las pantallas activas en coordenadas globales
+pub fn get_screen_frames() -> Vec<LogicalRect> {
+    get_screen_layout().frames()
+}
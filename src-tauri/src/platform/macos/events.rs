@@ -1,3 +1,4 @@
+use crate::models::LogicalPoint;
 use crate::{AppError, Result};
 use core_graphics::event::{CGEvent, CGEventTapLocation, CGEventType, CGMouseButton};
 use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
@@ -16,15 +17,17 @@ fn create_mouse_event(
     })
 }
 
-pub fn post_mouse_click(x: f64, y: f64) -> Result<()> {
-    tracing::info!("Simulating mouse click at ({}, {})", x, y);
+/// Click izquierdo en un punto global logico: CGEvent usa el mismo espacio
+/// (puntos, origen arriba/izquierda de la pantalla principal), sin escalar
+pub fn post_mouse_click(point: LogicalPoint) -> Result<()> {
+    tracing::info!("Simulating mouse click at ({}, {})", point.x, point.y);
 
     let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState).map_err(|_| {
         tracing::error!("Failed to create CGEventSource");
         AppError::Click("Failed to create CGEventSource".to_string())
     })?;
 
-    let location = CGPoint::new(x, y);
+    let location = CGPoint::new(point.x, point.y);
 
     let mouse_down = create_mouse_event(
         source.clone(),
@@ -44,6 +47,6 @@ pub fn post_mouse_click(x: f64, y: f64) -> Result<()> {
     )?;
     mouse_up.post(CGEventTapLocation::HID);
 
-    tracing::info!("Mouse click simulated successfully at ({}, {})", x, y);
+    tracing::info!("Mouse click simulated successfully at ({}, {})", point.x, point.y);
     Ok(())
 }
@@ -0,0 +1,158 @@
+use std::ffi::c_void;
+
+use core_foundation::base::{CFRelease, TCFType};
+use core_foundation_sys::data::{CFDataGetBytePtr, CFDataRef};
+use core_foundation::string::{CFString, CFStringRef};
+use keyboard_types::Code;
+
+use crate::models::{KeyboardLayout, KeymapChars};
+
+// UCKeyTranslate: tecla pulsada, sin modificadores ni teclas muertas
+const K_UC_KEY_ACTION_DOWN: u16 = 0;
+const K_UC_KEY_TRANSLATE_NO_DEAD_KEYS_MASK: u32 = 1;
+// Una tecla escribe como mucho unos pocos UTF-16
+const MAX_KEY_CHARS: usize = 4;
+
+// FFI de Text Input Sources y Unicode Utilities (Carbon)
+#[link(name = "Carbon", kind = "framework")]
+extern "C" {
+    static kTISPropertyInputSourceID: CFStringRef;
+    static kTISPropertyUnicodeKeyLayoutData: CFStringRef;
+    fn TISCopyCurrentKeyboardLayoutInputSource() -> *const c_void;
+    fn TISGetInputSourceProperty(source: *const c_void, key: CFStringRef) -> *const c_void;
+    fn LMGetKbdType() -> u8;
+    #[allow(clippy::too_many_arguments)]
+    fn UCKeyTranslate(
+        key_layout: *const c_void,
+        virtual_key_code: u16,
+        key_action: u16,
+        modifier_key_state: u32,
+        keyboard_type: u32,
+        key_translate_options: u32,
+        dead_key_state: *mut u32,
+        max_string_length: usize,
+        actual_string_length: *mut usize,
+        unicode_string: *mut u16,
+    ) -> i32;
+}
+
+/// Layout de la fuente de entrada de teclado activa, o `None` si macOS no
+/// la devuelve
+pub fn current_layout() -> Option<KeyboardLayout> {
+    // SAFETY: la fuente sigue la regla Copy (se libera aqui); la propiedad
+    // sigue la regla Get y se copia a un CFString propio antes de liberar
+    unsafe {
+        let source = TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            tracing::warn!("TISCopyCurrentKeyboardLayoutInputSource returned null");
+            return None;
+        }
+
+        let id = TISGetInputSourceProperty(source, kTISPropertyInputSourceID);
+        let source_id =
+            (!id.is_null()).then(|| CFString::wrap_under_get_rule(id as CFStringRef).to_string());
+        CFRelease(source);
+
+        tracing::debug!("Current keyboard input source: {:?}", source_id);
+        source_id.map(|id| KeyboardLayout::from_mac_source_id(&id))
+    }
+}
+
+/// Lo que escribe cada tecla de letras con la fuente de entrada activa, o
+/// `None` si no tiene tabla Unicode (metodos de entrada como el japones).
+/// Text Input Sources solo se puede llamar desde el hilo principal
+pub fn current_keymap() -> Option<KeymapChars> {
+    // SAFETY: la fuente sigue la regla Copy y se libera al final; la tabla
+    // (regla Get) pertenece a la fuente y solo se usa mientras vive
+    unsafe {
+        let source = TISCopyCurrentKeyboardLayoutInputSource();
+        if source.is_null() {
+            tracing::warn!("TISCopyCurrentKeyboardLayoutInputSource returned null");
+            return None;
+        }
+
+        let data = TISGetInputSourceProperty(source, kTISPropertyUnicodeKeyLayoutData);
+        let keymap = (!data.is_null()).then(|| {
+            let layout = CFDataGetBytePtr(data as CFDataRef) as *const c_void;
+            let keyboard_type = u32::from(LMGetKbdType());
+            KeymapChars::translate(|code| translate_key(layout, keyboard_type, code))
+        });
+        CFRelease(source);
+
+        if keymap.is_none() {
+            tracing::debug!("Keyboard input source without a Unicode key layout");
+        }
+        keymap
+    }
+}
+
+/// Caracter de una tecla sin modificadores
+///
+/// # Safety
+/// `layout` debe apuntar a un `UCKeyboardLayout` valido
+unsafe fn translate_key(layout: *const c_void, keyboard_type: u32, code: Code) -> Option<char> {
+    let mut dead_key_state = 0;
+    let mut length = 0;
+    let mut buffer = [0u16; MAX_KEY_CHARS];
+    let status = UCKeyTranslate(
+        layout,
+        virtual_keycode(code)?,
+        K_UC_KEY_ACTION_DOWN,
+        0,
+        keyboard_type,
+        K_UC_KEY_TRANSLATE_NO_DEAD_KEYS_MASK,
+        &mut dead_key_state,
+        MAX_KEY_CHARS,
+        &mut length,
+        buffer.as_mut_ptr(),
+    );
+    if status != 0 || length == 0 {
+        return None;
+    }
+
+    // Solo teclas que escriben un unico caracter visible
+    let mut chars = char::decode_utf16(buffer[..length].iter().copied());
+    match (chars.next(), chars.next()) {
+        (Some(Ok(ch)), None) if !ch.is_control() => Some(ch),
+        _ => None,
+    }
+}
+
+/// Codigo de tecla virtual (kVK_ANSI_*) de una posicion; en ISO y JIS son
+/// los mismos para estas teclas
+fn virtual_keycode(code: Code) -> Option<u16> {
+    let keycode = match code {
+        Code::KeyA => 0x00,
+        Code::KeyS => 0x01,
+        Code::KeyD => 0x02,
+        Code::KeyF => 0x03,
+        Code::KeyH => 0x04,
+        Code::KeyG => 0x05,
+        Code::KeyZ => 0x06,
+        Code::KeyX => 0x07,
+        Code::KeyC => 0x08,
+        Code::KeyV => 0x09,
+        Code::KeyB => 0x0b,
+        Code::KeyQ => 0x0c,
+        Code::KeyW => 0x0d,
+        Code::KeyE => 0x0e,
+        Code::KeyR => 0x0f,
+        Code::KeyY => 0x10,
+        Code::KeyT => 0x11,
+        Code::KeyO => 0x1f,
+        Code::KeyU => 0x20,
+        Code::KeyI => 0x22,
+        Code::KeyP => 0x23,
+        Code::KeyL => 0x25,
+        Code::KeyJ => 0x26,
+        Code::KeyK => 0x28,
+        Code::Semicolon => 0x29,
+        Code::Comma => 0x2b,
+        Code::Slash => 0x2c,
+        Code::KeyN => 0x2d,
+        Code::KeyM => 0x2e,
+        Code::Period => 0x2f,
+        _ => return None,
+    };
+    Some(keycode)
+}
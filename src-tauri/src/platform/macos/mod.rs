@@ -1,4 +1,7 @@
 pub mod accessibility;
+pub mod display;
 pub mod events;
+pub mod keyboard_layout;
 pub mod overlay;
+pub mod window_list;
  
\ No newline at end of file
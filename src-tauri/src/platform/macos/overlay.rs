@@ -1,25 +1,61 @@
+use std::collections::{HashMap, HashSet};
+
+use core_foundation::base::TCFType;
+use core_graphics::data_provider::CGDataProvider;
+use core_graphics::font::CGFont;
 use objc2::rc::Retained;
-use objc2_app_kit::{NSBackingStoreType, NSScreen, NSWindow, NSWindowStyleMask};
-use objc2_core_foundation::{CGPoint, CGRect, CGSize};
-use objc2_foundation::{MainThreadMarker, NSPoint, NSRect, NSSize, NSString};
+use objc2::runtime::AnyObject;
+use objc2::AllocAnyThread;
+use objc2_app_kit::{NSBackingStoreType, NSWindow, NSWindowStyleMask};
+use objc2_core_foundation::{
+  CFRetained, CFString, CFType, CGAffineTransform, CGPoint, CGRect, CGSize,
+};
+use objc2_core_graphics::CGColor;
+use objc2_core_text::{kCTFontAttributeName, kCTForegroundColorAttributeName, CTFont};
+use objc2_foundation::{
+  MainThreadMarker, NSMutableAttributedString, NSPoint, NSRange, NSRect, NSSize, NSString,
+};
 use objc2_quartz_core::{kCAAlignmentCenter, CALayer, CATextLayer, CATransaction};
 
 use crate::error::AppError;
-use crate::models::Hint;
+use crate::models::hint_style::{BUNDLED_FONT_FAMILY, TYPED_ALPHA, UNIQUE_BORDER_EXTRA};
+use crate::models::{
+  Color, HintStyle, LabelBox, LabelState, LogicalPoint, LogicalRect, SceneNode, SceneOp, Screen, ScreenLayout,
+};
+use crate::platform::macos::display::get_screen_layout;
+use crate::services::text_measure::HINT_FONT;
 use crate::services::window_manager::OverlayRenderer;
 use crate::Result;
 
-const HINT_FONT_SIZE: f64 = 14.0;
-const HINT_PADDING: f64 = 6.0;
-const HINT_CORNER_RADIUS: f64 = 4.0;
+const LEADER_WIDTH: f64 = 1.0;
+
+/// Ventana transparente que cubre una pantalla
+struct Surface {
+  screen: Screen,
+  window: Retained<NSWindow>,
+  root: Retained<CALayer>,
+}
+
+/// Capas de un hint dibujado y el nodo de escena que representan
+struct HintLayers {
+  node: SceneNode,
+  // Pantalla (y por tanto superficie) donde esta dibujado
+  screen_id: u32,
+  text: Retained<CATextLayer>,
+  leader: Option<Retained<CALayer>>,
+}
 
-/// Overlay nativo simple en macOS usando NSWindow + CALayer
+/// Overlay nativo en macOS: una NSWindow + CALayer por pantalla con hints
 pub struct MacOverlay {
-  window: Option<Retained<NSWindow>>,
-  root_layer: Option<Retained<CALayer>>,
-  hint_layers: Vec<Retained<CATextLayer>>,
-  scale_factor: f64,
-  screen_height: f64,
+  // Pantallas de la escena actual; se leen al empezar cada escena
+  layout: ScreenLayout,
+  // Superficies por id de pantalla, creadas solo donde caen hints
+  surfaces: HashMap<u32, Surface>,
+  // Capas retenidas por `element_id`; las `SceneOp` las modifican en sitio
+  hints: HashMap<String, HintLayers>,
+  // Fuente empaquetada: la misma con la que el layout mide los labels
+  font: Option<CGFont>,
+  visible: bool,
   initialized: bool,
 }
 
@@ -30,11 +66,11 @@ impl MacOverlay {
   pub fn new() -> Self {
     tracing::debug!("Creating MacOverlay");
     Self {
-      window: None,
-      root_layer: None,
-      hint_layers: Vec::new(),
-      scale_factor: 1.0,
-      screen_height: 0.0,
+      layout: ScreenLayout::default(),
+      surfaces: HashMap::new(),
+      hints: HashMap::new(),
+      font: None,
+      visible: false,
       initialized: false,
     }
   }
@@ -46,56 +82,62 @@ impl MacOverlay {
     })
   }
 
-  /// Obtiene frame y escala de la pantalla principal
-  fn screen_info(&self) -> Result<(NSRect, f64)> {
-    let mtm = self.main_thread_marker()?;
+  /// Rectangulo global al espacio de la capa raiz de una pantalla (origen
+  /// abajo/izquierda de la pantalla)
+  fn local_frame(screen: &Screen, rect: &LogicalRect) -> CGRect {
+    let local = screen.rect_to_local(rect);
+    CGRect::new(
+      CGPoint::new(local.x, screen.frame.height - local.y - local.height),
+      CGSize::new(local.width, local.height),
+    )
+  }
 
-    let info = if let Some(screen) = NSScreen::mainScreen(mtm) {
-      let frame = screen.frame();
-      let scale = screen.backingScaleFactor() as f64;
-      (frame, scale)
-    } else {
-      (NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(1920.0, 1080.0)), 1.0)
-    };
+  fn local_point(screen: &Screen, point: LogicalPoint) -> CGPoint {
+    let local = screen.to_local(point);
+    CGPoint::new(local.x, screen.frame.height - local.y)
+  }
 
-    Ok(info)
+  /// Pantalla donde se dibuja una caja (la que contiene la mayor parte)
+  fn screen_for(&self, label_box: &LabelBox) -> Result<Screen> {
+    self
+      .layout
+      .screen_for(&label_box.rect)
+      .copied()
+      .ok_or_else(|| AppError::Overlay("No screens available for the overlay".to_string()))
   }
 
-  /// Ajusta el frame de ventana y capa raíz al tamaño de pantalla actual
-  fn resize_to_screen(&mut self, frame: NSRect) -> Result<()> {
-    if let Some(ref window) = self.window {
-      window.setFrame_display(frame, true);
+  /// Capa raiz de la superficie de una pantalla; crea la ventana si aun no existe
+  fn surface_root(&mut self, screen: Screen) -> Result<Retained<CALayer>> {
+    if let Some(surface) = self.surfaces.get(&screen.id) {
+      return Ok(surface.root.clone());
     }
 
-    if let Some(ref root) = self.root_layer {
-      let cg_frame = CGRect::new(
-        CGPoint::new(0.0, 0.0),
-        CGSize::new(frame.size.width as f64, frame.size.height as f64),
-      );
-      root.setFrame(cg_frame);
-      root.setContentsScale(self.scale_factor);
+    let surface = self.create_surface(screen)?;
+    let root = surface.root.clone();
+    if self.visible {
+      surface.window.makeKeyAndOrderFront(None);
     }
-
-    Ok(())
-  }
-
-  /// Convierte coordenadas Y (origen arriba) al sistema de AppKit (origen abajo)
-  fn convert_y(&self, y: f64, height: f64) -> f64 {
-    self.screen_height - y - height
+    self.surfaces.insert(screen.id, surface);
+    Ok(root)
   }
 
-  /// Crea la ventana transparente y la capa raíz
-  fn create_window(&mut self) -> Result<()> {
+  /// Crea la ventana transparente y la capa raíz que cubren una pantalla
+  fn create_surface(&self, screen: Screen) -> Result<Surface> {
     let mtm = self.main_thread_marker()?;
-    let (frame, scale) = self.screen_info()?;
-    self.scale_factor = scale;
-    self.screen_height = frame.size.height;
+    let cocoa = self.layout.rect_to_cocoa(&screen.frame);
+    let frame = NSRect::new(
+      NSPoint::new(cocoa.x, cocoa.y),
+      NSSize::new(cocoa.width, cocoa.height),
+    );
 
     tracing::debug!(
-      "Creating overlay window frame={}x{} scale={}",
+      "Creating overlay window for screen {} frame={}x{} at ({}, {}) scale={}",
+      screen.id,
       frame.size.width,
       frame.size.height,
-      self.scale_factor
+      frame.origin.x,
+      frame.origin.y,
+      screen.scale_factor
     );
 
     let window = unsafe {
@@ -108,80 +150,233 @@ impl MacOverlay {
       )
     };
 
-    // Configurar ventana transparente y click-through
+    // Configurar ventana transparente y click-through. La ventana la
+    // libera `Retained`, no `close`.
+    unsafe { window.setReleasedWhenClosed(false) };
     window.setOpaque(false);
     window.setHasShadow(false);
     window.setIgnoresMouseEvents(true);
 
-    // Configurar layer raíz
-    if let Some(content_view) = window.contentView() {
-      content_view.setWantsLayer(true);
+    let Some(content_view) = window.contentView() else {
+      return Err(AppError::Overlay("Overlay window has no content view".to_string()));
+    };
+    content_view.setWantsLayer(true);
 
-      let root_layer = CALayer::new();
-      let cg_frame = CGRect::new(
-        CGPoint::new(0.0, 0.0),
-        CGSize::new(frame.size.width as f64, frame.size.height as f64),
-      );
+    // Configurar layer raíz
+    let root = CALayer::new();
+    root.setFrame(CGRect::new(
+      CGPoint::new(0.0, 0.0),
+      CGSize::new(screen.frame.width, screen.frame.height),
+    ));
+    root.setContentsScale(screen.scale_factor);
+    content_view.setLayer(Some(&root));
+
+    Ok(Surface {
+      screen,
+      window,
+      root,
+    })
+  }
 
-      root_layer.setFrame(cg_frame);
-      root_layer.setContentsScale(self.scale_factor);
-      content_view.setLayer(Some(&root_layer));
+  /// Cierra las superficies de pantallas que se han quedado sin hints
+  fn close_unused_surfaces(&mut self) {
+    let used: HashSet<u32> = self.hints.values().map(|hint| hint.screen_id).collect();
+    self.surfaces.retain(|id, surface| {
+      if used.contains(id) {
+        return true;
+      }
+      tracing::debug!("Closing overlay window for screen {}", surface.screen.id);
+      surface.window.orderOut(None);
+      surface.window.close();
+      false
+    });
+  }
 
-      self.root_layer = Some(root_layer);
-    }
+  /// Marco y tamaño de fuente de la caja de un label
+  fn set_geometry(&self, layer: &CATextLayer, label_box: &LabelBox, screen: &Screen) {
+    let frame = Self::local_frame(screen, &label_box.rect);
+    layer.setFrame(frame);
+    layer.setFontSize(label_box.font_size);
+  }
 
-    self.window = Some(window);
-    self.resize_to_screen(frame)?;
+  /// Texto segun el estado: plano sin prefijo, con el prefijo atenuado al
+  /// escribir. Un label oculto conserva el que tenia.
+  fn set_text(&self, layer: &CATextLayer, node: &SceneNode) {
+    let label_box = &node.label_box;
 
-    Ok(())
+    // SAFETY: Configuración del layer en hilo principal
+    unsafe {
+      match node.state {
+        LabelState::Idle => layer.setString(Some(&NSString::from_str(&label_box.label))),
+        LabelState::Matching { typed } | LabelState::Unique { typed } => {
+          layer.setString(Some(&self.prefix_string(label_box, typed)))
+        }
+        LabelState::Hidden => {}
+      }
+    }
   }
 
-  /// Crea un CATextLayer para un hint
-  fn create_hint_layer(&self, hint: &Hint) -> Result<Retained<CATextLayer>> {
-    let layer = CATextLayer::new();
+  /// Configura por completo la caja de un nodo: geometria, estilo y estado
+  fn configure_hint_layer(&self, layer: &CATextLayer, node: &SceneNode, screen: &Screen) {
+    let style = &node.label_box.style;
 
-    let text = NSString::from_str(&hint.label);
+    let background = cg_color(style.background);
+    let foreground = cg_color(style.foreground);
+    let border = cg_color(style.border_color);
+    let font_name = NSString::from_str(&style.font_family);
 
-    // Tamaños
-    let text_width = hint.label.len() as f64 * HINT_FONT_SIZE * 0.8;
-    let layer_width = (text_width + HINT_PADDING * 2.5).max(HINT_FONT_SIZE * 2.0);
-    let layer_height = HINT_FONT_SIZE + HINT_PADDING * 2.5;
-
-    // Coordenadas
-    let x = hint.x - layer_width / 2.0;
-    let y = self.convert_y(hint.y, layer_height);
+    // El match unico se destaca con un borde mas grueso y por encima del resto
+    let (border_width, z_position) = match node.state {
+      LabelState::Unique { .. } => (style.border_width + UNIQUE_BORDER_EXTRA, 1.0),
+      _ => (style.border_width, 0.0),
+    };
 
-    let frame = CGRect::new(
-      CGPoint::new(x, y),
-      CGSize::new(layer_width, layer_height),
-    );
+    self.set_geometry(layer, &node.label_box, screen);
 
-    // SAFETY: Configuración del layer en hilo principal
+    // SAFETY: Configuración del layer en hilo principal. CGFontRef y NSString
+    // son CFTypeRef válidos (NSString por toll-free bridging) que viven hasta
+    // el final de la función; el layer los retiene.
     unsafe {
-      layer.setFrame(frame);
-      layer.setCornerRadius(HINT_CORNER_RADIUS);
-      layer.setString(Some(&text));
-      layer.setFontSize(HINT_FONT_SIZE);
+      layer.setCornerRadius(style.corner_radius);
+      layer.setBackgroundColor(Some(&background));
+      layer.setBorderColor(Some(&border));
+      layer.setBorderWidth(border_width);
+      layer.setZPosition(z_position);
+      layer.setOpacity(style.opacity as f32);
+      layer.setHidden(node.state == LabelState::Hidden);
+      layer.setForegroundColor(Some(&foreground));
+      match self.font {
+        // La fuente empaquetada es la misma con la que el layout midio; por
+        // nombre solo si no se pudo cargar
+        Some(ref font) if style.font_family == BUNDLED_FONT_FAMILY => {
+          layer.setFont(Some(&*(font.as_CFTypeRef() as *const CFType)));
+        }
+        _ => layer.setFont(Some(&*(Retained::as_ptr(&font_name) as *const CFType))),
+      }
       layer.setAlignmentMode(kCAAlignmentCenter);
-      layer.setContentsScale(self.scale_factor);
+      layer.setContentsScale(screen.scale_factor);
     }
 
-    Ok(layer)
+    self.set_text(layer, node);
+  }
+
+  /// Rehace la linea guia de un hint: es una capa barata y cambia entera
+  /// cuando se mueve la caja
+  fn rebuild_leader(&self, root: &CALayer, screen: &Screen, hint: &mut HintLayers) {
+    if let Some(old) = hint.leader.take() {
+      old.removeFromSuperlayer();
+    }
+
+    let Some(leader) = hint.node.label_box.leader else {
+      return;
+    };
+    let style = &hint.node.label_box.style;
+    let layer = CALayer::new();
+
+    let from = Self::local_point(screen, leader.from);
+    let to = Self::local_point(screen, leader.to);
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let (sin, cos) = dy.atan2(dx).sin_cos();
+
+    // La capa se ancla en su extremo izquierdo y se rota hacia el label
+    layer.setBounds(CGRect::new(
+      CGPoint::new(0.0, 0.0),
+      CGSize::new(dx.hypot(dy), LEADER_WIDTH),
+    ));
+    layer.setAnchorPoint(CGPoint::new(0.0, 0.5));
+    layer.setPosition(from);
+    layer.setAffineTransform(CGAffineTransform {
+      a: cos,
+      b: sin,
+      c: -sin,
+      d: cos,
+      tx: 0.0,
+      ty: 0.0,
+    });
+    // La linea usa el color de borde de su caja y va debajo de las cajas
+    layer.setBackgroundColor(Some(&cg_color(style.border_color)));
+    layer.setOpacity(style.opacity as f32);
+    layer.setZPosition(-1.0);
+    layer.setHidden(hint.node.state == LabelState::Hidden);
+    layer.setContentsScale(screen.scale_factor);
+
+    root.addSublayer(&layer);
+    hint.leader = Some(layer);
+  }
+
+  fn remove_hint_layers(hint: &HintLayers) {
+    hint.text.removeFromSuperlayer();
+    if let Some(ref leader) = hint.leader {
+      leader.removeFromSuperlayer();
+    }
   }
 
   fn clear_hint_layers(&mut self) {
-    if self.hint_layers.is_empty() {
+    if self.hints.is_empty() {
       return;
     }
 
     CATransaction::begin();
     CATransaction::setDisableActions(true);
-    for layer in &self.hint_layers {
-      layer.removeFromSuperlayer();
+    for hint in self.hints.values() {
+      Self::remove_hint_layers(hint);
     }
     CATransaction::commit();
 
-    self.hint_layers.clear();
+    self.hints.clear();
+  }
+
+  /// CTFont del estilo: la fuente empaquetada o, si no se pudo cargar, la
+  /// familia por nombre
+  fn ct_font(&self, style: &HintStyle, size: f64) -> CFRetained<CTFont> {
+    // SAFETY: CGFontRef de core-graphics y CGFont de objc2 son el mismo tipo
+    // CF; sin matriz ni descriptor extra
+    unsafe {
+      match self.font {
+        Some(ref font) if style.font_family == BUNDLED_FONT_FAMILY => {
+          let font = &*(font.as_CFTypeRef() as *const objc2_core_graphics::CGFont);
+          CTFont::with_graphics_font(font, size, std::ptr::null(), None)
+        }
+        _ => CTFont::with_name(&CFString::from_str(&style.font_family), size, std::ptr::null()),
+      }
+    }
+  }
+
+  /// Texto del label con los `typed` primeros caracteres atenuados
+  fn prefix_string(&self, label_box: &LabelBox, typed: usize) -> Retained<NSMutableAttributedString> {
+    let style = &label_box.style;
+    let text = NSString::from_str(&label_box.label);
+    let string = NSMutableAttributedString::initWithString(NSMutableAttributedString::alloc(), &text);
+
+    let font = self.ct_font(style, label_box.font_size);
+    let (r, g, b, a) = style.foreground.components();
+    let foreground = CGColor::new_srgb(r, g, b, a);
+    let dimmed = CGColor::new_srgb(r, g, b, a * TYPED_ALPHA);
+
+    // NSRange cuenta unidades UTF-16, no caracteres
+    let typed_len = label_box.label.chars().take(typed).map(char::len_utf16).sum();
+
+    // SAFETY: las claves CoreText son CFString (toll-free bridged con
+    // NSString) y los valores son objetos CF validos durante la llamada
+    unsafe {
+      string.addAttribute_value_range(
+        cf_key(kCTFontAttributeName),
+        cf_object(&*font),
+        NSRange::new(0, text.length()),
+      );
+      string.addAttribute_value_range(
+        cf_key(kCTForegroundColorAttributeName),
+        cf_object(&*foreground),
+        NSRange::new(0, text.length()),
+      );
+      string.addAttribute_value_range(
+        cf_key(kCTForegroundColorAttributeName),
+        cf_object(&*dimmed),
+        NSRange::new(0, typed_len),
+      );
+    }
+
+    string
   }
 }
 
@@ -192,70 +387,156 @@ impl OverlayRenderer for MacOverlay {
     }
 
     self.main_thread_marker()?;
-    self.create_window()?;
+    self.font = CGFont::from_data_provider(CGDataProvider::from_slice(HINT_FONT))
+      .map_err(|_| tracing::warn!("Failed to load bundled hint font, using system font"))
+      .ok();
     self.initialized = true;
     Ok(())
   }
 
-  fn draw_hints(&mut self, hints: &[Hint]) -> Result<()> {
+  fn apply(&mut self, ops: &[SceneOp]) -> Result<()> {
     if !self.initialized {
       return Err(AppError::Overlay("Overlay not initialized".to_string()));
     }
 
-    let (frame, scale) = self.screen_info()?;
-    self.scale_factor = scale;
-    self.screen_height = frame.size.height;
-    self.resize_to_screen(frame)?;
-
-    self.clear_hint_layers();
-
-    let Some(root) = self.root_layer.clone() else {
-      return Err(AppError::Overlay("Root layer not available".to_string()));
-    };
+    // Escena nueva (tras ocultar): releer pantallas, posiciones y escalas
+    if self.hints.is_empty() {
+      self.layout = get_screen_layout();
+      tracing::debug!("MacOverlay: {} screens", self.layout.screens().len());
+    }
 
     CATransaction::begin();
     CATransaction::setDisableActions(true);
-
-    for hint in hints {
-      let hint_layer = self.create_hint_layer(hint)?;
-      root.addSublayer(&hint_layer);
-      self.hint_layers.push(hint_layer);
-    }
-
+    let result = self.apply_ops(ops);
     CATransaction::commit();
 
-    Ok(())
+    self.close_unused_surfaces();
+    result
   }
 
   fn show(&mut self) -> Result<()> {
-    if let Some(ref window) = self.window {
-      window.makeKeyAndOrderFront(None);
+    self.visible = true;
+    for surface in self.surfaces.values() {
+      surface.window.makeKeyAndOrderFront(None);
     }
     Ok(())
   }
 
   fn hide(&mut self) -> Result<()> {
     self.clear_hint_layers();
-
-    if let Some(ref window) = self.window {
-      window.orderOut(None);
-    }
+    self.visible = false;
+    self.close_unused_surfaces();
     Ok(())
   }
 
   fn teardown(&mut self) -> Result<()> {
-    self.clear_hint_layers();
+    self.hide()?;
+    self.initialized = false;
+    Ok(())
+  }
+}
 
-    if let Some(ref window) = self.window {
-      window.orderOut(None);
-      window.close();
+impl MacOverlay {
+  fn apply_ops(&mut self, ops: &[SceneOp]) -> Result<()> {
+    for op in ops {
+      match op {
+        SceneOp::Add { node } => {
+          let screen = self.screen_for(&node.label_box)?;
+          let root = self.surface_root(screen)?;
+
+          let text = CATextLayer::new();
+          self.configure_hint_layer(&text, node, &screen);
+          root.addSublayer(&text);
+
+          let mut hint = HintLayers {
+            node: node.clone(),
+            screen_id: screen.id,
+            text,
+            leader: None,
+          };
+          self.rebuild_leader(&root, &screen, &mut hint);
+          if let Some(previous) = self.hints.insert(node.element_id().to_string(), hint) {
+            Self::remove_hint_layers(&previous);
+          }
+        }
+        SceneOp::Move {
+          element_id,
+          rect,
+          font_size,
+          leader,
+        } => {
+          let Some(mut hint) = self.hints.remove(element_id) else {
+            tracing::warn!("MacOverlay: move for unknown hint {}", element_id);
+            continue;
+          };
+          hint.node.label_box.rect = *rect;
+          hint.node.label_box.font_size = *font_size;
+          hint.node.label_box.leader = *leader;
+
+          let (screen, root) = self.attach(&mut hint)?;
+          self.set_geometry(&hint.text, &hint.node.label_box, &screen);
+          // El texto atribuido lleva la fuente con su tamaño
+          if hint.node.state != LabelState::Idle {
+            self.set_text(&hint.text, &hint.node);
+          }
+          self.rebuild_leader(&root, &screen, &mut hint);
+          self.hints.insert(element_id.clone(), hint);
+        }
+        SceneOp::Update { node } => {
+          let Some(mut hint) = self.hints.remove(node.element_id()) else {
+            tracing::warn!("MacOverlay: update for unknown hint {}", node.element_id());
+            continue;
+          };
+          hint.node = node.clone();
+
+          let (screen, root) = self.attach(&mut hint)?;
+          self.configure_hint_layer(&hint.text, &hint.node, &screen);
+          self.rebuild_leader(&root, &screen, &mut hint);
+          self.hints.insert(node.element_id().to_string(), hint);
+        }
+        SceneOp::Remove { element_id } => {
+          if let Some(hint) = self.hints.remove(element_id) {
+            Self::remove_hint_layers(&hint);
+          }
+        }
+      }
     }
 
-    self.window = None;
-    self.root_layer = None;
-    self.initialized = false;
     Ok(())
   }
+
+  /// Pantalla y capa raiz de un hint tras moverse; si ha cambiado de
+  /// pantalla pasa su caja a la superficie nueva
+  fn attach(&mut self, hint: &mut HintLayers) -> Result<(Screen, Retained<CALayer>)> {
+    let screen = self.screen_for(&hint.node.label_box)?;
+    let root = self.surface_root(screen)?;
+
+    if screen.id != hint.screen_id {
+      hint.text.removeFromSuperlayer();
+      root.addSublayer(&hint.text);
+      hint.screen_id = screen.id;
+    }
+
+    Ok((screen, root))
+  }
+}
+
+/// Convierte un color del tema a CGColor (sRGB)
+fn cg_color(color: Color) -> CFRetained<CGColor> {
+  let (r, g, b, a) = color.components();
+  CGColor::new_srgb(r, g, b, a)
+}
+
+/// Clave CoreText usada como clave de NSAttributedString
+fn cf_key(key: &CFString) -> &NSString {
+  // SAFETY: CFString y NSString son toll-free bridged
+  unsafe { &*(key as *const CFString as *const NSString) }
+}
+
+/// Objeto CF usado como valor de un atributo
+fn cf_object<T>(value: &T) -> &AnyObject {
+  // SAFETY: solo se llama con tipos CF, que son objetos Objective-C
+  unsafe { &*(value as *const T as *const AnyObject) }
 }
 
 impl Default for MacOverlay {
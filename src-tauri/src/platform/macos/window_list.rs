@@ -0,0 +1,83 @@
+use core_foundation::base::{CFType, TCFType};
+use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+use core_foundation::number::CFNumber;
+use core_foundation::string::CFString;
+use core_graphics::geometry::CGRect;
+use core_graphics::window::{
+    copy_window_info, kCGNullWindowID, kCGWindowListExcludeDesktopElements,
+    kCGWindowListOptionOnScreenOnly,
+};
+
+use crate::models::{LogicalRect, StackedWindow, WindowStack};
+
+// Claves de los diccionarios de CGWindowListCopyWindowInfo
+const K_CG_WINDOW_NUMBER: &str = "kCGWindowNumber";
+const K_CG_WINDOW_OWNER_PID: &str = "kCGWindowOwnerPID";
+const K_CG_WINDOW_BOUNDS: &str = "kCGWindowBounds";
+const K_CG_WINDOW_ALPHA: &str = "kCGWindowAlpha";
+
+/// Ventanas en pantalla ordenadas de delante hacia atras.
+///
+/// Cuentan todas las capas: popovers, menus y paneles flotantes viven por
+/// encima de la de ventanas normales y tapan igual. Se omiten las ventanas
+/// de esta aplicacion (el overlay no debe tapar a nadie) y las totalmente
+/// transparentes.
+pub fn get_window_stack() -> WindowStack {
+    let Some(info) = copy_window_info(
+        kCGWindowListOptionOnScreenOnly | kCGWindowListExcludeDesktopElements,
+        kCGNullWindowID,
+    ) else {
+        tracing::warn!("CGWindowListCopyWindowInfo returned no windows");
+        return WindowStack::default();
+    };
+
+    let own_pid = std::process::id() as i32;
+
+    let windows = info
+        .iter()
+        .filter_map(|item| {
+            // SAFETY: cada entrada de la lista es un CFDictionary que vive
+            // mientras vive el array
+            let dict: CFDictionary<CFString, CFType> =
+                unsafe { CFDictionary::wrap_under_get_rule(*item as CFDictionaryRef) };
+
+            let pid = get_number(&dict, K_CG_WINDOW_OWNER_PID).map(|pid| pid as i32);
+            if pid == Some(own_pid) || get_number(&dict, K_CG_WINDOW_ALPHA) == Some(0.0) {
+                return None;
+            }
+
+            Some(StackedWindow {
+                id: get_number(&dict, K_CG_WINDOW_NUMBER)? as u32,
+                pid,
+                frame: get_bounds(&dict)?,
+            })
+        })
+        .collect();
+
+    WindowStack::new(windows)
+}
+
+fn get_number(dict: &CFDictionary<CFString, CFType>, key: &str) -> Option<f64> {
+    dict.find(CFString::new(key))
+        .and_then(|value| value.downcast::<CFNumber>())
+        .and_then(|number| number.to_f64())
+}
+
+fn get_bounds(dict: &CFDictionary<CFString, CFType>) -> Option<LogicalRect> {
+    let value = dict.find(CFString::new(K_CG_WINDOW_BOUNDS))?;
+    if !value.instance_of::<CFDictionary>() {
+        return None;
+    }
+
+    // SAFETY: se acaba de comprobar que el valor es un CFDictionary
+    let bounds: CFDictionary =
+        unsafe { CFDictionary::wrap_under_get_rule(value.as_CFTypeRef() as CFDictionaryRef) };
+    let rect = CGRect::from_dict_representation(&bounds)?;
+
+    Some(LogicalRect::new(
+        rect.origin.x,
+        rect.origin.y,
+        rect.size.width,
+        rect.size.height,
+    ))
+}
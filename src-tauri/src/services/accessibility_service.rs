@@ -1,27 +1,54 @@
 use crate::error::AppError;
-use crate::models::UIElement;
+use crate::models::{ClickAnchor, ElementTree, LogicalRect, Role, ScanOptions, UIElement};
+#[cfg(target_os = "macos")]
 use crate::platform::macos::accessibility::{
-    self, get_active_window, get_element_rect, get_element_role, get_element_title,
-    is_clickable_role, traverse_accessibility_tree, AXUIElementRef,
+    self, describe_attribute_value, get_active_window, get_attribute_names, get_element_actions,
+    get_element_description, get_element_identifier, get_element_pid, get_element_rect, get_element_role,
+    get_element_title, get_element_value, get_window_id, is_element_enabled, is_element_focused,
+    is_element_selected, map_role, traverse_accessibility_tree_nodes, AXUIElementRef,
 };
+#[cfg(target_os = "macos")]
+use crate::platform::macos::display::get_screen_frames;
+#[cfg(target_os = "macos")]
+use crate::platform::macos::window_list::get_window_stack;
+use crate::services::inspector_service::NodeAttributes;
+use crate::services::click_point_service::ClickPointResolver;
+use crate::services::clickable_stages::ClickableStages;
+use crate::services::occlusion_service::OcclusionFilter;
 use crate::Result;
+#[cfg(target_os = "macos")]
 use core_foundation::base::CFRelease;
+use std::collections::HashMap;
+#[cfg(target_os = "macos")]
 use std::ffi::c_void;
+use std::sync::{PoisonError, RwLock};
 
+#[cfg(target_os = "macos")]
 const DEFAULT_MAX_DEPTH: usize = 10;
 const DEFAULT_MAX_ELEMENTS: usize = 500;
+// Nodos visitados (clickables o no) por cada elemento de `max_elements`: en
+// los árboles AX la mayoría de nodos son grupos y textos, y con un límite
+// igual a `max_elements` el recorrido se cortaba antes de llegar a ellos
+#[cfg(target_os = "macos")]
+const NODES_PER_ELEMENT: usize = 10;
 
 pub struct AccessibilityService {
+    // Profundidad del recorrido AX
+    #[cfg(target_os = "macos")]
     max_depth: usize,
     max_elements: usize,
+    // Se reconstruye al cambiar `AppConfig.click_anchors`
+    click_points: RwLock<ClickPointResolver>,
 }
 
 impl AccessibilityService {
     pub fn new() -> Self {
         tracing::debug!("Initializing AccessibilityService");
         Self {
+            #[cfg(target_os = "macos")]
             max_depth: DEFAULT_MAX_DEPTH,
             max_elements: DEFAULT_MAX_ELEMENTS,
+            click_points: RwLock::new(ClickPointResolver::new()),
         }
     }
 
@@ -33,11 +60,39 @@ impl AccessibilityService {
             max_elements
         );
         Self {
+            #[cfg(target_os = "macos")]
             max_depth,
             max_elements,
+            click_points: RwLock::new(ClickPointResolver::new()),
         }
     }
 
+    /// Anclajes de click por rol para los siguientes escaneos
+    pub fn set_click_anchors(&self, anchors: HashMap<Role, ClickAnchor>) {
+        tracing::debug!("Updating click anchors ({} roles)", anchors.len());
+        *self
+            .click_points
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = ClickPointResolver::with_anchors(anchors);
+    }
+
+    /// Etapas que convierten el arbol escaneado en elementos con hint, con
+    /// la oclusion actual, los anclajes configurados y `max_elements`
+    pub fn clickable_stages(&self, screens: Vec<LogicalRect>) -> ClickableStages {
+        let click_points = self
+            .click_points
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        ClickableStages::new(screens)
+            .with_occlusion(self.occlusion_filter())
+            .with_click_points(click_points)
+            .with_max_elements(self.max_elements)
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl AccessibilityService {
     pub fn check_permissions(&self) -> bool {
         tracing::trace!("Checking permissions");
         let has_perms = accessibility::has_accessibility_permissions();
@@ -57,6 +112,11 @@ impl AccessibilityService {
         accessibility::get_focused_application()
     }
 
+    /// Nombre de la app enfocada, para separar el uso aprendido por app
+    pub fn get_focused_application_name(&self) -> Option<String> {
+        accessibility::get_focused_application_name()
+    }
+
     pub fn ensure_permissions(&self) -> Result<()> {
         if self.check_permissions() {
             Ok(())
@@ -69,23 +129,68 @@ impl AccessibilityService {
     }
 
     fn map_ax_element(&self, element: AXUIElementRef, index: usize) -> Result<UIElement> {
-        let role = get_element_role(element)?;
+        let raw_role = get_element_role(element)?;
         let title = get_element_title(element);
         let rect = get_element_rect(element)?;
 
-        let id = format!("ax-{}-{}", index, role.to_lowercase().replace("ax", ""));
+        let id = format!("ax-{}-{}", index, raw_role.to_lowercase().replace("ax", ""));
+
+        tracing::trace!("Mapped: {} role={}", id, raw_role);
 
-        tracing::trace!("Mapped: {} role={}", id, role);
+        Ok(UIElement::new(id, map_role(&raw_role), title, rect).with_raw_role(raw_role))
+    }
+
+    /// Mapea un nodo del árbol. Los nodos sin rol o frame se conservan como
+    /// marcadores vacíos para no romper los enlaces padre-hijo.
+    fn map_tree_node(&self, element: AXUIElementRef, index: usize) -> UIElement {
+        match self.map_ax_element(element, index) {
+            Ok(ui_element) => ui_element,
+            Err(err) => {
+                tracing::trace!("Node {} without role or frame: {}", index, err);
+                UIElement::new(
+                    format!("ax-{}-unknown", index),
+                    Role::Other(String::new()),
+                    None,
+                    LogicalRect::default(),
+                )
+            }
+        }
+    }
+
+    /// Lee solo los atributos opcionales pedidos en `options`. Cada uno es
+    /// una llamada AX más, así que solo se piden para nodos clickables
+    fn fill_optional_attributes(
+        &self,
+        ui_element: &mut UIElement,
+        element: AXUIElementRef,
+        options: &ScanOptions,
+    ) {
+        if options.value {
+            ui_element.value = get_element_value(element);
+        }
+        if options.description {
+            ui_element.description = get_element_description(element);
+        }
+        if options.enabled {
+            ui_element.enabled = is_element_enabled(element);
+        }
+        if options.focused {
+            ui_element.focused = is_element_focused(element);
+        }
+        if options.selected {
+            ui_element.selected = is_element_selected(element);
+        }
+        if options.actions {
+            match get_element_actions(element) {
+                Ok(actions) => ui_element.actions = Some(actions),
+                Err(err) => tracing::trace!("No actions for {}: {}", ui_element.id, err),
+            }
+        }
+    }
 
-        Ok(UIElement::new(
-            id,
-            role,
-            title,
-            rect.x,
-            rect.y,
-            rect.width,
-            rect.height,
-        ))
+    /// Límite de nodos del recorrido, proporcional a `max_elements`
+    fn max_nodes(&self) -> usize {
+        self.max_elements.saturating_mul(NODES_PER_ELEMENT)
     }
 
     /// Libera un AXUIElementRef de forma segura
@@ -97,47 +202,194 @@ impl AccessibilityService {
     }
 
     pub async fn scan_clickable_elements(&self) -> Result<Vec<UIElement>> {
+        self.scan_clickable_elements_with(&ScanOptions::default())
+            .await
+    }
+
+    /// Escanea elementos clickables leyendo los atributos opcionales pedidos
+    pub async fn scan_clickable_elements_with(
+        &self,
+        options: &ScanOptions,
+    ) -> Result<Vec<UIElement>> {
         tracing::info!("Scanning clickable elements");
 
+        let tree = self.scan_tree(options).await?;
+        let ui_elements = self.clickable_stages(get_screen_frames()).clickable(&tree);
+
+        tracing::info!("Found {} clickable elements", ui_elements.len());
+
+        Ok(ui_elements)
+    }
+
+    /// Filtro de oclusión para la ventana activa según el apilado actual
+    /// (None si no se puede identificar la ventana)
+    pub fn occlusion_filter(&self) -> Option<OcclusionFilter> {
+        let active_window = get_active_window().ok()?;
+        let window_id = get_window_id(active_window);
+        self.release_element(active_window);
+
+        match window_id {
+            Ok(window_id) => OcclusionFilter::for_window(&get_window_stack(), window_id),
+            Err(err) => {
+                tracing::debug!("Skipping occlusion filter: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Escanea la ventana activa y devuelve el árbol completo de elementos
+    pub async fn scan_tree(&self, options: &ScanOptions) -> Result<ElementTree> {
+        self.scan_tree_with(options, |_, _| {})
+    }
+
+    /// Escanea el árbol leyendo todos los atributos nativos de cada nodo
+    pub async fn inspect_tree(&self) -> Result<(ElementTree, Vec<NodeAttributes>)> {
+        let mut attributes = Vec::new();
+
+        let tree = self.scan_tree_with(&ScanOptions::all(), |_, element| {
+            let names = get_attribute_names(element).unwrap_or_default();
+            attributes.push(
+                names
+                    .into_iter()
+                    .map(|name| {
+                        let value = describe_attribute_value(element, &name);
+                        (name, value)
+                    })
+                    .collect(),
+            );
+        })?;
+
+        Ok((tree, attributes))
+    }
+
+    /// Recorre la ventana activa construyendo el árbol. `visit` recibe cada
+    /// nodo antes de liberar su AXUIElementRef.
+    fn scan_tree_with<F>(&self, options: &ScanOptions, mut visit: F) -> Result<ElementTree>
+    where
+        F: FnMut(usize, AXUIElementRef),
+    {
+        tracing::info!("Scanning accessibility tree ({:?})", options);
+
         self.ensure_permissions()?;
 
         let active_window = get_active_window()?;
 
-        let ax_elements =
-            traverse_accessibility_tree(active_window, self.max_depth, self.max_elements);
-
-        tracing::debug!("Found {} AX elements", ax_elements.len());
-
-        let mut ui_elements = Vec::with_capacity(ax_elements.len());
-
-        for (index, ax_element) in ax_elements.iter().enumerate() {
-            match self.map_ax_element(*ax_element, index) {
-                Ok(ui_element) => {
-                    if is_clickable_role(&ui_element.role)
-                        && ui_element.width > 1.0
-                        && ui_element.height > 1.0
-                    {
-                        ui_elements.push(ui_element);
-                    }
-                }
-                Err(err) => {
-                    tracing::debug!("Skipping element {}: {}", index, err);
-                }
+        // PID y ventana son comunes a todo el escaneo: se leen una sola vez
+        let pid = if options.pid {
+            get_element_pid(active_window)
+                .map_err(|err| tracing::debug!("Could not read window PID: {}", err))
+                .ok()
+        } else {
+            None
+        };
+        let window_id = if options.window {
+            get_window_id(active_window)
+                .map_err(|err| tracing::debug!("Could not read window id: {}", err))
+                .ok()
+        } else {
+            None
+        };
+
+        let nodes =
+            traverse_accessibility_tree_nodes(active_window, self.max_depth, self.max_nodes());
+
+        tracing::debug!("Found {} AX nodes", nodes.len());
+
+        let mut tree = ElementTree::new();
+
+        for (index, node) in nodes.iter().enumerate() {
+            let mut ui_element = self.map_tree_node(node.element, index);
+            if ui_element.role.is_clickable() {
+                // Parte de la clave estable con la que se aprende el uso
+                ui_element.identifier = get_element_identifier(node.element);
+                self.fill_optional_attributes(&mut ui_element, node.element, options);
             }
+            ui_element.pid = pid;
+            ui_element.window_id = window_id;
+            tree.add_node(ui_element, node.parent);
+            visit(index, node.element);
 
             // Liberar el AXUIElementRef después de procesarlo
-            self.release_element(*ax_element);
+            self.release_element(node.element);
         }
 
         // Liberar la ventana activa
         self.release_element(active_window);
 
-        tracing::info!("Found {} clickable elements", ui_elements.len());
+        Ok(tree)
+    }
+}
 
-        Ok(ui_elements)
+/// Stub para plataformas no soportadas: sin API de accesibilidad no hay
+/// elementos que escanear
+#[cfg(not(target_os = "macos"))]
+impl AccessibilityService {
+    pub fn check_permissions(&self) -> bool {
+        false
+    }
+
+    pub fn request_permissions(&self) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn get_focused_application_pid(&self) -> Result<Option<i32>> {
+        Err(unsupported())
+    }
+
+    pub fn get_focused_application_name(&self) -> Option<String> {
+        None
+    }
+
+    pub fn ensure_permissions(&self) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub async fn scan_clickable_elements(&self) -> Result<Vec<UIElement>> {
+        Err(unsupported())
+    }
+
+    pub async fn scan_clickable_elements_with(
+        &self,
+        _options: &ScanOptions,
+    ) -> Result<Vec<UIElement>> {
+        Err(unsupported())
+    }
+
+    /// Filtro de oclusión para la ventana activa de X11 según el apilado
+    /// de EWMH (None si no hay gestor de ventanas que lo publique)
+    #[cfg(target_os = "linux")]
+    pub fn occlusion_filter(&self) -> Option<OcclusionFilter> {
+        use crate::platform::linux::window_list::{get_active_window, get_window_stack};
+
+        let read = get_active_window().and_then(|window| Ok((window, get_window_stack()?)));
+        match read {
+            Ok((window_id, stack)) => OcclusionFilter::for_window(&stack, window_id),
+            Err(err) => {
+                tracing::debug!("Skipping occlusion filter: {}", err);
+                None
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn occlusion_filter(&self) -> Option<OcclusionFilter> {
+        None
+    }
+
+    pub async fn scan_tree(&self, _options: &ScanOptions) -> Result<ElementTree> {
+        Err(unsupported())
+    }
+
+    pub async fn inspect_tree(&self) -> Result<(ElementTree, Vec<NodeAttributes>)> {
+        Err(unsupported())
     }
 }
 
+#[cfg(not(target_os = "macos"))]
+fn unsupported() -> AppError {
+    AppError::Accessibility("Accessibility scanning not supported on this OS".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +397,7 @@ mod tests {
     #[test]
     fn test_service_creation() {
         let service = AccessibilityService::new();
+        #[cfg(target_os = "macos")]
         assert_eq!(service.max_depth, DEFAULT_MAX_DEPTH);
         assert_eq!(service.max_elements, DEFAULT_MAX_ELEMENTS);
     }
@@ -152,7 +405,26 @@ mod tests {
     #[test]
     fn test_service_with_config() {
         let service = AccessibilityService::with_config(5, 100);
+        #[cfg(target_os = "macos")]
         assert_eq!(service.max_depth, 5);
         assert_eq!(service.max_elements, 100);
     }
+
+    #[test]
+    fn test_set_click_anchors_rebuilds_resolver() {
+        let service = AccessibilityService::new();
+        let button = UIElement::new(
+            "btn".to_string(),
+            Role::Button,
+            None,
+            LogicalRect::new(0.0, 0.0, 100.0, 20.0),
+        );
+        let click_point = |service: &AccessibilityService| {
+            service.click_points.read().unwrap().resolve(&button, &[])
+        };
+        assert_eq!(click_point(&service).x, 50.0);
+
+        service.set_click_anchors(HashMap::from([(Role::Button, ClickAnchor::Left)]));
+        assert_eq!(click_point(&service).x, 8.0);
+    }
 }
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use crate::models::{ClickAnchor, ElementTree, LogicalPoint, LogicalRect, Role, UIElement};
+
+/// Elige donde hacer click dentro de cada elemento.
+///
+/// El centro geometrico puede caer sobre un hijo accionable (el boton de
+/// borrar de un campo de texto, un link dentro de una fila). El resolvedor
+/// busca un punto dentro de la parte visible del elemento que no caiga en
+/// ninguno de esos hijos, empezando por el anclaje configurado para su rol.
+#[derive(Clone)]
+pub struct ClickPointResolver {
+    anchors: HashMap<Role, ClickAnchor>,
+}
+
+impl ClickPointResolver {
+    pub fn new() -> Self {
+        Self::with_anchors(ClickAnchor::defaults())
+    }
+
+    pub fn with_anchors(anchors: HashMap<Role, ClickAnchor>) -> Self {
+        Self { anchors }
+    }
+
+    /// Calcula `click_point` para cada elemento evitando sus descendientes
+    /// accionables en el arbol y las ventanas que tapan la suya (`occluders`)
+    pub fn resolve_all(
+        &self,
+        tree: &ElementTree,
+        elements: &mut [UIElement],
+        occluders: &[LogicalRect],
+    ) {
+        let index_by_id: HashMap<&str, usize> = tree
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.element.id.as_str(), index))
+            .collect();
+
+        for element in elements {
+            let mut obstacles = index_by_id
+                .get(element.id.as_str())
+                .map(|&index| actionable_descendants(tree, index))
+                .unwrap_or_default();
+            obstacles.extend_from_slice(occluders);
+            element.click_point = Some(self.resolve(element, &obstacles));
+        }
+    }
+
+    /// Punto de click de un elemento que evita los rectangulos `obstacles`
+    pub fn resolve(&self, element: &UIElement, obstacles: &[LogicalRect]) -> LogicalPoint {
+        let visible = element.visible_frame();
+        let obstacles: Vec<LogicalRect> = obstacles
+            .iter()
+            .filter_map(|rect| rect.intersection(&visible))
+            .collect();
+        let is_free =
+            |point: &LogicalPoint| !obstacles.iter().any(|rect| rect.contains_point(*point));
+
+        let anchor = self
+            .anchors
+            .get(&element.role)
+            .copied()
+            .unwrap_or(ClickAnchor::Center);
+        let preferred = anchor.point_in(&visible);
+
+        if let Some(point) = [preferred, visible.center()].into_iter().find(is_free) {
+            return point;
+        }
+
+        // Hijos o ventanas tapan ambos puntos: centro del mayor trozo libre
+        obstacles
+            .iter()
+            .fold(vec![visible], |free, obstacle| {
+                free.iter()
+                    .flat_map(|piece| piece.subtract(obstacle))
+                    .collect()
+            })
+            .into_iter()
+            .max_by(|a, b| a.area().total_cmp(&b.area()))
+            .map_or(preferred, |piece| piece.center())
+    }
+}
+
+impl Default for ClickPointResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Frames de los descendientes clickables mas cercanos de un nodo (no se
+/// baja dentro de un hijo clickable: sus propios hijos ya estan dentro)
+fn actionable_descendants(tree: &ElementTree, index: usize) -> Vec<LogicalRect> {
+    let mut frames = Vec::new();
+    let mut pending: Vec<usize> = tree
+        .node(index)
+        .map(|node| node.children.clone())
+        .unwrap_or_default();
+
+    while let Some(child) = pending.pop() {
+        let Some(node) = tree.node(child) else {
+            continue;
+        };
+        if node.element.role.is_clickable() {
+            frames.push(node.element.frame);
+        } else {
+            pending.extend(&node.children);
+        }
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::config::AppConfig;
+
+    fn make_element(id: &str, role: Role, x: f64, y: f64, w: f64, h: f64) -> UIElement {
+        UIElement::new(id.to_string(), role, None, LogicalRect::new(x, y, w, h))
+    }
+
+    #[test]
+    fn test_text_field_uses_left_anchor() {
+        let field = make_element("field", Role::TextField, 0.0, 0.0, 200.0, 24.0);
+        let point = ClickPointResolver::new().resolve(&field, &[]);
+        assert_eq!(point, LogicalPoint::new(8.0, 12.0));
+    }
+
+    #[test]
+    fn test_configured_anchor_changes_click_point() {
+        let button = make_element("btn", Role::Button, 0.0, 0.0, 100.0, 20.0);
+        let mut config = AppConfig::default();
+        let default_point = ClickPointResolver::with_anchors(config.click_anchors.clone())
+            .resolve(&button, &[]);
+
+        config.click_anchors.insert(Role::Button, ClickAnchor::Right);
+        let configured_point =
+            ClickPointResolver::with_anchors(config.click_anchors).resolve(&button, &[]);
+
+        assert_eq!(default_point, LogicalPoint::new(50.0, 10.0));
+        assert_eq!(configured_point, LogicalPoint::new(92.0, 10.0));
+    }
+
+    #[test]
+    fn test_avoids_child_on_center() {
+        // Fila con un link sobre el centro; queda mas espacio a la izquierda
+        let row = make_element("row", Role::Row, 0.0, 0.0, 300.0, 20.0);
+        let link = LogicalRect::new(120.0, 0.0, 80.0, 20.0);
+
+        let point = ClickPointResolver::new().resolve(&row, &[link]);
+
+        assert!(!link.contains_point(point));
+        assert!(row.frame.contains_point(point));
+        assert_eq!(point, LogicalPoint::new(60.0, 10.0));
+    }
+
+    #[test]
+    fn test_falls_back_to_anchor_when_fully_covered() {
+        let button = make_element("btn", Role::Button, 0.0, 0.0, 20.0, 20.0);
+        let cover = LogicalRect::new(-5.0, -5.0, 30.0, 30.0);
+        let point = ClickPointResolver::new().resolve(&button, &[cover]);
+        assert_eq!(point, LogicalPoint::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn test_resolve_all_uses_nested_children() {
+        // tabgroup -> group -> tab; la pestaña cubre el centro del grupo
+        let mut tree = ElementTree::new();
+        let tab_group = tree.add_node(
+            make_element("tabs", Role::TabGroup, 0.0, 0.0, 300.0, 20.0),
+            None,
+        );
+        let group = tree.add_node(
+            make_element("group", Role::Group, 0.0, 0.0, 300.0, 20.0),
+            Some(tab_group),
+        );
+        tree.add_node(
+            make_element("tab", Role::Tab, 120.0, 0.0, 80.0, 20.0),
+            Some(group),
+        );
+
+        let mut elements = tree.clickable_elements();
+        let resolver =
+            ClickPointResolver::with_anchors(HashMap::from([(Role::Tab, ClickAnchor::Left)]));
+        resolver.resolve_all(&tree, &mut elements, &[]);
+
+        assert_eq!(elements[0].id, "tabs");
+        assert_eq!(elements[0].click_point, Some(LogicalPoint::new(60.0, 10.0)));
+        // La pestaña no tiene hijos: su anclaje configurado
+        assert_eq!(
+            elements[1].click_point,
+            Some(LogicalPoint::new(128.0, 10.0))
+        );
+    }
+}
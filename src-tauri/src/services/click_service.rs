@@ -1,3 +1,7 @@
+use crate::models::LogicalPoint;
+#[cfg(target_os = "linux")]
+use crate::platform::linux::pointer as events;
+#[cfg(target_os = "macos")]
 use crate::platform::macos::events;
 use crate::Result;
 
@@ -9,11 +13,21 @@ impl ClickService {
         Self {}
     }
 
-    // Simula un click en las coordenadas especificas
-    pub fn  perform_click(&self, x: f64, y: f64) -> Result<()> {
-        // 1. Mover el cursor a (x, y)
+    // Simula un click en un punto global logico
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    pub fn  perform_click(&self, point: LogicalPoint) -> Result<()> {
+        // 1. Mover el cursor al punto
         // 2. Simular evento de click
-        events::post_mouse_click(x, y)
+        events::post_mouse_click(point)
+    }
+
+    /// Stub para plataformas no soportadas
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    pub fn perform_click(&self, point: LogicalPoint) -> Result<()> {
+        Err(crate::AppError::Internal(format!(
+            "Synthetic click at ({}, {}) not supported on this OS",
+            point.x, point.y
+        )))
     }
 }
 
@@ -0,0 +1,214 @@
+use crate::models::{ElementTree, FilterDecision, FilterExplanation, LogicalRect, UIElement};
+use crate::services::click_point_service::ClickPointResolver;
+use crate::services::occlusion_service::OcclusionFilter;
+use crate::services::visibility_service::VisibilityFilter;
+
+/// Lado minimo (exclusivo) del frame de un elemento para recibir hint
+const MIN_FRAME_SIZE: f64 = 1.0;
+
+/// Etapas que deciden que nodos clickables del arbol escaneado se
+/// conservan: visibilidad, tamaño minimo, oclusion y limite de elementos.
+/// A los conservados se les calcula el punto de click.
+///
+/// Las usan el escaneo y el inspector, asi que un volcado explica
+/// exactamente lo que hizo el escaneo.
+pub struct ClickableStages {
+    visibility: VisibilityFilter,
+    occlusion: Option<OcclusionFilter>,
+    click_points: ClickPointResolver,
+    max_elements: usize,
+}
+
+impl ClickableStages {
+    /// Etapas que recortan a las pantallas dadas (vacio = sin recorte), sin
+    /// oclusion, con los anclajes por defecto y sin limite de elementos
+    pub fn new(screens: Vec<LogicalRect>) -> Self {
+        Self {
+            visibility: VisibilityFilter::new(screens),
+            occlusion: None,
+            click_points: ClickPointResolver::new(),
+            max_elements: usize::MAX,
+        }
+    }
+
+    /// Añade la etapa de oclusion por otras ventanas
+    pub fn with_occlusion(mut self, occlusion: Option<OcclusionFilter>) -> Self {
+        self.occlusion = occlusion;
+        self
+    }
+
+    pub fn with_click_points(mut self, click_points: ClickPointResolver) -> Self {
+        self.click_points = click_points;
+        self
+    }
+
+    pub fn with_max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = max_elements;
+        self
+    }
+
+    /// Elementos conservados, en orden BFS y con `click_point`
+    pub fn clickable(&self, tree: &ElementTree) -> Vec<UIElement> {
+        let (kept, dropped) = self.explain(tree);
+        for explanation in dropped {
+            if let FilterDecision::Dropped { rule, reason } = explanation.decision {
+                tracing::trace!("Dropped {} ({}): {}", explanation.element.id, rule, reason);
+            }
+        }
+        kept
+    }
+
+    /// Conservados (en orden BFS y con `click_point`) y descartados con la
+    /// etapa que los descarto
+    pub fn explain(&self, tree: &ElementTree) -> (Vec<UIElement>, Vec<FilterExplanation>) {
+        let (visible, mut dropped) = split_kept(self.visibility.explain(tree));
+
+        let (mut kept, small): (Vec<_>, Vec<_>) = visible
+            .into_iter()
+            .partition(|el| el.frame.width > MIN_FRAME_SIZE && el.frame.height > MIN_FRAME_SIZE);
+        dropped.extend(small.into_iter().map(|element| {
+            let reason = format!(
+                "frame {}x{} <= {}px",
+                element.frame.width, element.frame.height, MIN_FRAME_SIZE
+            );
+            FilterExplanation {
+                element,
+                decision: FilterDecision::dropped("size", reason),
+            }
+        }));
+
+        if let Some(occlusion) = &self.occlusion {
+            let (uncovered, covered) = split_kept(occlusion.explain(kept));
+            kept = uncovered;
+            dropped.extend(covered);
+        }
+
+        if kept.len() > self.max_elements {
+            let reason = format!("beyond the first {} elements", self.max_elements);
+            dropped.extend(
+                kept.split_off(self.max_elements)
+                    .into_iter()
+                    .map(|element| FilterExplanation {
+                        element,
+                        decision: FilterDecision::dropped("limit", reason.clone()),
+                    }),
+            );
+        }
+
+        // El anclaje puede caer bajo otra ventana aunque el centro no
+        let occluders = self
+            .occlusion
+            .as_ref()
+            .map(OcclusionFilter::occluder_frames)
+            .unwrap_or_default();
+        self.click_points.resolve_all(tree, &mut kept, &occluders);
+        (kept, dropped)
+    }
+}
+
+/// Separa los elementos conservados por una etapa de los descartados
+pub fn split_kept(
+    explanations: Vec<FilterExplanation>,
+) -> (Vec<UIElement>, Vec<FilterExplanation>) {
+    let (kept, dropped): (Vec<_>, Vec<_>) = explanations
+        .into_iter()
+        .partition(|explanation| explanation.decision == FilterDecision::Kept);
+    (kept.into_iter().map(|e| e.element).collect(), dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LogicalPoint, Role, StackedWindow, WindowStack};
+
+    fn make_element(id: &str, role: Role, x: f64, y: f64, w: f64, h: f64) -> UIElement {
+        UIElement::new(id.to_string(), role, None, LogicalRect::new(x, y, w, h))
+    }
+
+    // window (0,0 800x600)
+    // ├── ok     (10,10 100x20)
+    // ├── line   (10,40 100x1)
+    // ├── offwin (900,10 100x20)
+    // └── field  (10,70 200x24)
+    fn sample_tree() -> ElementTree {
+        let mut tree = ElementTree::new();
+        let window = tree.add_node(
+            make_element("window", Role::Window, 0.0, 0.0, 800.0, 600.0),
+            None,
+        );
+        for element in [
+            make_element("ok", Role::Button, 10.0, 10.0, 100.0, 20.0),
+            make_element("line", Role::Button, 10.0, 40.0, 100.0, 1.0),
+            make_element("offwin", Role::Button, 900.0, 10.0, 100.0, 20.0),
+            make_element("field", Role::TextField, 10.0, 70.0, 200.0, 24.0),
+        ] {
+            tree.add_node(element, Some(window));
+        }
+        tree
+    }
+
+    fn rule_of(explanation: &FilterExplanation) -> &str {
+        match &explanation.decision {
+            FilterDecision::Dropped { rule, .. } => rule,
+            FilterDecision::Kept => "kept",
+        }
+    }
+
+    #[test]
+    fn test_runs_every_stage_in_order() {
+        let (kept, dropped) = ClickableStages::new(Vec::new())
+            .with_max_elements(1)
+            .explain(&sample_tree());
+
+        let ids: Vec<&str> = kept.iter().map(|el| el.id.as_str()).collect();
+        assert_eq!(ids, vec!["ok"]);
+        let rules: Vec<(&str, &str)> = dropped
+            .iter()
+            .map(|e| (e.element.id.as_str(), rule_of(e)))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("offwin", "visibility"),
+                ("line", "size"),
+                ("field", "limit")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kept_elements_get_click_points() {
+        let kept = ClickableStages::new(Vec::new()).clickable(&sample_tree());
+
+        assert_eq!(kept[0].click_point, Some(LogicalPoint::new(60.0, 20.0)));
+        // Los campos de texto usan el anclaje izquierdo por defecto
+        assert_eq!(kept[1].click_point, Some(LogicalPoint::new(18.0, 82.0)));
+    }
+
+    #[test]
+    fn test_click_point_avoids_occluding_windows() {
+        // Popover (2) sobre la izquierda del campo: tapa su anclaje pero no
+        // su centro (110, 82)
+        let popover = LogicalRect::new(0.0, 60.0, 50.0, 100.0);
+        let stack = WindowStack::new(vec![
+            StackedWindow {
+                id: 2,
+                pid: None,
+                frame: popover,
+            },
+            StackedWindow {
+                id: 1,
+                pid: None,
+                frame: LogicalRect::new(0.0, 0.0, 800.0, 600.0),
+            },
+        ]);
+        let kept = ClickableStages::new(Vec::new())
+            .with_occlusion(OcclusionFilter::for_window(&stack, 1))
+            .clickable(&sample_tree());
+
+        let field = kept.iter().find(|el| el.id == "field").unwrap();
+        let point = field.click_point.unwrap();
+        assert!(!popover.contains_point(point));
+        assert_eq!(point, LogicalPoint::new(110.0, 82.0));
+    }
+}
@@ -1,11 +1,23 @@
-use crate::models::UIElement;
-use std::collections::HashSet;
+use crate::models::{FilterDecision, FilterExplanation, Role, UIElement};
 
 /// Tamaño minimo para considerar un elemento visible
 const MIN_SIZE: f64 = 5.0;
 
-// Precision para redondear posiciones (deduplicacion)
-const POSITION_PRECISION: i32 = 5;
+// IoU a partir del cual dos elementos se consideran el mismo objetivo
+const DUPLICATE_IOU: f64 = 0.8;
+
+// Fraccion del elemento interior que debe quedar dentro del exterior
+const CONTAINMENT_RATIO: f64 = 0.95;
+
+// Area minima del interior respecto al exterior para tratarlos como el mismo
+// objetivo (un link pequeño dentro de una fila grande no lo es)
+const SAME_TARGET_COVERAGE: f64 = 0.5;
+
+// Elemento junto a su posicion en la lista de entrada
+type Indexed = (usize, UIElement);
+
+// Elemento descartado junto a su posicion de entrada y la decision
+type Rejected = (usize, UIElement, FilterDecision);
 
 // Filtra elementos UI aplicando reglas de visibilidad y deduplicacion
 pub struct ElementFilter;
@@ -22,42 +34,161 @@ impl ElementFilter {
             elements.len()
         );
 
-        let filtered = self.filter_by_size(elements);
-        let filtered = self.deduplicate_by_position(filtered);
+        let (filtered, rejected) = self.run(elements);
+
+        for (_, element, decision) in &rejected {
+            if let FilterDecision::Dropped { reason, .. } = decision {
+                tracing::trace!("Filtered out {}: {}", element.id, reason);
+            }
+        }
 
         tracing::debug!(
             "Finished element filtering. Final count: {}",
             filtered.len()
         );
-        filtered
+        filtered.into_iter().map(|(_, el)| el).collect()
     }
 
-    // Filtra elementos demasiado pequeños
-    fn filter_by_size(&self, elements: Vec<UIElement>) -> Vec<UIElement> {
-        elements
+    /// Modo explicacion: devuelve, para cada elemento de entrada y en el mismo
+    /// orden, que filtro lo conservo o descarto y por que
+    pub fn explain(&self, elements: Vec<UIElement>) -> Vec<FilterExplanation> {
+        let (kept, mut decided) = self.run(elements);
+
+        decided.extend(
+            kept.into_iter()
+                .map(|(index, el)| (index, el, FilterDecision::Kept)),
+        );
+        decided.sort_by_key(|(index, _, _)| *index);
+
+        decided
             .into_iter()
-            .filter(|el| el.width > MIN_SIZE && el.height > MIN_SIZE)
+            .map(|(_, element, decision)| {
+                match &decision {
+                    FilterDecision::Kept => tracing::debug!("Filter kept {}", element.id),
+                    FilterDecision::Dropped { rule, reason } => {
+                        tracing::debug!("Filter dropped {} ({}): {}", element.id, rule, reason)
+                    }
+                }
+                FilterExplanation { element, decision }
+            })
             .collect()
     }
 
-    // Elimina elementos duplicados en la misma posicion (redondeada)
-    fn deduplicate_by_position(&self, elements: Vec<UIElement>) -> Vec<UIElement> {
-        let mut seen: HashSet<(i32, i32)> = HashSet::new();
-        let mut result = Vec::with_capacity(elements.len());
+    /// Aplica todos los filtros y devuelve los conservados y los descartados
+    /// con su decision, ambos con su indice de entrada
+    fn run(&self, elements: Vec<UIElement>) -> (Vec<Indexed>, Vec<Rejected>) {
+        let mut rejected = Vec::new();
 
-        for el in elements {
-            let key = (
-                (el.x as i32) / POSITION_PRECISION * POSITION_PRECISION,
-                (el.y as i32) / POSITION_PRECISION * POSITION_PRECISION,
-            );
+        let indexed = elements.into_iter().enumerate().collect();
+        let filtered = self.filter_by_size(indexed, &mut rejected);
+        let filtered = self.deduplicate_by_overlap(filtered, &mut rejected);
 
-            if seen.insert(key) {
-                result.push(el);
+        (filtered, rejected)
+    }
+
+    // Filtra elementos demasiado pequeños
+    fn filter_by_size(&self, elements: Vec<Indexed>, rejected: &mut Vec<Rejected>) -> Vec<Indexed> {
+        let mut result = Vec::with_capacity(elements.len());
+
+        for (index, el) in elements {
+            // Un elemento recortado solo cuenta por su parte visible
+            let visible = el.visible_frame();
+            if visible.width > MIN_SIZE && visible.height > MIN_SIZE {
+                result.push((index, el));
+            } else {
+                let reason = format!(
+                    "too small {}x{} < MIN_SIZE ({})",
+                    visible.width, visible.height, MIN_SIZE
+                );
+                rejected.push((index, el, FilterDecision::dropped("size", reason)));
             }
         }
 
         result
     }
+
+    /// Elimina candidatos que apuntan al mismo objetivo: frames casi iguales
+    /// (IoU alto) o un elemento que ocupa la mayor parte de otro que lo
+    /// contiene. De cada grupo sobrevive el mas especifico segun la prioridad
+    /// de rol y, a igualdad, el de menor area.
+    fn deduplicate_by_overlap(
+        &self,
+        elements: Vec<Indexed>,
+        rejected: &mut Vec<Rejected>,
+    ) -> Vec<Indexed> {
+        let mut candidates = elements;
+
+        // Mejor candidato primero; el indice de entrada desempata
+        candidates.sort_by(|(ia, a), (ib, b)| {
+            role_priority(&b.role)
+                .cmp(&role_priority(&a.role))
+                .then(a.frame.area().total_cmp(&b.frame.area()))
+                .then(ia.cmp(ib))
+        });
+
+        let mut kept: Vec<Indexed> = Vec::with_capacity(candidates.len());
+
+        for (index, el) in candidates {
+            let reason = kept
+                .iter()
+                .find_map(|(_, winner)| same_target_reason(&el, winner));
+
+            match reason {
+                Some(reason) => {
+                    rejected.push((index, el, FilterDecision::dropped("duplicate", reason)))
+                }
+                None => kept.push((index, el)),
+            }
+        }
+
+        // Conservar el orden de entrada
+        kept.sort_by_key(|(index, _)| *index);
+        kept
+    }
+}
+
+/// Prioridad de rol al elegir entre candidatos solapados (mayor = mas especifico)
+fn role_priority(role: &Role) -> u8 {
+    match role {
+        Role::Button
+        | Role::Link
+        | Role::TextField
+        | Role::TextArea
+        | Role::CheckBox
+        | Role::RadioButton
+        | Role::ComboBox
+        | Role::PopUpButton
+        | Role::MenuButton
+        | Role::Slider
+        | Role::Incrementor
+        | Role::ColorWell
+        | Role::DisclosureTriangle => 3,
+        Role::MenuItem | Role::Tab => 2,
+        Role::Cell | Role::Row | Role::TabGroup => 1,
+        _ => 0,
+    }
+}
+
+/// Si `candidate` apunta al mismo objetivo que `winner` (que tiene prioridad),
+/// devuelve la razon por la que se descarta
+fn same_target_reason(candidate: &UIElement, winner: &UIElement) -> Option<String> {
+    let a = candidate.frame;
+    let b = winner.frame;
+
+    let iou = a.iou(&b);
+    if iou >= DUPLICATE_IOU {
+        return Some(format!("duplicate of {} (IoU {:.2})", winner.id, iou));
+    }
+
+    if b.fraction_inside(&a) >= CONTAINMENT_RATIO && b.area() >= a.area() * SAME_TARGET_COVERAGE {
+        return Some(format!("contains more specific {}", winner.id));
+    }
+
+    if a.fraction_inside(&b) >= CONTAINMENT_RATIO && a.area() >= b.area() * SAME_TARGET_COVERAGE {
+        return Some(format!("inside {} with higher role priority", winner.id));
+    }
+
+    None
 }
 
 impl Default for ElementFilter {
@@ -74,9 +205,15 @@ pub fn filter_elements(elements: Vec<UIElement>) -> Vec<UIElement> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{LogicalRect, Role};
 
     fn make_element(id: &str, x: f64, y: f64, w: f64, h: f64) -> UIElement {
-        UIElement::new(id.to_string(), "AXButton".to_string(), None, x, y, w, h)
+        UIElement::new(
+            id.to_string(),
+            Role::Button,
+            None,
+            LogicalRect::new(x, y, w, h),
+        )
     }
 
     #[test]
@@ -103,4 +240,94 @@ mod tests {
         let filtered = filter_elements(elements);
         assert_eq!(filtered.len(), 2);
     }
+
+    #[test]
+    fn test_explain_reports_every_element_in_order() {
+        let elements = vec![
+            make_element("a", 10.0, 10.0, 50.0, 20.0),
+            make_element("b", 0.0, 0.0, 4.0, 4.0),
+            make_element("c", 11.0, 11.0, 50.0, 20.0),
+        ];
+
+        let explanations = ElementFilter::new().explain(elements);
+        let ids: Vec<&str> = explanations.iter().map(|e| e.element.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+
+        assert_eq!(explanations[0].decision, FilterDecision::Kept);
+        assert_eq!(
+            explanations[1].decision,
+            FilterDecision::dropped("size", "too small 4x4 < MIN_SIZE (5)")
+        );
+        assert_eq!(
+            explanations[2].decision,
+            FilterDecision::dropped("duplicate", "duplicate of a (IoU 0.87)")
+        );
+    }
+
+    fn make_role_element(id: &str, role: Role, x: f64, y: f64, w: f64, h: f64) -> UIElement {
+        UIElement::new(id.to_string(), role, None, LogicalRect::new(x, y, w, h))
+    }
+
+    #[test]
+    fn test_small_buttons_in_same_cell_are_kept() {
+        // Con el antiguo bucketing de 5px ambos caian en la celda (10,10)
+        let elements = vec![
+            make_element("a", 10.0, 10.0, 6.0, 6.0),
+            make_element("b", 13.0, 11.0, 6.0, 6.0),
+        ];
+
+        let filtered = filter_elements(elements);
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn test_duplicates_across_cell_boundary() {
+        // Mismo elemento reportado dos veces a ambos lados de un borde de celda
+        let elements = vec![
+            make_element("a", 9.5, 9.5, 40.0, 20.0),
+            make_element("b", 10.5, 10.5, 40.0, 20.0),
+        ];
+
+        let filtered = filter_elements(elements);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "a");
+    }
+
+    #[test]
+    fn test_button_filling_row_replaces_row() {
+        let elements = vec![
+            make_role_element("row", Role::Row, 0.0, 100.0, 200.0, 24.0),
+            make_role_element("btn", Role::Button, 4.0, 102.0, 180.0, 20.0),
+        ];
+
+        let explanations = ElementFilter::new().explain(elements);
+        assert_eq!(
+            explanations[0].decision,
+            FilterDecision::dropped("duplicate", "contains more specific btn")
+        );
+        assert_eq!(explanations[1].decision, FilterDecision::Kept);
+    }
+
+    #[test]
+    fn test_small_link_inside_large_row_keeps_both() {
+        let elements = vec![
+            make_role_element("row", Role::Row, 0.0, 100.0, 400.0, 24.0),
+            make_role_element("link", Role::Link, 300.0, 104.0, 60.0, 16.0),
+        ];
+
+        let filtered = filter_elements(elements);
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn test_role_priority_wins_over_input_order() {
+        let elements = vec![
+            make_role_element("tab", Role::Tab, 0.0, 0.0, 80.0, 24.0),
+            make_role_element("btn", Role::Button, 0.0, 0.0, 80.0, 24.0),
+        ];
+
+        let filtered = filter_elements(elements);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "btn");
+    }
 }
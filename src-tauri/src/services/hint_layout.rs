@@ -0,0 +1,301 @@
+use crate::models::{
+    Hint, HintMode, HintStyle, HintTheme, LabelBox, LeaderLine, LogicalPoint, LogicalRect,
+};
+use crate::services::text_measure::{FontMeasure, TextMeasure};
+
+// Separacion entre la caja y su anclaje en las posiciones alternativas
+const ANCHOR_GAP: f64 = 2.0;
+
+// Escalas de fuente a probar antes de desplazar la caja
+const SHRINK_STEPS: [f64; 3] = [1.0, 0.85, 0.7];
+
+// Anillos de desplazamiento (en altos de caja) alrededor del anclaje
+const DISPLACEMENT_RINGS: usize = 3;
+
+// Distancia a partir de la cual la caja lleva linea guia
+const LEADER_DISTANCE: f64 = 6.0;
+
+/// Posicion de la caja respecto al anclaje del hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Center,
+    TopLeft,
+    Left,
+    Above,
+}
+
+// Orden de preferencia de las posiciones alternativas
+const PLACEMENTS: [Placement; 4] = [
+    Placement::Center,
+    Placement::TopLeft,
+    Placement::Left,
+    Placement::Above,
+];
+
+/// Motor de layout de labels independiente de la plataforma.
+///
+/// Coloca las cajas en el orden de los hints. Si una caja choca con las ya
+/// colocadas prueba posiciones alternativas, despues encoge la fuente y por
+/// ultimo la aleja del anclaje (con linea guia). Si nada encaja se queda con
+/// la opcion que menos solapa.
+pub struct HintLayout {
+    measure: Box<dyn TextMeasure>,
+}
+
+impl HintLayout {
+    pub fn new() -> Self {
+        Self::with_measure(Box::new(FontMeasure::new()))
+    }
+
+    pub fn with_measure(measure: Box<dyn TextMeasure>) -> Self {
+        Self { measure }
+    }
+
+    /// Calcula la caja de cada hint, en el mismo orden, con el estilo que le
+    /// toca segun el modo y su rol
+    pub fn layout(&self, hints: &[Hint], theme: &HintTheme, mode: HintMode) -> Vec<LabelBox> {
+        let mut placed: Vec<LogicalRect> = Vec::with_capacity(hints.len());
+
+        hints
+            .iter()
+            .map(|hint| {
+                let style = theme.resolve(mode, hint.role.as_ref());
+                let label_box = self.place(hint, style, &placed);
+                placed.push(label_box.rect);
+                label_box
+            })
+            .collect()
+    }
+
+    /// Tamaño de la caja de un label con su estilo encogido por `scale`
+    pub fn box_size(&self, label: &str, style: &HintStyle, scale: f64) -> (f64, f64) {
+        let font_size = style.font_size * scale;
+        let padding = style.padding * scale;
+        let metrics = self.measure.measure(label, font_size);
+
+        let width = (metrics.width + padding * 2.5).max(font_size * 2.0);
+        let height = metrics.line_height + padding * 2.5;
+        (width, height)
+    }
+
+    fn place(&self, hint: &Hint, style: HintStyle, placed: &[LogicalRect]) -> LabelBox {
+        let anchor = hint.anchor;
+        let mut best: Option<(f64, LogicalRect, f64)> = None;
+
+        'search: for ring in 0..DISPLACEMENT_RINGS {
+            for scale in SHRINK_STEPS {
+                let font_size = style.font_size * scale;
+                let (width, height) = self.box_size(&hint.label, &style, scale);
+                let gap = ANCHOR_GAP + ring as f64 * height;
+
+                for placement in PLACEMENTS {
+                    // El centro no admite desplazamiento
+                    if ring > 0 && placement == Placement::Center {
+                        continue;
+                    }
+
+                    let rect = candidate_rect(placement, anchor, width, height, gap);
+                    let overlap: f64 = placed
+                        .iter()
+                        .map(|other| rect.intersection_area(other))
+                        .sum();
+
+                    if best.is_none_or(|(best_overlap, _, _)| overlap < best_overlap) {
+                        best = Some((overlap, rect, font_size));
+                    }
+                    if overlap == 0.0 {
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        let (_, rect, font_size) = best.unwrap_or_else(|| {
+            let (width, height) = self.box_size(&hint.label, &style, 1.0);
+            let rect = candidate_rect(Placement::Center, anchor, width, height, 0.0);
+            (0.0, rect, style.font_size)
+        });
+
+        let edge = nearest_point(&rect, anchor);
+        let leader = (anchor.distance_to(edge) > LEADER_DISTANCE).then_some(LeaderLine {
+            from: anchor,
+            to: edge,
+        });
+
+        LabelBox {
+            label: hint.label.clone(),
+            element_id: hint.element_id.clone(),
+            rect,
+            font_size,
+            leader,
+            style,
+        }
+    }
+}
+
+impl Default for HintLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn candidate_rect(
+    placement: Placement,
+    anchor: LogicalPoint,
+    width: f64,
+    height: f64,
+    gap: f64,
+) -> LogicalRect {
+    let (x, y) = match placement {
+        Placement::Center => (anchor.x - width / 2.0, anchor.y - height / 2.0),
+        Placement::TopLeft => (anchor.x - width - gap, anchor.y - height - gap),
+        Placement::Left => (anchor.x - width - gap, anchor.y - height / 2.0),
+        Placement::Above => (anchor.x - width / 2.0, anchor.y - height - gap),
+    };
+    LogicalRect::new(x, y, width, height)
+}
+
+/// Punto de `rect` mas cercano a `point`
+fn nearest_point(rect: &LogicalRect, point: LogicalPoint) -> LogicalPoint {
+    LogicalPoint::new(
+        point.x.clamp(rect.x, rect.right()),
+        point.y.clamp(rect.y, rect.bottom()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Role;
+
+    const HINT_FONT_SIZE: f64 = 14.0;
+
+    fn hint(label: &str, x: f64, y: f64) -> Hint {
+        Hint::new(
+            label.to_string(),
+            LogicalPoint::new(x, y),
+            format!("el-{}", label),
+        )
+    }
+
+    fn layout(hints: &[Hint]) -> Vec<LabelBox> {
+        HintLayout::new().layout(hints, &HintTheme::default(), HintMode::Left)
+    }
+
+    fn box_size(label: &str) -> (f64, f64) {
+        HintLayout::new().box_size(label, &HintStyle::default(), 1.0)
+    }
+
+    fn assert_no_overlaps(boxes: &[LabelBox]) {
+        for (i, a) in boxes.iter().enumerate() {
+            for b in &boxes[i + 1..] {
+                assert_eq!(
+                    a.rect.intersection_area(&b.rect),
+                    0.0,
+                    "{} overlaps {}",
+                    a.label,
+                    b.label
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_isolated_hint_is_centered() {
+        let boxes = layout(&[hint("a", 100.0, 100.0)]);
+
+        let (width, height) = box_size("a");
+        // Un solo caracter no baja del ancho minimo (2 veces la fuente)
+        assert_eq!(width, 28.0);
+        assert_eq!(boxes[0].rect.center(), LogicalPoint::new(100.0, 100.0));
+        assert_eq!((boxes[0].rect.width, boxes[0].rect.height), (width, height));
+        assert_eq!(boxes[0].font_size, HINT_FONT_SIZE);
+        assert_eq!(boxes[0].leader, None);
+    }
+
+    #[test]
+    fn test_shrinks_before_displacing() {
+        // La caja centrada de "s" solapa un poco con la de "a"; encogida cabe
+        let (_, height) = box_size("a");
+        let boxes = layout(&[hint("a", 100.0, 100.0), hint("s", 100.0, 97.0 + height)]);
+
+        assert_no_overlaps(&boxes);
+        assert!(boxes[1].font_size < HINT_FONT_SIZE);
+        assert_eq!(
+            boxes[1].rect.center(),
+            LogicalPoint::new(100.0, 97.0 + height)
+        );
+        assert_eq!(boxes[1].leader, None);
+    }
+
+    #[test]
+    fn test_displaced_label_gets_leader() {
+        // Anclajes a 10px: ninguna posicion cercana ni encogida cabe
+        let boxes = layout(&[hint("a", 100.0, 100.0), hint("s", 110.0, 100.0)]);
+
+        assert_no_overlaps(&boxes);
+        // Arriba a la izquierda, un alto de caja mas lejos
+        let (width, height) = box_size("s");
+        let gap = ANCHOR_GAP + height;
+        let corner = LogicalPoint::new(110.0 - gap, 100.0 - gap);
+        assert_eq!(
+            boxes[1].rect,
+            LogicalRect::new(corner.x - width, corner.y - height, width, height)
+        );
+        assert_eq!(
+            boxes[1].leader,
+            Some(LeaderLine {
+                from: LogicalPoint::new(110.0, 100.0),
+                to: corner,
+            })
+        );
+    }
+
+    #[test]
+    fn test_dense_toolbar_has_no_overlaps() {
+        // Fila de botones de 16px, mas estrechos que las cajas
+        let hints: Vec<Hint> = ["a", "s", "d", "f", "g", "h"]
+            .iter()
+            .enumerate()
+            .map(|(i, label)| hint(label, 100.0 + i as f64 * 16.0, 200.0))
+            .collect();
+
+        let boxes = layout(&hints);
+
+        assert_no_overlaps(&boxes);
+        let displaced = boxes.iter().filter(|b| b.leader.is_some()).count();
+        assert!(displaced > 0, "crowded labels should get leader lines");
+        for (label_box, hint) in boxes.iter().zip(&hints) {
+            if let Some(leader) = label_box.leader {
+                assert_eq!(leader.from, hint.anchor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_boxes_carry_mode_and_role_style() {
+        let theme = HintTheme::default();
+        let hints = [hint("a", 100.0, 100.0).with_role(Role::Link)];
+
+        let boxes = HintLayout::new().layout(&hints, &theme, HintMode::Right);
+        assert_eq!(
+            boxes[0].style,
+            theme.resolve(HintMode::Right, Some(&Role::Link))
+        );
+    }
+
+    #[test]
+    fn test_layout_is_deterministic() {
+        let hints: Vec<Hint> = (0..20)
+            .map(|i| {
+                hint(
+                    &format!("a{}", i),
+                    (i % 5) as f64 * 10.0,
+                    (i / 5) as f64 * 10.0,
+                )
+            })
+            .collect();
+
+        assert_eq!(layout(&hints), layout(&hints));
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+use crate::models::{LabelBox, LabelState, SceneNode, SceneOp};
+
+/// Escena retenida de hints, indexada por `element_id`.
+///
+/// `WindowManager` guarda la escena dibujada y la compara con la siguiente
+/// (un rescan, un prefijo nuevo) para mandar al renderer solo las
+/// operaciones necesarias en vez de redibujarlo todo.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HintScene {
+    nodes: Vec<SceneNode>,
+    index: HashMap<String, usize>,
+}
+
+impl HintScene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Escena con las cajas colocadas por el layout, sin prefijo escrito.
+    /// Si dos cajas comparten `element_id` se queda la primera.
+    pub fn from_labels(labels: Vec<LabelBox>) -> Self {
+        let mut scene = Self::new();
+        for label_box in labels {
+            scene.insert(SceneNode {
+                label_box,
+                state: LabelState::Idle,
+            });
+        }
+        scene
+    }
+
+    /// Aplica una operacion de `diff`; asi un renderer puede retener la
+    /// escena sin reimplementar la logica. Las operaciones sobre nodos que
+    /// no existen se ignoran.
+    #[cfg(test)]
+    pub fn apply(&mut self, op: &SceneOp) {
+        match op {
+            SceneOp::Add { node } | SceneOp::Update { node } => {
+                match self.index.get(node.element_id()) {
+                    Some(&index) => self.nodes[index] = node.clone(),
+                    None => self.insert(node.clone()),
+                }
+            }
+            SceneOp::Move {
+                element_id,
+                rect,
+                font_size,
+                leader,
+            } => {
+                if let Some(&index) = self.index.get(element_id) {
+                    let label_box = &mut self.nodes[index].label_box;
+                    label_box.rect = *rect;
+                    label_box.font_size = *font_size;
+                    label_box.leader = *leader;
+                }
+            }
+            SceneOp::Remove { element_id } => {
+                if let Some(index) = self.index.remove(element_id) {
+                    self.nodes.remove(index);
+                    for position in self.index.values_mut() {
+                        if *position > index {
+                            *position -= 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, node: SceneNode) {
+        if self.index.contains_key(node.element_id()) {
+            tracing::warn!(
+                "HintScene: duplicate element id '{}' ignored",
+                node.element_id()
+            );
+            return;
+        }
+        self.index
+            .insert(node.element_id().to_string(), self.nodes.len());
+        self.nodes.push(node);
+    }
+
+    /// Nodos en orden de dibujo
+    #[cfg(test)]
+    pub fn nodes(&self) -> &[SceneNode] {
+        &self.nodes
+    }
+
+    pub fn get(&self, element_id: &str) -> Option<&SceneNode> {
+        self.index.get(element_id).map(|&index| &self.nodes[index])
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Misma escena con el estado de cada label para un prefijo (sin
+    /// distinguir mayusculas). Un prefijo vacio vuelve todo a `Idle`.
+    pub fn with_prefix(&self, prefix: &str) -> Self {
+        let prefix = prefix.to_lowercase();
+        let typed = prefix.chars().count();
+        let is_match = |node: &SceneNode| node.label_box.label.to_lowercase().starts_with(&prefix);
+        let unique = self.nodes.iter().filter(|node| is_match(node)).count() == 1;
+
+        let mut scene = self.clone();
+        for node in &mut scene.nodes {
+            node.state = match (typed, is_match(node), unique) {
+                (0, _, _) => LabelState::Idle,
+                (_, false, _) => LabelState::Hidden,
+                (_, true, true) => LabelState::Unique { typed },
+                (_, true, false) => LabelState::Matching { typed },
+            };
+        }
+        scene
+    }
+
+    /// `element_id` de los labels que no estan ocultos, en orden
+    pub fn visible_ids(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter(|node| node.state != LabelState::Hidden)
+            .map(|node| node.element_id().to_string())
+            .collect()
+    }
+
+    /// Operaciones para pasar de esta escena a `next`: primero las bajas y
+    /// despues altas, movimientos y cambios en el orden de `next`
+    pub fn diff(&self, next: &HintScene) -> Vec<SceneOp> {
+        let removed = self
+            .nodes
+            .iter()
+            .filter(|node| next.get(node.element_id()).is_none())
+            .map(|node| SceneOp::Remove {
+                element_id: node.element_id().to_string(),
+            });
+
+        let changed = next.nodes.iter().filter_map(|node| {
+            let Some(old) = self.get(node.element_id()) else {
+                return Some(SceneOp::Add { node: node.clone() });
+            };
+            if old == node {
+                return None;
+            }
+
+            let (before, after) = (&old.label_box, &node.label_box);
+            let same_content = before.label == after.label
+                && before.style == after.style
+                && old.state == node.state;
+            Some(if same_content {
+                SceneOp::Move {
+                    element_id: node.element_id().to_string(),
+                    rect: after.rect,
+                    font_size: after.font_size,
+                    leader: after.leader,
+                }
+            } else {
+                SceneOp::Update { node: node.clone() }
+            })
+        });
+
+        removed.chain(changed).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{HintStyle, LogicalRect};
+
+    fn label(text: &str, x: f64) -> LabelBox {
+        LabelBox {
+            label: text.to_string(),
+            element_id: format!("el-{}", text),
+            rect: LogicalRect::new(x, 0.0, 20.0, 20.0),
+            font_size: 14.0,
+            leader: None,
+            style: HintStyle::default(),
+        }
+    }
+
+    fn scene(labels: &[(&str, f64)]) -> HintScene {
+        HintScene::from_labels(labels.iter().map(|(text, x)| label(text, *x)).collect())
+    }
+
+    fn ids(ops: &[SceneOp]) -> Vec<String> {
+        ops.iter()
+            .map(|op| match op {
+                SceneOp::Add { node } => format!("add {}", node.element_id()),
+                SceneOp::Move { element_id, .. } => format!("move {}", element_id),
+                SceneOp::Update { node } => format!("update {}", node.element_id()),
+                SceneOp::Remove { element_id } => format!("remove {}", element_id),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_first_frame_adds_everything() {
+        let next = scene(&[("a", 0.0), ("s", 30.0)]);
+        assert_eq!(
+            ids(&HintScene::new().diff(&next)),
+            vec!["add el-a", "add el-s"]
+        );
+    }
+
+    #[test]
+    fn test_identical_scenes_have_no_ops() {
+        let current = scene(&[("a", 0.0), ("s", 30.0)]);
+        assert!(current.diff(&current.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_rescan_moves_adds_and_removes() {
+        let current = scene(&[("a", 0.0), ("s", 30.0), ("d", 60.0)]);
+        // "a" se desplaza, "s" sigue igual, "d" desaparece y llega "f"
+        let next = scene(&[("a", 5.0), ("s", 30.0), ("f", 90.0)]);
+
+        let ops = current.diff(&next);
+        assert_eq!(ids(&ops), vec!["remove el-d", "move el-a", "add el-f"]);
+        assert_eq!(
+            ops[1],
+            SceneOp::Move {
+                element_id: "el-a".to_string(),
+                rect: LogicalRect::new(5.0, 0.0, 20.0, 20.0),
+                font_size: 14.0,
+                leader: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_style_change_is_an_update() {
+        let current = scene(&[("a", 0.0)]);
+        let mut restyled = label("a", 10.0);
+        restyled.style.font_size = 20.0;
+        let next = HintScene::from_labels(vec![restyled]);
+
+        assert_eq!(ids(&current.diff(&next)), vec!["update el-a"]);
+    }
+
+    #[test]
+    fn test_prefix_states() {
+        let current = scene(&[("as", 0.0), ("ad", 30.0), ("sa", 60.0)]);
+
+        let typed_a = current.with_prefix("A");
+        let states: Vec<LabelState> = ["el-as", "el-ad", "el-sa"]
+            .iter()
+            .filter_map(|id| typed_a.get(id).map(|node| node.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                LabelState::Matching { typed: 1 },
+                LabelState::Matching { typed: 1 },
+                LabelState::Hidden,
+            ]
+        );
+
+        let typed_ad = current.with_prefix("ad");
+        assert_eq!(
+            typed_ad.get("el-ad").map(|node| node.state),
+            Some(LabelState::Unique { typed: 2 })
+        );
+        assert_eq!(typed_ad.visible_ids(), vec!["el-ad"]);
+        assert_eq!(current.with_prefix(""), current);
+    }
+
+    #[test]
+    fn test_keystroke_only_touches_changed_labels() {
+        let current = scene(&[("as", 0.0), ("ad", 30.0), ("sa", 60.0)]);
+        let typed_a = current.with_prefix("a");
+        let typed_ad = current.with_prefix("ad");
+
+        assert_eq!(current.diff(&typed_a).len(), 3);
+        // "sa" ya estaba oculto: no se vuelve a tocar
+        assert_eq!(
+            ids(&typed_a.diff(&typed_ad)),
+            vec!["update el-as", "update el-ad"]
+        );
+    }
+
+    #[test]
+    fn test_applying_diff_reaches_next_scene() {
+        let current = scene(&[("a", 0.0), ("s", 30.0), ("d", 60.0)]);
+        let mut restyled = label("s", 30.0);
+        restyled.style.font_size = 20.0;
+        let next = HintScene::from_labels(vec![label("f", 90.0), label("a", 5.0), restyled])
+            .with_prefix("a");
+
+        let mut applied = current.clone();
+        for op in current.diff(&next) {
+            applied.apply(&op);
+        }
+
+        assert_eq!(applied.len(), next.len());
+        for node in next.nodes() {
+            assert_eq!(applied.get(node.element_id()), Some(node));
+        }
+        assert!(applied.get("el-d").is_none());
+    }
+
+    #[test]
+    fn test_duplicate_ids_keep_first() {
+        let mut duplicate = label("s", 30.0);
+        duplicate.element_id = "el-a".to_string();
+        let current = HintScene::from_labels(vec![label("a", 0.0), duplicate]);
+
+        assert_eq!(current.len(), 1);
+        assert_eq!(
+            current
+                .get("el-a")
+                .map(|node| node.label_box.label.as_str()),
+            Some("a")
+        );
+    }
+}
@@ -1,61 +1,210 @@
-use crate::models::{Hint, UIElement};
+use crate::models::{Hint, KeyboardLayout, LabelStrategy, LogicalPoint, UIElement};
 
-/// Charset para generar hints (home row)
+use super::label_cost::{labels_look_alike, TypingCostModel};
+
+/// Charset por defecto (home row QWERTY); el de la config sale de
+/// `keyboard_layout_service::hint_charset`
 const HINT_CHARS: &str = "asdfghjkl";
 
+/// Distancia (puntos) entre anclajes por debajo de la cual dos hints se
+/// consideran vecinos y no deben llevar labels parecidos
+const NEIGHBOR_DISTANCE: f64 = 80.0;
+
+/// Puntuacion de uso a partir de la cual un elemento merece una sola tecla
+/// (unos dos clicks recientes)
+const HOT_USAGE_SCORE: f64 = 2.0;
+
 /// Generador de hints tipo Vimium
 pub struct HintGenerator {
     charset: Vec<char>,
+    strategy: LabelStrategy,
+    cost_model: TypingCostModel,
 }
 
 impl HintGenerator {
     pub fn new() -> Self {
         tracing::debug!("Initializing HintGenerator");
-        Self {
-            charset: HINT_CHARS.chars().collect(),
-        }
+        Self::with_charset(HINT_CHARS)
     }
 
-    /// Crea un generador con charset personalizado
-    #[allow(dead_code)]
+    /// Crea un generador con charset personalizado (ej: el del layout activo)
     pub fn with_charset(charset: &str) -> Self {
         Self {
             charset: charset.chars().collect(),
+            strategy: LabelStrategy::BaseN,
+            cost_model: TypingCostModel::new(KeyboardLayout::default()),
         }
     }
 
-    /// Genera hints para una lista de elementos
+    /// Elige como se reparten los labels; `layout` es con el que se
+    /// teclean (lo usa la estrategia ergonomica)
+    pub fn with_strategy(mut self, strategy: LabelStrategy, layout: KeyboardLayout) -> Self {
+        self.strategy = strategy;
+        self.cost_model = TypingCostModel::new(layout);
+        self
+    }
+
+    /// Genera hints para una lista de elementos, de mayor a menor prioridad
     pub fn generate(&self, elements: &[UIElement]) -> Vec<Hint> {
-        tracing::debug!("Generating hints for {} elements", elements.len());
+        self.generate_ranked(elements, 0)
+    }
 
-        let labels = self.generate_labels(elements.len());
+    /// Genera hints dando prioridad a los elementos mas usados: `usage` es
+    /// la puntuacion de cada elemento (mismo orden). Los que llegan a
+    /// `HOT_USAGE_SCORE` reciben labels de una tecla si el resto de labels
+    /// no tiene que crecer por ello. Los hints salen del mas al menos usado.
+    pub fn generate_with_usage(&self, elements: &[UIElement], usage: &[f64]) -> Vec<Hint> {
+        let score = |index: usize| usage.get(index).copied().unwrap_or(0.0);
 
-        elements
+        // Estable: a igual uso se mantiene el orden del escaneo
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        order.sort_by(|a, b| score(*b).total_cmp(&score(*a)));
+
+        let hot = order
             .iter()
-            .zip(labels.into_iter())
-            .map(|(element, label)| {
-                // Calcular centro del elemento
-                let center_x = element.x + element.width / 2.0;
-                let center_y = element.y + element.height / 2.0;
+            .filter(|index| score(**index) >= HOT_USAGE_SCORE)
+            .count();
+        let ranked: Vec<UIElement> = order.iter().map(|index| elements[*index].clone()).collect();
+
+        self.generate_ranked(&ranked, hot)
+    }
 
-                Hint::new(label, center_x, center_y, element.id.clone())
+    fn generate_ranked(&self, elements: &[UIElement], hot: usize) -> Vec<Hint> {
+        tracing::debug!(
+            "Generating {:?} hints for {} elements ({} hot)",
+            self.strategy,
+            elements.len(),
+            hot
+        );
+
+        // Label en el centro de la parte visible; el click puede ir a otro
+        // punto del elemento
+        let anchors: Vec<LogicalPoint> = elements
+            .iter()
+            .map(|element| element.visible_frame().center())
+            .collect();
+
+        let singles = self.single_key_slots(elements.len(), hot);
+        let labels = match self.strategy {
+            LabelStrategy::BaseN => self.generate_labels(elements.len(), singles),
+            LabelStrategy::Ergonomic => self.generate_ergonomic_labels(&anchors, singles),
+        };
+
+        elements
+            .iter()
+            .zip(anchors)
+            .zip(labels)
+            .map(|((element, anchor), label)| {
+                Hint::new(label, anchor, element.id.clone())
+                    .with_click_point(element.click_point())
+                    .with_role(element.role.clone())
             })
             .collect()
     }
 
-    /// Genera labels base-N para N elementos
-    fn generate_labels(&self, count: usize) -> Vec<String> {
+    /// Cuantos de los `hot` primeros elementos pueden llevar una sola
+    /// tecla. Esas teclas ya no empiezan labels largos, asi que solo se
+    /// reservan mientras el resto siga cabiendo con la misma longitud.
+    fn single_key_slots(&self, count: usize, hot: usize) -> usize {
+        let base = self.charset.len();
+        let digits = self.calculate_digits(count, base);
+        if digits == 1 {
+            // Ya son todos de una tecla
+            return 0;
+        }
+
+        let block = base.pow(digits as u32 - 1);
+        (0..=hot.min(base - 1))
+            .rev()
+            .find(|singles| (base - singles) * block + singles >= count)
+            .unwrap_or(0)
+    }
+
+    /// Labels ordenados por coste de tecleo: el mas barato va al primer
+    /// elemento. Los `singles` caracteres mas baratos son labels de una
+    /// tecla; el resto tiene la longitud de base-N y empieza por otro
+    /// caracter, asi ningun label es prefijo de otro. Un elemento no recibe
+    /// un label parecido al de un vecino ya asignado salvo que no quede
+    /// otro.
+    fn generate_ergonomic_labels(&self, anchors: &[LogicalPoint], singles: usize) -> Vec<String> {
+        let count = anchors.len();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let base = self.charset.len();
+        let digits = self.calculate_digits(count, base);
+
+        let mut by_cost = self.charset.clone();
+        by_cost.sort_by(|a, b| {
+            self.cost_model
+                .key_cost(*a)
+                .total_cmp(&self.cost_model.key_cost(*b))
+        });
+        let single_chars = &by_cost[..singles];
+
+        let mut candidates: Vec<(f64, String)> = single_chars
+            .iter()
+            .map(|ch| ch.to_string())
+            .chain(
+                (0..base.pow(digits as u32))
+                    .map(|index| self.index_to_label(index, digits))
+                    .filter(|label| !label.starts_with(single_chars)),
+            )
+            .map(|label| (self.cost_model.label_cost(&label), label))
+            .collect();
+        // Estable: a igual coste se mantiene el orden base-N
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut used = vec![false; candidates.len()];
+        let mut labels: Vec<String> = Vec::with_capacity(count);
+
+        for anchor in anchors {
+            let neighbor_labels: Vec<&String> = labels
+                .iter()
+                .zip(anchors)
+                .filter(|(_, other)| anchor.distance_to(**other) < NEIGHBOR_DISTANCE)
+                .map(|(label, _)| label)
+                .collect();
+
+            let free = || (0..candidates.len()).filter(|i| !used[*i]);
+            let chosen = free()
+                .find(|i| {
+                    !neighbor_labels
+                        .iter()
+                        .any(|label| labels_look_alike(label, &candidates[*i].1))
+                })
+                .or_else(|| free().next())
+                .expect("there are at least as many candidates as elements");
+
+            used[chosen] = true;
+            labels.push(candidates[chosen].1.clone());
+        }
+
+        labels
+    }
+
+    /// Genera labels base-N para N elementos. Los `singles` primeros
+    /// caracteres del charset son labels de una tecla y los demas labels
+    /// no empiezan por ellos
+    fn generate_labels(&self, count: usize, singles: usize) -> Vec<String> {
         if count == 0 {
             return Vec::new();
         }
 
         let base = self.charset.len();
-        let mut labels = Vec::with_capacity(count);
 
         // Calcular cuántos dígitos necesitamos
         let digits = self.calculate_digits(count, base);
 
-        for i in 0..count {
+        let mut labels: Vec<String> = self.charset[..singles]
+            .iter()
+            .map(|ch| ch.to_string())
+            .collect();
+
+        // Saltar los labels largos que empiezan por una tecla ya usada sola
+        let first = singles * base.pow(digits as u32 - 1);
+        for i in first..first + count - singles {
             labels.push(self.index_to_label(i, digits));
         }
 
@@ -102,23 +251,21 @@ impl Default for HintGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{LogicalPoint, LogicalRect, Role};
 
     fn make_element(id: &str, x: f64, y: f64) -> UIElement {
         UIElement::new(
             id.to_string(),
-            "AXButton".to_string(),
+            Role::Button,
             None,
-            x,
-            y,
-            100.0,
-            30.0,
+            LogicalRect::new(x, y, 100.0, 30.0),
         )
     }
 
     #[test]
     fn test_generate_single_char_labels() {
         let gen = HintGenerator::new();
-        let labels = gen.generate_labels(5);
+        let labels = gen.generate_labels(5, 0);
 
         assert_eq!(labels.len(), 5);
         assert_eq!(labels[0], "a");
@@ -130,7 +277,7 @@ mod tests {
     fn test_generate_double_char_labels() {
         let gen = HintGenerator::new();
         // Con 9 chars, necesitamos 2 dígitos para >9 elementos
-        let labels = gen.generate_labels(15);
+        let labels = gen.generate_labels(15, 0);
 
         assert_eq!(labels.len(), 15);
         assert_eq!(labels[0], "aa");
@@ -151,8 +298,145 @@ mod tests {
         assert_eq!(hints[0].label, "a");
         assert_eq!(hints[1].label, "s");
         // Centro de btn1: (10 + 100/2, 20 + 30/2) = (60, 35)
-        assert_eq!(hints[0].x, 60.0);
-        assert_eq!(hints[0].y, 35.0);
+        assert_eq!(hints[0].anchor, LogicalPoint::new(60.0, 35.0));
+    }
+
+    #[test]
+    fn test_hint_carries_click_point() {
+        let gen = HintGenerator::new();
+        let mut element = make_element("field", 10.0, 20.0);
+        element.click_point = Some(LogicalPoint::new(18.0, 35.0));
+
+        let hints = gen.generate(&[element]);
+
+        assert_eq!(hints[0].anchor, LogicalPoint::new(60.0, 35.0));
+        assert_eq!(hints[0].click_point, LogicalPoint::new(18.0, 35.0));
+    }
+
+    #[test]
+    fn test_ergonomic_labels_start_with_cheapest_keys() {
+        let gen =
+            HintGenerator::new().with_strategy(LabelStrategy::Ergonomic, KeyboardLayout::Qwerty);
+        let elements: Vec<UIElement> = (0..4)
+            .map(|i| make_element(&format!("btn{}", i), 0.0, 200.0 * i as f64))
+            .collect();
+
+        let labels: Vec<String> = gen
+            .generate(&elements)
+            .into_iter()
+            .map(|h| h.label)
+            .collect();
+
+        // Indices primero, luego corazones
+        assert_eq!(labels, vec!["f", "j", "d", "k"]);
+    }
+
+    #[test]
+    fn test_ergonomic_labels_avoid_same_finger_bigrams() {
+        let gen =
+            HintGenerator::new().with_strategy(LabelStrategy::Ergonomic, KeyboardLayout::Qwerty);
+        let elements: Vec<UIElement> = (0..20)
+            .map(|i| make_element(&format!("btn{}", i), 0.0, 200.0 * i as f64))
+            .collect();
+
+        let hints = gen.generate(&elements);
+        let labels: Vec<&str> = hints.iter().map(|h| h.label.as_str()).collect();
+
+        assert_eq!(labels.len(), 20);
+        assert!(labels.iter().all(|l| l.len() == 2));
+        // Los primeros alternan manos
+        assert!(["fj", "jf"].contains(&labels[0]));
+        assert!(!labels[..10].contains(&"fg"));
+        let mut unique = labels.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 20);
+    }
+
+    #[test]
+    fn test_ergonomic_labels_differ_between_neighbors() {
+        let gen =
+            HintGenerator::new().with_strategy(LabelStrategy::Ergonomic, KeyboardLayout::Qwerty);
+        // Dos botones pegados: "fj" y "jf" serian los mas baratos
+        let elements: Vec<UIElement> = (0..10)
+            .map(|i| make_element(&format!("btn{}", i), 0.0, 30.0 * i as f64))
+            .collect();
+
+        let hints = gen.generate(&elements);
+
+        assert!(!labels_look_alike(&hints[0].label, &hints[1].label));
+    }
+
+    fn make_column(count: usize) -> Vec<UIElement> {
+        (0..count)
+            .map(|i| make_element(&format!("btn{}", i), 0.0, 200.0 * i as f64))
+            .collect()
+    }
+
+    fn assert_prefix_free(hints: &[Hint]) {
+        for a in hints {
+            for b in hints {
+                if a.element_id != b.element_id {
+                    assert!(!b.label.starts_with(&a.label), "{} {}", a.label, b.label);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hot_elements_get_single_keys() {
+        let gen = HintGenerator::new();
+        let mut usage = vec![0.0; 20];
+        usage[7] = 5.0;
+        usage[3] = 2.5;
+        // Usado, pero no lo bastante
+        usage[12] = 1.0;
+
+        let hints = gen.generate_with_usage(&make_column(20), &usage);
+
+        assert_eq!(hints.len(), 20);
+        assert_eq!(
+            (hints[0].element_id.as_str(), hints[0].label.as_str()),
+            ("btn7", "a")
+        );
+        assert_eq!(
+            (hints[1].element_id.as_str(), hints[1].label.as_str()),
+            ("btn3", "s")
+        );
+        assert_eq!(hints[2].element_id, "btn12");
+        assert!(hints[2..].iter().all(|h| h.label.len() == 2));
+        assert_prefix_free(&hints);
+    }
+
+    #[test]
+    fn test_single_keys_never_lengthen_other_labels() {
+        let gen = HintGenerator::new();
+        // 81 = 9^2: reservar una tecla obligaria a usar 3 letras
+        let mut usage = vec![0.0; 81];
+        usage[40] = 10.0;
+
+        let hints = gen.generate_with_usage(&make_column(81), &usage);
+
+        assert_eq!(hints[0].element_id, "btn40");
+        assert!(hints.iter().all(|h| h.label.len() == 2));
+        assert_prefix_free(&hints);
+    }
+
+    #[test]
+    fn test_ergonomic_hot_element_gets_cheapest_key() {
+        let gen =
+            HintGenerator::new().with_strategy(LabelStrategy::Ergonomic, KeyboardLayout::Qwerty);
+        let mut usage = vec![0.0; 30];
+        usage[29] = 3.0;
+
+        let hints = gen.generate_with_usage(&make_column(30), &usage);
+
+        assert_eq!(
+            (hints[0].element_id.as_str(), hints[0].label.as_str()),
+            ("btn29", "f")
+        );
+        assert!(hints[1..].iter().all(|h| h.label.len() == 2));
+        assert_prefix_free(&hints);
     }
 
     #[test]
@@ -1,75 +1,422 @@
+use crate::models::{
+    HotkeyAction, HotkeyBinding, HotkeyConflict, HotkeyStatus, KeyCombo, KeySequence, KeySide,
+    ModifierKey,
+};
+use crate::services::sequence_matcher::{SequenceMatcher, SequenceOutcome, SEQUENCE_TIMEOUT};
 use crate::{AppError, Result};
-use crossbeam_channel::Receiver;
-use global_hotkey::hotkey::{Code, HotKey, Modifiers};
-use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager};
+use global_hotkey::hotkey::{HotKey, Modifiers};
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use std::cell::RefCell;
+use std::sync::mpsc;
+use std::time::Instant;
 
-pub const DEFAULT_HOTKEY: &str = "Cmd+Shift+J";
+/// Combos que el sistema atiende antes que cualquier app: registrarlos o
+/// falla o no llega nunca el evento
+#[cfg(target_os = "macos")]
+const RESERVED_COMBOS: &[(&str, &str)] = &[
+    ("Cmd+Tab", "app switcher"),
+    ("Cmd+Shift+Tab", "app switcher"),
+    ("Cmd+Space", "Spotlight"),
+    ("Ctrl+Space", "input source"),
+    ("Cmd+Alt+Esc", "force quit"),
+    ("Ctrl+Cmd+Q", "lock screen"),
+    ("Cmd+Shift+3", "screenshot"),
+    ("Cmd+Shift+4", "screenshot"),
+    ("Cmd+Shift+5", "screenshot"),
+];
 
-/// Servicio para gestionar hotkeys globales (macOS/Win)
+#[cfg(not(target_os = "macos"))]
+const RESERVED_COMBOS: &[(&str, &str)] = &[
+    ("Alt+Tab", "app switcher"),
+    ("Alt+Shift+Tab", "app switcher"),
+    ("Ctrl+Alt+Delete", "security screen"),
+    ("Super+L", "lock screen"),
+];
+
+/// Registro de hotkeys en el sistema; separado del servicio para poder
+/// probar el rebinding sin tocar el OS
+pub trait HotkeyBackend: Send {
+    /// Registra el hotkey; el error es el mensaje del SO
+    fn register(&mut self, hotkey: HotKey) -> std::result::Result<(), String>;
+
+    fn unregister(&mut self, hotkey: HotKey) -> Result<()>;
+}
+
+/// Ejecuta una tarea en el hilo principal (en la app,
+/// `AppHandle::run_on_main_thread`); desde el propio hilo principal la
+/// ejecuta en el momento
+pub type MainThreadRunner = Box<dyn Fn(Box<dyn FnOnce() + Send>) -> Result<()> + Send>;
+
+thread_local! {
+    // En macOS el manager se crea y se usa solo en el hilo principal, asi
+    // que solo existe ahi. Vive lo que la app: al soltarlo se pierden sus
+    // registros
+    static MANAGER: RefCell<Option<GlobalHotKeyManager>> = const { RefCell::new(None) };
+}
+
+/// Backend de global-hotkey. Cada llamada se manda al hilo principal, donde
+/// se crea el manager en el primer registro, y espera el resultado: sirve
+/// igual desde un comando async que desde el hilo de eventos
+pub struct GlobalHotkeyBackend {
+    run_on_main: MainThreadRunner,
+}
+
+impl GlobalHotkeyBackend {
+    pub fn new(run_on_main: MainThreadRunner) -> Self {
+        Self { run_on_main }
+    }
+
+    fn on_main_thread<T, F>(&self, task: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&GlobalHotKeyManager) -> T + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        (self.run_on_main)(Box::new(move || {
+            let result = MANAGER.with(|slot| {
+                let mut slot = slot.borrow_mut();
+                if slot.is_none() {
+                    *slot = Some(GlobalHotKeyManager::new()?);
+                }
+                slot.as_ref()
+                    .map(task)
+                    .ok_or_else(|| AppError::Hotkey("Hotkey manager unavailable".to_string()))
+            });
+            let _ = tx.send(result);
+        }))?;
+
+        rx.recv()
+            .map_err(|_| AppError::Hotkey("Hotkey task dropped by the main thread".to_string()))?
+    }
+}
+
+impl HotkeyBackend for GlobalHotkeyBackend {
+    fn register(&mut self, hotkey: HotKey) -> std::result::Result<(), String> {
+        self.on_main_thread(move |manager| manager.register(hotkey).map_err(|e| e.to_string()))
+            .map_err(|e| e.to_string())?
+    }
+
+    fn unregister(&mut self, hotkey: HotKey) -> Result<()> {
+        Ok(self.on_main_thread(move |manager| manager.unregister(hotkey))??)
+    }
+}
+
+/// Backend hasta que `start_hotkeys` instala el real: sin hilo principal
+/// todavia no se puede registrar nada
+struct DetachedBackend;
+
+impl HotkeyBackend for DetachedBackend {
+    fn register(&mut self, _hotkey: HotKey) -> std::result::Result<(), String> {
+        Err("hotkeys are not started yet".to_string())
+    }
+
+    fn unregister(&mut self, _hotkey: HotKey) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Asignacion activa; varias pueden compartir leader ("Cmd+J F" y
+/// "Cmd+J S"), que se registra una sola vez
+struct ActiveBinding {
+    binding: HotkeyBinding,
+    // Hotkey de cada paso; el primero es el leader registrado
+    steps: Vec<HotKey>,
+}
+
+impl ActiveBinding {
+    fn leader(&self) -> HotKey {
+        self.steps[0]
+    }
+}
+
+/// Servicio para gestionar hotkeys globales (macOS/Win): un combo o
+/// secuencia por accion, todos en el mismo manager
 pub struct HotkeyService {
-    registered_hotkey: Option<String>,
-    is_active: bool,
+    backend: Box<dyn HotkeyBackend>,
+    active: Vec<ActiveBinding>,
+    // Ultimo intento fallido de cada accion (p. ej. al arrancar con un combo
+    // que ya tiene otra app); la accion puede seguir con su combo anterior
+    failures: Vec<(HotkeyBinding, HotkeyConflict)>,
+    // Acciones que fallaron al arrancar y aun no se han avisado al frontend
+    unreported: Vec<HotkeyAction>,
+    matcher: SequenceMatcher<HotKey>,
+    // Siguientes pasos registrados mientras hay una secuencia a medias
+    follow_ups: Vec<HotKey>,
 }
 
 impl HotkeyService {
+    /// Servicio sin backend: los registros fallan hasta `set_backend`
     pub fn new() -> Self {
         tracing::debug!("Initializing HotkeyService");
+        Self::with_backend(Box::new(DetachedBackend))
+    }
+
+    pub fn with_backend(backend: Box<dyn HotkeyBackend>) -> Self {
         Self {
-            registered_hotkey: None,
-            is_active: false,
+            backend,
+            active: Vec::new(),
+            failures: Vec::new(),
+            unreported: Vec::new(),
+            matcher: SequenceMatcher::new(SEQUENCE_TIMEOUT),
+            follow_ups: Vec::new(),
         }
     }
 
-    /// Registra el hotkey por defecto (Cmd+Shift+J)
-    #[allow(dead_code)]
-    pub fn register_default(&mut self) -> Result<Receiver<GlobalHotKeyEvent>> {
-        self.register(DEFAULT_HOTKEY)
+    /// Cambia el backend; se hace una vez al arrancar, antes del primer
+    /// registro
+    pub fn set_backend(&mut self, backend: Box<dyn HotkeyBackend>) {
+        self.backend = backend;
     }
 
-    /// Registra un hotkey global y devuelve un receptor de eventos
-    pub fn register(&mut self, hotkey: &str) -> Result<Receiver<GlobalHotKeyEvent>> {
-        tracing::info!("Registering hotkey: {}", hotkey);
+    /// Asigna `combo` a `action`. El leader nuevo se registra antes de
+    /// soltar el anterior: si falla, la accion sigue con el que tenia
+    pub fn bind(&mut self, action: HotkeyAction, combo: KeySequence) -> Result<()> {
+        tracing::info!("Binding hotkey {} to {:?}", combo, action);
 
-        let parsed = parse_hotkey(hotkey)?;
-        let manager = GlobalHotKeyManager::new().map_err(|e| AppError::Hotkey(e.to_string()))?;
-        manager
-            .register(parsed)
-            .map_err(|e| AppError::Hotkey(e.to_string()))?;
+        let result = self.try_bind(action, combo.clone());
+        self.release_follow_ups();
+        self.reload_sequences();
 
-        let rx = GlobalHotKeyEvent::receiver().clone();
+        match result {
+            Ok(()) => {
+                self.failures
+                    .retain(|(binding, _)| binding.action != action);
+                Ok(())
+            }
+            Err(conflict) => {
+                tracing::warn!(
+                    "Hotkey {} for {:?} not registered: {}",
+                    combo,
+                    action,
+                    conflict
+                );
 
-        self.registered_hotkey = Some(hotkey.to_string());
-        self.is_active = true;
+                // Queda en el estado aunque la accion conserve su combo
+                // anterior, para que ajustes pueda explicar el fallo
+                self.failures
+                    .retain(|(binding, _)| binding.action != action);
+                self.failures
+                    .push((HotkeyBinding::new(action, combo.clone()), conflict.clone()));
 
-        Ok(rx)
+                Err(AppError::HotkeyConflict {
+                    combo: combo.to_string(),
+                    conflict,
+                })
+            }
+        }
     }
 
-    /// Desregistra el hotkey actual
-    #[allow(dead_code)]
-    pub fn unregister(&mut self) -> Result<()> {
-        tracing::info!("Unregistering hotkey");
-        if let Some(ref key) = self.registered_hotkey {
-            let parsed = parse_hotkey(key)?;
-            GlobalHotKeyManager::new()
-                .map_err(|e| AppError::Hotkey(e.to_string()))?
-                .unregister(parsed)
-                .map_err(|e| AppError::Hotkey(e.to_string()))?;
+    fn try_bind(
+        &mut self,
+        action: HotkeyAction,
+        combo: KeySequence,
+    ) -> std::result::Result<(), HotkeyConflict> {
+        let steps: Vec<HotKey> = combo.steps().iter().map(to_hotkey).collect();
+        let leader = steps[0];
+        if let Some(reason) = reserved_reason(&leader) {
+            return Err(HotkeyConflict::Reserved { reason });
         }
-        self.registered_hotkey = None;
-        self.is_active = false;
+
+        // Dos acciones no pueden tener la misma secuencia ni una que empiece
+        // por la otra ("Cmd+J" y "Cmd+J F"): la corta taparia a la larga
+        if let Some(other) = self.active.iter().find(|entry| {
+            entry.binding.action != action
+                && entry
+                    .steps
+                    .iter()
+                    .zip(&steps)
+                    .all(|(a, b)| a.id() == b.id())
+        }) {
+            return Err(HotkeyConflict::AlreadyBound {
+                action: other.binding.action,
+            });
+        }
+
+        // El leader puede estar ya registrado por otra secuencia o por la
+        // propia accion escrita de otra forma ("Shift+Cmd+J")
+        if !self.is_leader(leader) {
+            self.backend
+                .register(leader)
+                .map_err(|message| HotkeyConflict::RejectedByOs { message })?;
+        }
+
+        if let Some(index) = self
+            .active
+            .iter()
+            .position(|entry| entry.binding.action == action)
+        {
+            let old = self.active.remove(index).leader();
+            if old.id() != leader.id() && !self.is_leader(old) {
+                if let Err(e) = self.backend.unregister(old) {
+                    tracing::warn!("Failed to unregister previous hotkey {}: {}", old, e);
+                }
+            }
+        }
+
+        self.active.push(ActiveBinding {
+            binding: HotkeyBinding::new(action, combo),
+            steps,
+        });
         Ok(())
     }
 
-    /// Obtiene el hotkey registrado actualmente
-    #[allow(dead_code)]
-    pub fn current_hotkey(&self) -> Option<&str> {
-        self.registered_hotkey.as_deref()
+    /// Quita el combo de una accion (no hace nada si no tenia)
+    pub fn unbind(&mut self, action: HotkeyAction) -> Result<()> {
+        self.failures
+            .retain(|(binding, _)| binding.action != action);
+        let Some(index) = self
+            .active
+            .iter()
+            .position(|entry| entry.binding.action == action)
+        else {
+            return Ok(());
+        };
+
+        tracing::info!("Unbinding hotkey for {:?}", action);
+        let leader = self.active[index].leader();
+        let shared = self
+            .active
+            .iter()
+            .filter(|entry| entry.leader().id() == leader.id())
+            .count()
+            > 1;
+        if !shared {
+            self.backend.unregister(leader)?;
+        }
+        self.active.remove(index);
+
+        self.release_follow_ups();
+        self.reload_sequences();
+        Ok(())
+    }
+
+    /// Estado de cada accion: registrada, fallida (con el motivo) o sin combo
+    pub fn statuses(&self) -> Vec<HotkeyStatus> {
+        HotkeyAction::ALL
+            .into_iter()
+            .map(|action| {
+                let active = self.active.iter().find(|e| e.binding.action == action);
+                let failure = self.failures.iter().find(|(b, _)| b.action == action);
+                match (active, failure) {
+                    (Some(entry), Some((binding, conflict))) => {
+                        HotkeyStatus::registered(entry.binding.clone())
+                            .with_rejected(binding.combo.clone(), conflict.clone())
+                    }
+                    (Some(entry), None) => HotkeyStatus::registered(entry.binding.clone()),
+                    (None, Some((binding, conflict))) => {
+                        HotkeyStatus::failed(binding.clone(), conflict.clone())
+                    }
+                    (None, None) => HotkeyStatus::unbound(action),
+                }
+            })
+            .collect()
+    }
+
+    /// Registra las asignaciones de la config al arrancar. Los fallos quedan
+    /// pendientes de avisar hasta `take_startup_failures`
+    pub fn bind_startup(&mut self, bindings: Vec<HotkeyBinding>) {
+        for binding in bindings {
+            if let Err(e) = self.bind(binding.action, binding.combo.clone()) {
+                tracing::error!("Failed to register hotkey {}: {}", binding.combo, e);
+                self.unreported.push(binding.action);
+            }
+        }
+    }
+
+    /// Estado de las acciones que fallaron al arrancar, solo la primera vez
+    /// que se pide. Las que ya se han reasignado con exito no salen
+    pub fn take_startup_failures(&mut self) -> Vec<HotkeyStatus> {
+        let unreported = std::mem::take(&mut self.unreported);
+        self.statuses()
+            .into_iter()
+            .filter(|status| unreported.contains(&status.action) && status.conflict.is_some())
+            .collect()
+    }
+
+    /// Procesa un evento de global-hotkey (solo al pulsar, no al soltar).
+    /// Devuelve la accion si completa una secuencia; tras un leader
+    /// registra solo los posibles siguientes pasos hasta que se pulsa uno o
+    /// vence el plazo. Las demas teclas no se capturan: llegan a la app sin
+    /// cancelar la secuencia.
+    pub fn handle_event(
+        &mut self,
+        event: &GlobalHotKeyEvent,
+        now: Instant,
+    ) -> Option<HotkeyAction> {
+        if event.state != HotKeyState::Pressed {
+            return None;
+        }
+        let hotkey = self
+            .active
+            .iter()
+            .map(|entry| entry.leader())
+            .chain(self.follow_ups.iter().copied())
+            .find(|hotkey| hotkey.id() == event.id)?;
+
+        let outcome = self.matcher.press(hotkey, now);
+        self.release_follow_ups();
+
+        match outcome {
+            SequenceOutcome::Dispatch(action) => Some(action),
+            SequenceOutcome::Armed(expecting) => {
+                self.grab_follow_ups(expecting);
+                None
+            }
+            SequenceOutcome::Cancelled | SequenceOutcome::Ignored => None,
+        }
+    }
+
+    /// Plazo de la secuencia a medias, si la hay
+    pub fn deadline(&self) -> Option<Instant> {
+        self.matcher.deadline()
+    }
+
+    /// Descarta la secuencia a medias si vencio el plazo
+    pub fn expire(&mut self, now: Instant) {
+        if self.matcher.expire(now) {
+            tracing::debug!("Hotkey sequence timed out");
+            self.release_follow_ups();
+        }
+    }
+
+    fn is_leader(&self, hotkey: HotKey) -> bool {
+        self.active
+            .iter()
+            .any(|entry| entry.leader().id() == hotkey.id())
+    }
+
+    fn reload_sequences(&mut self) {
+        let sequences = self
+            .active
+            .iter()
+            .map(|entry| (entry.steps.clone(), entry.binding.action))
+            .collect();
+        self.matcher.set_sequences(sequences);
+    }
+
+    /// Registra los siguientes pasos; si el SO rechaza alguno, esa
+    /// secuencia no se puede completar pero el resto si. El backend hace el
+    /// registro en el hilo principal aunque esto corra en el hilo de eventos
+    fn grab_follow_ups(&mut self, keys: Vec<HotKey>) {
+        for key in keys {
+            if self.is_leader(key) || self.follow_ups.iter().any(|k| k.id() == key.id()) {
+                continue;
+            }
+            match self.backend.register(key) {
+                Ok(()) => self.follow_ups.push(key),
+                Err(message) => {
+                    tracing::warn!("Failed to grab sequence key {}: {}", key, message)
+                }
+            }
+        }
     }
 
-    // Verifica si el servicio esta activo
-    #[allow(dead_code)]
-    pub fn is_active(&self) -> bool {
-        self.is_active
+    fn release_follow_ups(&mut self) {
+        for key in std::mem::take(&mut self.follow_ups) {
+            if let Err(e) = self.backend.unregister(key) {
+                tracing::warn!("Failed to release sequence key {}: {}", key, e);
+            }
+        }
     }
 }
 
@@ -79,136 +426,443 @@ impl Default for HotkeyService {
     }
 }
 
-/// Parsea una cadena estilo "Cmd+Shift+J" a HotKey
-fn parse_hotkey(input: &str) -> Result<HotKey> {
+/// Motivo por el que el sistema se reserva el hotkey, si lo hace
+fn reserved_reason(hotkey: &HotKey) -> Option<&'static str> {
+    RESERVED_COMBOS
+        .iter()
+        .find(|(combo, _)| {
+            KeyCombo::parse(combo)
+                .map(|combo| to_hotkey(&combo).id() == hotkey.id())
+                .unwrap_or(false)
+        })
+        .map(|(_, reason)| *reason)
+}
+
+/// global-hotkey no distingue el lado de los modificadores: "RightShift"
+/// se registra como cualquier Shift
+fn to_hotkey(combo: &KeyCombo) -> HotKey {
     let mut mods = Modifiers::empty();
-    let mut key: Option<Code> = None;
-
-    for part in input.split('+') {
-        match part.to_lowercase().as_str() {
-            "cmd" | "meta" => mods |= Modifiers::META,
-            "shift" => mods |= Modifiers::SHIFT,
-            "alt" | "option" => mods |= Modifiers::ALT,
-            "ctrl" | "control" => mods |= Modifiers::CONTROL,
-            k if k.len() == 1 => {
-                let ch = k.chars().next().unwrap().to_ascii_uppercase();
-                key = match ch {
-                    'A' => Some(Code::KeyA),
-                    'B' => Some(Code::KeyB),
-                    'C' => Some(Code::KeyC),
-                    'D' => Some(Code::KeyD),
-                    'E' => Some(Code::KeyE),
-                    'F' => Some(Code::KeyF),
-                    'G' => Some(Code::KeyG),
-                    'H' => Some(Code::KeyH),
-                    'I' => Some(Code::KeyI),
-                    'J' => Some(Code::KeyJ),
-                    'K' => Some(Code::KeyK),
-                    'L' => Some(Code::KeyL),
-                    'M' => Some(Code::KeyM),
-                    'N' => Some(Code::KeyN),
-                    'O' => Some(Code::KeyO),
-                    'P' => Some(Code::KeyP),
-                    'Q' => Some(Code::KeyQ),
-                    'R' => Some(Code::KeyR),
-                    'S' => Some(Code::KeyS),
-                    'T' => Some(Code::KeyT),
-                    'U' => Some(Code::KeyU),
-                    'V' => Some(Code::KeyV),
-                    'W' => Some(Code::KeyW),
-                    'X' => Some(Code::KeyX),
-                    'Y' => Some(Code::KeyY),
-                    'Z' => Some(Code::KeyZ),
-                    _ => None,
-                };
-            }
-            _ => {}
+    for modifier in combo.modifiers() {
+        if modifier.side != KeySide::Either {
+            tracing::debug!(
+                "Hotkey {}: side-specific modifier registered as either side",
+                combo
+            );
         }
+        mods |= match modifier.key {
+            ModifierKey::Control => Modifiers::CONTROL,
+            ModifierKey::Alt => Modifiers::ALT,
+            ModifierKey::Shift => Modifiers::SHIFT,
+            // HotKey guarda Cmd/Win como SUPER
+            ModifierKey::Meta => Modifiers::SUPER,
+        };
     }
 
-    let key = key.ok_or_else(|| AppError::Hotkey("Invalid hotkey: no key found".to_string()))?;
-
     // Si no hay modifiers, pasar None
     let mods_option = if mods.is_empty() { None } else { Some(mods) };
 
-    Ok(HotKey::new(mods_option, key))
+    HotKey::new(mods_option, combo.key())
 }
 
-/// Parsea y devuelve los modifiers y key por separado (para testing)
 #[cfg(test)]
-fn parse_hotkey_parts(input: &str) -> Result<(Modifiers, Code)> {
-    let mut mods = Modifiers::empty();
-    let mut key: Option<Code> = None;
-
-    for part in input.split('+') {
-        match part.to_lowercase().as_str() {
-            "cmd" | "meta" => mods |= Modifiers::META,
-            "shift" => mods |= Modifiers::SHIFT,
-            "alt" | "option" => mods |= Modifiers::ALT,
-            "ctrl" | "control" => mods |= Modifiers::CONTROL,
-            k if k.len() == 1 => {
-                let ch = k.chars().next().unwrap().to_ascii_uppercase();
-                key = match ch {
-                    'A' => Some(Code::KeyA),
-                    'J' => Some(Code::KeyJ),
-                    'K' => Some(Code::KeyK),
-                    _ => None,
-                };
+mod tests {
+    use super::*;
+    use global_hotkey::hotkey::Code;
+    use std::sync::{Arc, Mutex};
+
+    fn parse_hotkey(input: &str) -> HotKey {
+        to_hotkey(&KeyCombo::parse(input).unwrap())
+    }
+
+    fn combo(input: &str) -> KeySequence {
+        KeySequence::parse(input).unwrap()
+    }
+
+    /// Backend en memoria que puede rechazar combos concretos
+    #[derive(Clone, Default)]
+    struct FakeBackend {
+        registered: Arc<Mutex<Vec<u32>>>,
+        rejected: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl FakeBackend {
+        fn reject(&self, input: &str) {
+            self.rejected.lock().unwrap().push(parse_hotkey(input).id());
+        }
+
+        fn is_registered(&self, input: &str) -> bool {
+            self.registered
+                .lock()
+                .unwrap()
+                .contains(&parse_hotkey(input).id())
+        }
+    }
+
+    impl HotkeyBackend for FakeBackend {
+        fn register(&mut self, hotkey: HotKey) -> std::result::Result<(), String> {
+            if self.rejected.lock().unwrap().contains(&hotkey.id()) {
+                return Err("already taken".to_string());
             }
-            _ => {}
+            self.registered.lock().unwrap().push(hotkey.id());
+            Ok(())
+        }
+
+        fn unregister(&mut self, hotkey: HotKey) -> Result<()> {
+            self.registered
+                .lock()
+                .unwrap()
+                .retain(|id| *id != hotkey.id());
+            Ok(())
         }
     }
 
-    let key = key.ok_or_else(|| AppError::Hotkey("Invalid hotkey".to_string()))?;
-    Ok((mods, key))
-}
+    fn service() -> (HotkeyService, FakeBackend) {
+        let backend = FakeBackend::default();
+        (
+            HotkeyService::with_backend(Box::new(backend.clone())),
+            backend,
+        )
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn pressed(input: &str) -> GlobalHotKeyEvent {
+        GlobalHotKeyEvent {
+            id: parse_hotkey(input).id(),
+            state: HotKeyState::Pressed,
+        }
+    }
+
+    fn press(hotkeys: &mut HotkeyService, input: &str) -> Option<HotkeyAction> {
+        hotkeys.handle_event(&pressed(input), Instant::now())
+    }
 
     #[test]
     fn test_parse_hotkey_cmd_shift_j() {
-        // Test the parsing logic directly, not the HotKey struct internals
-        let (mods, key) = parse_hotkey_parts("Cmd+Shift+J").unwrap();
-        assert!(mods.contains(Modifiers::META));
-        assert!(mods.contains(Modifiers::SHIFT));
-        assert_eq!(key, Code::KeyJ);
+        let hotkey = parse_hotkey("Cmd+Shift+J");
+        assert!(hotkey.mods.contains(Modifiers::SUPER));
+        assert!(hotkey.mods.contains(Modifiers::SHIFT));
+        assert_eq!(hotkey.key, Code::KeyJ);
     }
 
     #[test]
     fn test_parse_hotkey_ctrl_alt_a() {
-        let (mods, key) = parse_hotkey_parts("Ctrl+Alt+A").unwrap();
-        assert!(mods.contains(Modifiers::CONTROL));
-        assert!(mods.contains(Modifiers::ALT));
-        assert_eq!(key, Code::KeyA);
+        let hotkey = parse_hotkey("Ctrl+Alt+A");
+        assert!(hotkey.mods.contains(Modifiers::CONTROL));
+        assert!(hotkey.mods.contains(Modifiers::ALT));
+        assert_eq!(hotkey.key, Code::KeyA);
     }
 
     #[test]
-    fn test_parse_hotkey_single_modifier() {
-        let (mods, key) = parse_hotkey_parts("Cmd+K").unwrap();
-        assert!(mods.contains(Modifiers::META));
-        assert!(!mods.contains(Modifiers::SHIFT));
-        assert_eq!(key, Code::KeyK);
+    fn test_parse_hotkey_side_specific_modifier() {
+        let hotkey = parse_hotkey("RightShift+F5");
+        assert_eq!(hotkey.mods, Modifiers::SHIFT);
+        assert_eq!(hotkey.key, Code::F5);
     }
 
     #[test]
-    fn test_parse_hotkey_invalid() {
-        let result = parse_hotkey("Invalid");
-        assert!(result.is_err());
+    fn test_parse_hotkey_no_modifier() {
+        // Single key without modifiers should still work
+        assert!(parse_hotkey("J").mods.is_empty());
     }
 
     #[test]
-    fn test_parse_hotkey_creates_valid_hotkey() {
-        // Just verify that parse_hotkey doesn't panic and returns Ok
-        let result = parse_hotkey("Cmd+Shift+J");
-        assert!(result.is_ok());
+    fn test_global_backend_goes_through_main_thread_runner() {
+        let mut backend = GlobalHotkeyBackend::new(Box::new(|_task| {
+            Err(AppError::Hotkey("no main thread".to_string()))
+        }));
+        let error = backend.register(parse_hotkey("Cmd+J")).unwrap_err();
+        assert!(error.contains("no main thread"));
+
+        // Sin backend instalado no se registra nada
+        let mut hotkeys = HotkeyService::new();
+        assert!(hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .is_err());
     }
 
     #[test]
-    fn test_parse_hotkey_no_modifier() {
-        // Single key without modifiers should still work
-        let result = parse_hotkey("J");
-        assert!(result.is_ok());
+    fn test_routes_events_to_actions() {
+        let (mut hotkeys, _) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+        hotkeys
+            .bind(HotkeyAction::ScrollMode, combo("Cmd+Alt+J"))
+            .unwrap();
+
+        assert_eq!(
+            press(&mut hotkeys, "Cmd+J"),
+            Some(HotkeyAction::ActivateHints)
+        );
+        assert_eq!(
+            press(&mut hotkeys, "Alt+Cmd+J"),
+            Some(HotkeyAction::ScrollMode)
+        );
+        assert_eq!(press(&mut hotkeys, "Cmd+K"), None);
+
+        let released = GlobalHotKeyEvent {
+            state: HotKeyState::Released,
+            ..pressed("Cmd+J")
+        };
+        assert_eq!(hotkeys.handle_event(&released, Instant::now()), None);
+    }
+
+    #[test]
+    fn test_rebinding_replaces_previous_combo() {
+        let (mut hotkeys, backend) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+K"))
+            .unwrap();
+
+        assert!(!backend.is_registered("Cmd+J"));
+        assert!(backend.is_registered("Cmd+K"));
+        assert_eq!(backend.registered.lock().unwrap().len(), 1);
+        assert_eq!(press(&mut hotkeys, "Cmd+J"), None);
+    }
+
+    #[test]
+    fn test_failed_rebinding_keeps_previous_combo() {
+        let (mut hotkeys, backend) = service();
+        backend.reject("Cmd+K");
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+
+        assert!(hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+K"))
+            .is_err());
+        assert!(backend.is_registered("Cmd+J"));
+        assert_eq!(
+            press(&mut hotkeys, "Cmd+J"),
+            Some(HotkeyAction::ActivateHints)
+        );
+    }
+
+    #[test]
+    fn test_combo_cannot_serve_two_actions() {
+        let (mut hotkeys, _) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+        assert!(hotkeys
+            .bind(HotkeyAction::ScrollMode, combo("Cmd+J"))
+            .is_err());
+
+        hotkeys.unbind(HotkeyAction::ActivateHints).unwrap();
+        assert!(hotkeys
+            .bind(HotkeyAction::ScrollMode, combo("Cmd+J"))
+            .is_ok());
     }
-}
\ No newline at end of file
+
+    fn conflict_of(result: Result<()>) -> HotkeyConflict {
+        match result {
+            Err(AppError::HotkeyConflict { conflict, .. }) => conflict,
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_conflicts_are_classified() {
+        let (mut hotkeys, backend) = service();
+        backend.reject("Cmd+K");
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+
+        assert_eq!(
+            conflict_of(hotkeys.bind(HotkeyAction::ScrollMode, combo("Cmd+J"))),
+            HotkeyConflict::AlreadyBound {
+                action: HotkeyAction::ActivateHints
+            }
+        );
+        assert_eq!(
+            conflict_of(hotkeys.bind(HotkeyAction::ScrollMode, combo("Cmd+K"))),
+            HotkeyConflict::RejectedByOs {
+                message: "already taken".to_string()
+            }
+        );
+
+        let (reserved, reason) = RESERVED_COMBOS[0];
+        assert_eq!(
+            conflict_of(hotkeys.bind(HotkeyAction::ScrollMode, combo(reserved))),
+            HotkeyConflict::Reserved { reason }
+        );
+        assert!(!backend.is_registered(reserved));
+    }
+
+    #[test]
+    fn test_statuses_report_failed_and_unbound_actions() {
+        let (mut hotkeys, backend) = service();
+        backend.reject("Cmd+K");
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+        let _ = hotkeys.bind(HotkeyAction::RightClickHints, combo("Cmd+K"));
+
+        let statuses = hotkeys.statuses();
+        assert_eq!(statuses.len(), HotkeyAction::ALL.len());
+        assert!(statuses[0].registered);
+        assert!(!statuses[1].registered);
+        assert_eq!(statuses[1].combo, Some(combo("Cmd+K")));
+        assert!(matches!(
+            statuses[1].conflict,
+            Some(HotkeyConflict::RejectedByOs { .. })
+        ));
+        assert_eq!(statuses[2], HotkeyStatus::unbound(HotkeyAction::ScrollMode));
+
+        // Un rebinding fallido no tapa el combo que sigue activo, pero queda
+        // anotado
+        let _ = hotkeys.bind(HotkeyAction::ActivateHints, combo("Cmd+K"));
+        let status = &hotkeys.statuses()[0];
+        assert!(status.registered);
+        assert_eq!(status.combo, Some(combo("Cmd+J")));
+        assert_eq!(status.rejected_combo, Some(combo("Cmd+K")));
+        assert!(matches!(
+            status.conflict,
+            Some(HotkeyConflict::RejectedByOs { .. })
+        ));
+
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+I"))
+            .unwrap();
+        assert_eq!(hotkeys.statuses()[0].conflict, None);
+
+        hotkeys
+            .bind(HotkeyAction::RightClickHints, combo("Cmd+U"))
+            .unwrap();
+        assert!(hotkeys.statuses()[1].registered);
+    }
+
+    #[test]
+    fn test_startup_failures_are_reported_once() {
+        let (mut hotkeys, backend) = service();
+        backend.reject("Cmd+K");
+        backend.reject("Cmd+L");
+        hotkeys.bind_startup(vec![
+            HotkeyBinding::new(HotkeyAction::ActivateHints, combo("Cmd+J")),
+            HotkeyBinding::new(HotkeyAction::RightClickHints, combo("Cmd+K")),
+            HotkeyBinding::new(HotkeyAction::ScrollMode, combo("Cmd+L")),
+        ]);
+        assert!(backend.is_registered("Cmd+J"));
+
+        // Reasignada antes de que el frontend escuche: ya no es un fallo
+        hotkeys
+            .bind(HotkeyAction::ScrollMode, combo("Cmd+U"))
+            .unwrap();
+
+        let failures = hotkeys.take_startup_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].action, HotkeyAction::RightClickHints);
+        assert_eq!(failures[0].combo, Some(combo("Cmd+K")));
+        assert!(matches!(
+            failures[0].conflict,
+            Some(HotkeyConflict::RejectedByOs { .. })
+        ));
+        assert!(hotkeys.take_startup_failures().is_empty());
+    }
+
+    #[test]
+    fn test_sequences_share_leader() {
+        let (mut hotkeys, backend) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J F"))
+            .unwrap();
+        hotkeys
+            .bind(HotkeyAction::ScrollMode, combo("Cmd+J S"))
+            .unwrap();
+
+        // Solo el leader queda registrado
+        assert_eq!(
+            *backend.registered.lock().unwrap(),
+            vec![parse_hotkey("Cmd+J").id()]
+        );
+
+        hotkeys.unbind(HotkeyAction::ActivateHints).unwrap();
+        assert!(backend.is_registered("Cmd+J"));
+        hotkeys.unbind(HotkeyAction::ScrollMode).unwrap();
+        assert!(!backend.is_registered("Cmd+J"));
+    }
+
+    #[test]
+    fn test_sequence_grabs_next_keys_while_armed() {
+        let (mut hotkeys, backend) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J F"))
+            .unwrap();
+        hotkeys
+            .bind(HotkeyAction::ScrollMode, combo("Cmd+J S"))
+            .unwrap();
+
+        assert_eq!(press(&mut hotkeys, "Cmd+J"), None);
+        assert!(backend.is_registered("F"));
+        assert!(backend.is_registered("S"));
+
+        assert_eq!(press(&mut hotkeys, "S"), Some(HotkeyAction::ScrollMode));
+        assert!(!backend.is_registered("F"));
+        assert!(!backend.is_registered("S"));
+        assert!(backend.is_registered("Cmd+J"));
+    }
+
+    #[test]
+    fn test_other_keys_are_not_grabbed_while_armed() {
+        let (mut hotkeys, backend) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J F"))
+            .unwrap();
+
+        // Solo el siguiente paso: Esc, letras y digitos siguen llegando a la
+        // app y la secuencia espera hasta el plazo
+        assert_eq!(press(&mut hotkeys, "Cmd+J"), None);
+        assert_eq!(
+            *backend.registered.lock().unwrap(),
+            vec![parse_hotkey("Cmd+J").id(), parse_hotkey("F").id()]
+        );
+        for other in ["Esc", "X", "7"] {
+            assert!(!backend.is_registered(other));
+        }
+
+        // Repetir el leader reinicia la secuencia
+        let deadline = hotkeys.deadline();
+        let later = Instant::now() + SEQUENCE_TIMEOUT / 2;
+        assert_eq!(hotkeys.handle_event(&pressed("Cmd+J"), later), None);
+        assert!(hotkeys.deadline() > deadline);
+        assert_eq!(press(&mut hotkeys, "F"), Some(HotkeyAction::ActivateHints));
+        assert_eq!(
+            *backend.registered.lock().unwrap(),
+            vec![parse_hotkey("Cmd+J").id()]
+        );
+    }
+
+    #[test]
+    fn test_sequence_timeout_releases_next_keys() {
+        let (mut hotkeys, backend) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J F"))
+            .unwrap();
+
+        let now = Instant::now();
+        hotkeys.handle_event(&pressed("Cmd+J"), now);
+        assert_eq!(hotkeys.deadline(), Some(now + SEQUENCE_TIMEOUT));
+
+        hotkeys.expire(now + SEQUENCE_TIMEOUT);
+        assert_eq!(hotkeys.deadline(), None);
+        assert!(!backend.is_registered("F"));
+    }
+
+    #[test]
+    fn test_sequence_cannot_extend_another_action() {
+        let (mut hotkeys, _) = service();
+        hotkeys
+            .bind(HotkeyAction::ActivateHints, combo("Cmd+J"))
+            .unwrap();
+
+        assert_eq!(
+            conflict_of(hotkeys.bind(HotkeyAction::ScrollMode, combo("Cmd+J S"))),
+            HotkeyConflict::AlreadyBound {
+                action: HotkeyAction::ActivateHints
+            }
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::models::{ElementTree, FilterDecision, FilterExplanation, InspectedNode, InspectedTree};
+use crate::services::clickable_stages::ClickableStages;
+use crate::services::element_filter_service::ElementFilter;
+use crate::Result;
+
+/// Atributos nativos de un nodo: nombre -> valor legible
+pub type NodeAttributes = BTreeMap<String, Option<String>>;
+
+/// Construye volcados del arbol de accesibilidad con la decision del filtro
+/// para cada nodo
+pub struct TreeInspector {
+    stages: ClickableStages,
+    filter: ElementFilter,
+}
+
+impl TreeInspector {
+    pub fn new() -> Self {
+        Self::with_stages(ClickableStages::new(Vec::new()))
+    }
+
+    /// Inspector que aplica las mismas etapas que el escaneo (ver
+    /// `AccessibilityService::clickable_stages`)
+    pub fn with_stages(stages: ClickableStages) -> Self {
+        Self {
+            stages,
+            filter: ElementFilter::new(),
+        }
+    }
+
+    /// Combina el arbol escaneado con los atributos leidos de cada nodo
+    pub fn inspect(
+        &self,
+        tree: &ElementTree,
+        mut attributes: Vec<NodeAttributes>,
+    ) -> InspectedTree {
+        let decisions = self.decide(tree);
+        attributes.resize_with(tree.len(), NodeAttributes::new);
+
+        let nodes = tree
+            .nodes()
+            .iter()
+            .zip(attributes)
+            .zip(decisions)
+            .enumerate()
+            .map(|(index, ((node, attributes), decision))| InspectedNode {
+                element: node.element.clone(),
+                parent: node.parent,
+                children: node.children.clone(),
+                depth: node.depth,
+                path: tree.path_key(index),
+                attributes,
+                decision,
+            })
+            .collect();
+
+        InspectedTree { nodes }
+    }
+
+    /// Explica, para cada nodo clickable y en orden BFS, que etapa lo
+    /// conservo o descarto: las del escaneo y despues el filtro
+    pub fn explain_clickable(&self, tree: &ElementTree) -> Vec<FilterExplanation> {
+        let (kept, hidden) = self.stages.explain(tree);
+
+        let mut explanations: HashMap<String, FilterExplanation> = self
+            .filter
+            .explain(kept)
+            .into_iter()
+            .chain(hidden)
+            .map(|explanation| (explanation.element.id.clone(), explanation))
+            .collect();
+
+        tree.clickable()
+            .filter_map(|(_, node)| explanations.remove(&node.element.id))
+            .collect()
+    }
+
+    /// Calcula, para cada nodo del arbol, si llegaria a recibir hint
+    pub fn decide(&self, tree: &ElementTree) -> Vec<FilterDecision> {
+        let mut decisions: HashMap<String, FilterDecision> = self
+            .explain_clickable(tree)
+            .into_iter()
+            .map(|explanation| (explanation.element.id, explanation.decision))
+            .collect();
+
+        tree.nodes()
+            .iter()
+            .map(|node| {
+                let element = &node.element;
+                if !element.role.is_clickable() {
+                    return FilterDecision::dropped(
+                        "role",
+                        format!("role {} is not clickable", element.raw_role),
+                    );
+                }
+                decisions
+                    .remove(&element.id)
+                    .unwrap_or(FilterDecision::Kept)
+            })
+            .collect()
+    }
+}
+
+impl Default for TreeInspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Guarda un volcado en disco como JSON legible
+pub fn save_dump(tree: &InspectedTree, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(tree)?;
+    std::fs::write(path, json)?;
+    tracing::info!(
+        "Saved tree dump with {} nodes to {:?}",
+        tree.nodes.len(),
+        path
+    );
+    Ok(())
+}
+
+/// Carga un volcado previamente guardado (ej: como fixture de tests)
+#[cfg(test)]
+pub fn load_dump(path: &Path) -> Result<InspectedTree> {
+    let json = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/inspected_tree.json");
+
+    fn fixture() -> InspectedTree {
+        serde_json::from_str(FIXTURE).expect("fixture should deserialize")
+    }
+
+    #[test]
+    fn test_fixture_rebuilds_tree() {
+        let dump = fixture();
+        let tree = dump.to_element_tree();
+
+        assert_eq!(tree.len(), dump.nodes.len());
+        for (index, node) in dump.nodes.iter().enumerate() {
+            assert_eq!(tree.path_key(index), node.path);
+            assert_eq!(tree.node(index).unwrap().children, node.children);
+        }
+    }
+
+    #[test]
+    fn test_fixture_decisions_are_reproducible() {
+        let dump = fixture();
+        let tree = dump.to_element_tree();
+
+        let decisions = TreeInspector::new().decide(&tree);
+        let expected: Vec<FilterDecision> = dump
+            .nodes
+            .iter()
+            .map(|node| node.decision.clone())
+            .collect();
+
+        assert_eq!(decisions, expected);
+    }
+
+    #[test]
+    fn test_inspect_marks_non_clickable_roles() {
+        let tree = fixture().to_element_tree();
+        let inspected = TreeInspector::new().inspect(&tree, Vec::new());
+
+        let window = &inspected.nodes[0];
+        assert_eq!(window.path, "");
+        assert!(matches!(
+            &window.decision,
+            FilterDecision::Dropped { rule, .. } if rule == "role"
+        ));
+        assert!(window.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dump = fixture();
+        let path = std::env::temp_dir().join(format!("pltm-dump-{}.json", std::process::id()));
+
+        save_dump(&dump, &path).unwrap();
+        let loaded = load_dump(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.nodes.len(), dump.nodes.len());
+        assert_eq!(loaded.nodes[2].attributes, dump.nodes[2].attributes);
+    }
+}
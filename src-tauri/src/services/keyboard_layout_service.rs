@@ -0,0 +1,93 @@
+use crate::models::config::AppConfig;
+use crate::models::{KeyboardLayout, KeymapChars};
+
+/// Teclado activo del sistema: el layout con preset que mas se le parece y
+/// lo que escribe cada tecla. En macOS se lee con Text Input Sources, que
+/// solo se puede llamar desde el hilo principal
+#[derive(Debug, Clone, Default)]
+pub struct SystemKeyboard {
+    pub layout: Option<KeyboardLayout>,
+    pub keymap: Option<KeymapChars>,
+}
+
+/// Teclado activo; lo que no se pudo detectar queda en `None`
+#[cfg(target_os = "linux")]
+pub fn detect_keyboard() -> SystemKeyboard {
+    use crate::platform::linux::keyboard_layout::{current_keymap, current_layout};
+
+    SystemKeyboard {
+        layout: current_layout()
+            .map_err(|e| tracing::warn!("Failed to detect keyboard layout: {}", e))
+            .ok(),
+        keymap: current_keymap()
+            .map_err(|e| tracing::warn!("Failed to read keyboard keymap: {}", e))
+            .ok(),
+    }
+}
+
+/// Teclado activo; lo que no se pudo detectar queda en `None`. Llamar desde
+/// el hilo principal
+#[cfg(target_os = "macos")]
+pub fn detect_keyboard() -> SystemKeyboard {
+    use crate::platform::macos::keyboard_layout::{current_keymap, current_layout};
+
+    SystemKeyboard {
+        layout: current_layout(),
+        keymap: current_keymap(),
+    }
+}
+
+/// Teclado activo; lo que no se pudo detectar queda en `None`
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn detect_keyboard() -> SystemKeyboard {
+    SystemKeyboard::default()
+}
+
+/// Layout para los hints: el de la config o, si no hay, el detectado
+pub fn effective_layout(config: &AppConfig, system: &SystemKeyboard) -> KeyboardLayout {
+    config
+        .keyboard_layout
+        .or(system.layout)
+        .unwrap_or_default()
+}
+
+/// Caracteres con los que se generan y escriben los hints. Con un layout
+/// fijado en la config no se usa el keymap del sistema: es otro teclado
+pub fn hint_charset(config: &AppConfig, system: &SystemKeyboard) -> String {
+    let layout = effective_layout(config, system);
+    let keymap = system
+        .keymap
+        .as_ref()
+        .filter(|_| config.keyboard_layout.is_none());
+    let charset = config
+        .hint_charset
+        .resolve(&config.hint_chars, layout, keymap);
+    tracing::debug!("Hint charset for {:?}: {}", layout, charset);
+    charset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::HintCharset;
+    use keyboard_types::Code;
+
+    #[test]
+    fn test_configured_layout_ignores_system_keymap() {
+        let system = SystemKeyboard {
+            layout: Some(KeyboardLayout::Qwerty),
+            keymap: Some(KeymapChars::translate(|code| {
+                (code == Code::KeyA).then_some('ф')
+            })),
+        };
+        let mut config = AppConfig {
+            hint_charset: HintCharset::Physical,
+            hint_chars: "as".to_string(),
+            ..AppConfig::default()
+        };
+        assert_eq!(hint_charset(&config, &system), "фs");
+
+        config.keyboard_layout = Some(KeyboardLayout::Dvorak);
+        assert_eq!(hint_charset(&config, &system), "ao");
+    }
+}
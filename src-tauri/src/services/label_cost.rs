@@ -0,0 +1,166 @@
+use crate::models::KeyboardLayout;
+
+// Pesos del modelo, en unidades relativas (un indice en la fila central
+// cuesta 1)
+
+/// Coste de cada dedo: menique, anular, corazon, indice
+const FINGER_COST: [f64; 4] = [2.0, 1.5, 1.1, 1.0];
+/// Coste extra por fila: arriba, central, abajo
+const ROW_COST: [f64; 3] = [0.6, 0.0, 0.9];
+/// Estirar el indice a la columna interior
+const INNER_COLUMN_COST: f64 = 0.5;
+/// Caracter que no esta en el layout (ej: digitos en un charset custom)
+const UNKNOWN_KEY_COST: f64 = 3.0;
+
+/// Dos teclas distintas seguidas con el mismo dedo: lo mas lento
+const SAME_FINGER_COST: f64 = 2.5;
+/// La misma tecla dos veces
+const REPEATED_KEY_COST: f64 = 0.3;
+/// Dos teclas con la misma mano (alternar manos es mas rapido)
+const SAME_HAND_COST: f64 = 0.4;
+/// Por cada fila que salta la misma mano entre dos teclas
+const ROW_JUMP_COST: f64 = 0.6;
+
+/// Grupos de caracteres que se confunden de un vistazo en un label pequeno
+const CONFUSABLE: &[&[char]] = &[
+    &['i', 'l', 'j', '1', '!'],
+    &['o', '0', 'q'],
+    &['n', 'm', 'h'],
+    &['u', 'v'],
+    &['c', 'e'],
+    &['b', 'd', 'p'],
+    &[',', '.'],
+    &[';', ':'],
+];
+
+/// Coste de teclear un label en un layout: por tecla segun dedo, fila y
+/// columna, y por cada par de teclas seguidas segun si repiten dedo,
+/// alternan mano o saltan filas
+#[derive(Debug, Clone, Copy)]
+pub struct TypingCostModel {
+    layout: KeyboardLayout,
+}
+
+impl TypingCostModel {
+    pub fn new(layout: KeyboardLayout) -> Self {
+        Self { layout }
+    }
+
+    /// Coste de una tecla aislada
+    pub fn key_cost(&self, ch: char) -> f64 {
+        match self.layout.key_of(ch) {
+            Some(key) => {
+                let inner = if key.is_inner_column() {
+                    INNER_COLUMN_COST
+                } else {
+                    0.0
+                };
+                FINGER_COST[key.finger()] + ROW_COST[key.row] + inner
+            }
+            None => UNKNOWN_KEY_COST,
+        }
+    }
+
+    /// Coste de un label: sus teclas mas cada transicion entre ellas
+    pub fn label_cost(&self, label: &str) -> f64 {
+        let keys: Vec<char> = label.chars().collect();
+        let keys_cost: f64 = keys.iter().map(|ch| self.key_cost(*ch)).sum();
+        let transitions_cost: f64 = keys
+            .windows(2)
+            .map(|pair| self.transition_cost(pair[0], pair[1]))
+            .sum();
+        keys_cost + transitions_cost
+    }
+
+    fn transition_cost(&self, from: char, to: char) -> f64 {
+        let (Some(a), Some(b)) = (self.layout.key_of(from), self.layout.key_of(to)) else {
+            return 0.0;
+        };
+        if a == b {
+            return REPEATED_KEY_COST;
+        }
+        if a.hand() != b.hand() {
+            return 0.0;
+        }
+
+        let same_finger = if a.finger() == b.finger() {
+            SAME_FINGER_COST
+        } else {
+            0.0
+        };
+        SAME_HAND_COST + same_finger + ROW_JUMP_COST * a.row.abs_diff(b.row) as f64
+    }
+}
+
+fn chars_look_alike(a: char, b: char) -> bool {
+    a == b
+        || CONFUSABLE
+            .iter()
+            .any(|group| group.contains(&a) && group.contains(&b))
+}
+
+/// Labels distintos que se pueden confundir de un vistazo: cada posicion
+/// igual o con caracteres parecidos ("il" y "ll"), o las mismas letras en
+/// otro orden ("as" y "sa")
+pub fn labels_look_alike(a: &str, b: &str) -> bool {
+    if a == b || a.chars().count() != b.chars().count() {
+        return false;
+    }
+
+    let similar = a
+        .chars()
+        .zip(b.chars())
+        .all(|(x, y)| chars_look_alike(x, y));
+
+    let mut sorted_a: Vec<char> = a.chars().collect();
+    let mut sorted_b: Vec<char> = b.chars().collect();
+    sorted_a.sort_unstable();
+    sorted_b.sort_unstable();
+
+    similar || sorted_a == sorted_b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_home_row_index_is_cheapest() {
+        let model = TypingCostModel::new(KeyboardLayout::Qwerty);
+        assert!(model.key_cost('f') < model.key_cost('d'));
+        assert!(model.key_cost('f') < model.key_cost('r'));
+        assert!(model.key_cost('f') < model.key_cost('g'));
+        assert!(model.key_cost('d') < model.key_cost('a'));
+        assert_eq!(model.key_cost('1'), UNKNOWN_KEY_COST);
+    }
+
+    #[test]
+    fn test_bigram_costs() {
+        let model = TypingCostModel::new(KeyboardLayout::Qwerty);
+
+        // Alternar manos gana a repetir mano, y repetir dedo es lo peor
+        assert!(model.label_cost("fj") < model.label_cost("fd"));
+        assert!(model.label_cost("fd") < model.label_cost("fg"));
+        assert!(model.label_cost("fd") < model.label_cost("fv"));
+        // Saltar de la fila de arriba a la de abajo con la misma mano
+        assert!(model.label_cost("ex") > model.label_cost("es"));
+    }
+
+    #[test]
+    fn test_costs_follow_the_layout() {
+        // "aoeu" es la fila central en Dvorak, no en QWERTY
+        let qwerty = TypingCostModel::new(KeyboardLayout::Qwerty);
+        let dvorak = TypingCostModel::new(KeyboardLayout::Dvorak);
+        assert!(dvorak.label_cost("ue") < qwerty.label_cost("ue"));
+    }
+
+    #[test]
+    fn test_confusable_labels() {
+        assert!(labels_look_alike("il", "ll"));
+        assert!(labels_look_alike("as", "sa"));
+        assert!(labels_look_alike("on", "qm"));
+        assert!(!labels_look_alike("as", "ad"));
+        assert!(!labels_look_alike("as", "as"));
+        assert!(!labels_look_alike("a", "as"));
+    }
+}
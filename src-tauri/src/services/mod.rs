@@ -1,11 +1,29 @@
 pub mod accessibility_service;
 pub mod click_service;
 pub mod hint_service;
+pub mod label_cost;
+pub mod keyboard_layout_service;
 pub mod hotkey_service;
+pub mod sequence_matcher;
+// Solo la usa la sesion de teclado de X11
+#[cfg(target_os = "linux")]
+pub mod navigation_input;
 pub mod window_manager;
 pub mod element_filter_service;
 pub mod spatial_index;
 pub mod pipeline;
+pub mod inspector_service;
+pub mod visibility_service;
+pub mod occlusion_service;
+pub mod click_point_service;
+pub mod clickable_stages;
+pub mod text_measure;
+pub mod hint_layout;
+pub mod hint_scene;
+// Solo lo usan los tests de imagen: aun no hay renderer que pinte buffers
+#[cfg(test)]
+pub mod raster_renderer;
+pub mod usage_store;
 
 pub use accessibility_service::AccessibilityService;
 pub use click_service::ClickService;
@@ -13,3 +31,5 @@ pub use hint_service::HintGenerator;
 pub use hotkey_service::HotkeyService;
 pub use window_manager::WindowManager;
 pub use element_filter_service::filter_elements;
+pub use inspector_service::TreeInspector;
+pub use usage_store::UsageStore;
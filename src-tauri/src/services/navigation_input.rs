@@ -0,0 +1,152 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Tecla ya traducida por la plataforma (XKB en Linux)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavKey {
+    Char(char),
+    Backspace,
+    Escape,
+}
+
+/// Por que termina una sesion de navegacion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    Escape,
+    Timeout,
+    // El prefijo dejo un solo hint
+    Selected,
+}
+
+/// Resultado de pulsar una tecla
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavEvent {
+    // Prefijo escrito hasta ahora, tras anadir o borrar una letra
+    Prefix(String),
+    Exit(ExitReason),
+    // Tecla fuera del charset de hints
+    Ignored,
+}
+
+/// Maquina de estados del teclado mientras se ven los hints: acumula el
+/// prefijo con las letras del charset y termina con Esc o tras `timeout`
+/// sin teclas validas. Pura: el tiempo llega en cada llamada.
+pub struct NavigationInput {
+    charset: Vec<char>,
+    prefix: String,
+    timeout: Duration,
+    deadline: Instant,
+}
+
+impl NavigationInput {
+    pub fn new(charset: &str, timeout: Duration, now: Instant) -> Self {
+        Self {
+            charset: charset.chars().flat_map(char::to_lowercase).collect(),
+            prefix: String::new(),
+            timeout,
+            deadline: now + timeout,
+        }
+    }
+
+    /// Procesa una tecla pulsada en `now`
+    pub fn press(&mut self, key: NavKey, now: Instant) -> NavEvent {
+        if let Some(reason) = self.expire(now) {
+            return NavEvent::Exit(reason);
+        }
+
+        match key {
+            NavKey::Escape => return NavEvent::Exit(ExitReason::Escape),
+            NavKey::Backspace => {
+                if self.prefix.pop().is_none() {
+                    return NavEvent::Ignored;
+                }
+            }
+            NavKey::Char(ch) => {
+                let ch = ch.to_lowercase().next().unwrap_or(ch);
+                if !self.charset.contains(&ch) {
+                    return NavEvent::Ignored;
+                }
+                self.prefix.push(ch);
+            }
+        }
+
+        self.deadline = now + self.timeout;
+        NavEvent::Prefix(self.prefix.clone())
+    }
+
+    /// `Timeout` si paso el plazo sin teclas validas
+    pub fn expire(&self, now: Instant) -> Option<ExitReason> {
+        (now >= self.deadline).then_some(ExitReason::Timeout)
+    }
+
+    /// Plazo para la siguiente tecla valida
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_builds_prefix_from_charset() {
+        let now = Instant::now();
+        let mut input = NavigationInput::new("asdf", TIMEOUT, now);
+
+        assert_eq!(
+            input.press(NavKey::Char('a'), now),
+            NavEvent::Prefix("a".to_string())
+        );
+        assert_eq!(
+            input.press(NavKey::Char('S'), now),
+            NavEvent::Prefix("as".to_string())
+        );
+        assert_eq!(input.press(NavKey::Char('x'), now), NavEvent::Ignored);
+        assert_eq!(
+            input.press(NavKey::Backspace, now),
+            NavEvent::Prefix("a".to_string())
+        );
+        assert_eq!(
+            input.press(NavKey::Backspace, now),
+            NavEvent::Prefix(String::new())
+        );
+        assert_eq!(input.press(NavKey::Backspace, now), NavEvent::Ignored);
+    }
+
+    #[test]
+    fn test_escape_exits() {
+        let now = Instant::now();
+        let mut input = NavigationInput::new("asdf", TIMEOUT, now);
+        input.press(NavKey::Char('a'), now);
+
+        assert_eq!(
+            input.press(NavKey::Escape, now),
+            NavEvent::Exit(ExitReason::Escape)
+        );
+    }
+
+    #[test]
+    fn test_timeout_restarts_with_each_valid_key() {
+        let start = Instant::now();
+        let mut input = NavigationInput::new("asdf", TIMEOUT, start);
+        assert_eq!(input.deadline(), start + TIMEOUT);
+
+        // Las teclas ignoradas no alargan la sesion
+        let later = start + Duration::from_secs(3);
+        input.press(NavKey::Char('x'), later);
+        assert_eq!(input.deadline(), start + TIMEOUT);
+
+        input.press(NavKey::Char('a'), later);
+        assert_eq!(input.deadline(), later + TIMEOUT);
+        assert_eq!(input.expire(start + TIMEOUT), None);
+        assert_eq!(input.expire(later + TIMEOUT), Some(ExitReason::Timeout));
+        assert_eq!(
+            input.press(NavKey::Char('s'), later + TIMEOUT),
+            NavEvent::Exit(ExitReason::Timeout)
+        );
+    }
+}
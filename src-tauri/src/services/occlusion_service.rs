@@ -0,0 +1,189 @@
+use crate::models::{
+    FilterDecision, FilterExplanation, LogicalRect, StackedWindow, UIElement, WindowStack,
+};
+
+/// Region visible de una ventana: su frame menos el de todas las ventanas
+/// que tiene delante. None si la ventana no esta en el apilado.
+pub fn visible_region(stack: &WindowStack, window_id: u32) -> Option<Vec<LogicalRect>> {
+    let position = stack.position(window_id)?;
+    let mut region = vec![stack.windows[position].frame];
+
+    for window in &stack.windows[..position] {
+        region = region
+            .iter()
+            .flat_map(|piece| piece.subtract(&window.frame))
+            .collect();
+    }
+
+    Some(region)
+}
+
+/// Descarta o recoloca los elementos tapados por otras ventanas.
+///
+/// Si el punto de click (centro de la parte visible) queda tapado pero parte
+/// del elemento sigue a la vista, el elemento se recorta al trozo visible
+/// mas grande; si esta tapado por completo se descarta.
+pub struct OcclusionFilter {
+    // Trozos disjuntos de la ventana objetivo que quedan a la vista
+    region: Vec<LogicalRect>,
+    // Ventanas delante de la objetivo que la solapan
+    occluders: Vec<StackedWindow>,
+}
+
+impl OcclusionFilter {
+    /// Filtro para los elementos de `window_id` (None si la ventana no esta
+    /// en el apilado y no se puede calcular la oclusion)
+    pub fn for_window(stack: &WindowStack, window_id: u32) -> Option<Self> {
+        let region = visible_region(stack, window_id)?;
+        let position = stack.position(window_id)?;
+        let frame = stack.windows[position].frame;
+
+        let occluders = stack.windows[..position]
+            .iter()
+            .filter(|window| window.frame.intersection(&frame).is_some())
+            .cloned()
+            .collect();
+
+        Some(Self { region, occluders })
+    }
+
+    /// Frames de las ventanas que tapan parte de la objetivo
+    pub fn occluder_frames(&self) -> Vec<LogicalRect> {
+        self.occluders.iter().map(|window| window.frame).collect()
+    }
+
+    /// Decision de oclusion para cada elemento, en el orden de entrada
+    pub fn explain(&self, elements: Vec<UIElement>) -> Vec<FilterExplanation> {
+        elements
+            .into_iter()
+            .map(|mut element| {
+                let decision = self.decide(&mut element);
+                FilterExplanation { element, decision }
+            })
+            .collect()
+    }
+
+    fn decide(&self, element: &mut UIElement) -> FilterDecision {
+        let visible = element.visible_frame();
+        let click_point = visible.center();
+
+        if self
+            .region
+            .iter()
+            .any(|piece| piece.contains_point(click_point))
+        {
+            return FilterDecision::Kept;
+        }
+
+        let uncovered = self
+            .region
+            .iter()
+            .filter_map(|piece| visible.intersection(piece))
+            .max_by(|a, b| a.area().total_cmp(&b.area()));
+
+        match uncovered {
+            Some(piece) => {
+                tracing::trace!("Relocating occluded click point of {}", element.id);
+                element.visible_rect = Some(piece);
+                FilterDecision::Kept
+            }
+            None => {
+                let covering = self
+                    .occluders
+                    .iter()
+                    .find(|window| window.frame.contains_point(click_point))
+                    .map_or("window".to_string(), |window| {
+                        format!("window {}", window.id)
+                    });
+                FilterDecision::dropped("occlusion", format!("covered by {}", covering))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Role;
+    use crate::services::clickable_stages::split_kept;
+
+    fn window(id: u32, x: f64, y: f64, w: f64, h: f64) -> StackedWindow {
+        StackedWindow {
+            id,
+            pid: None,
+            frame: LogicalRect::new(x, y, w, h),
+        }
+    }
+
+    fn button(id: &str, x: f64, y: f64, w: f64, h: f64) -> UIElement {
+        UIElement::new(
+            id.to_string(),
+            Role::Button,
+            None,
+            LogicalRect::new(x, y, w, h),
+        )
+    }
+
+    // Popover (2) delante de la ventana objetivo (1); la 3 queda detras
+    fn sample_stack() -> WindowStack {
+        WindowStack::new(vec![
+            window(2, 100.0, 100.0, 200.0, 100.0),
+            window(1, 0.0, 0.0, 800.0, 600.0),
+            window(3, 0.0, 0.0, 1440.0, 900.0),
+        ])
+    }
+
+    #[test]
+    fn test_visible_region_subtracts_windows_in_front() {
+        let stack = sample_stack();
+
+        let region = visible_region(&stack, 1).unwrap();
+        let area: f64 = region.iter().map(|piece| piece.area()).sum();
+        assert_eq!(area, 800.0 * 600.0 - 200.0 * 100.0);
+
+        // La ventana de delante no pierde nada; una desconocida no tiene region
+        assert_eq!(
+            visible_region(&stack, 2).unwrap(),
+            vec![stack.windows[0].frame]
+        );
+        assert_eq!(visible_region(&stack, 99), None);
+    }
+
+    #[test]
+    fn test_drops_fully_covered_elements() {
+        let filter = OcclusionFilter::for_window(&sample_stack(), 1).unwrap();
+        let explanations = filter.explain(vec![
+            button("free", 10.0, 10.0, 50.0, 20.0),
+            button("covered", 150.0, 150.0, 50.0, 20.0),
+        ]);
+
+        assert_eq!(explanations[0].decision, FilterDecision::Kept);
+        assert_eq!(explanations[0].element.visible_rect, None);
+        assert_eq!(
+            explanations[1].decision,
+            FilterDecision::dropped("occlusion", "covered by window 2")
+        );
+    }
+
+    #[test]
+    fn test_relocates_partially_covered_elements() {
+        let filter = OcclusionFilter::for_window(&sample_stack(), 1).unwrap();
+        // Centro en (110, 115), dentro del popover; asoma por arriba y por la izquierda
+        let (elements, _) =
+            split_kept(filter.explain(vec![button("half", 40.0, 95.0, 140.0, 40.0)]));
+
+        assert_eq!(elements.len(), 1);
+        let visible = elements[0].visible_frame();
+        assert_eq!(visible, LogicalRect::new(40.0, 100.0, 60.0, 35.0));
+        let popover = sample_stack().windows[0].frame;
+        assert!(!popover.contains_point(visible.center()));
+    }
+
+    #[test]
+    fn test_centre_visible_is_kept_untouched() {
+        let filter = OcclusionFilter::for_window(&sample_stack(), 1).unwrap();
+        let el = button("edge", 280.0, 150.0, 60.0, 20.0);
+        let (elements, _) = split_kept(filter.explain(vec![el.clone()]));
+        assert_eq!(elements[0].visible_frame(), el.frame);
+    }
+}
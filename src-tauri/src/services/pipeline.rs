@@ -68,7 +68,7 @@ impl Pipeline {
     pub async fn render(&mut self, _hints: Vec<Hint>) -> Result<()> {
         tracing::debug!("Pipeline: render stage");
         self.state = PipelineState::Rendering;
-        // TODO: Invocar OverlayRenderer.draw_hints()
+        // TODO: Invocar OverlayRenderer.apply() con el diff de HintScene
         self.state = PipelineState::Idle;
         Ok(())
     }
@@ -0,0 +1,610 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap, Stroke, Transform};
+use ttf_parser::OutlineBuilder;
+
+use crate::error::AppError;
+use crate::models::hint_style::{TYPED_ALPHA, UNIQUE_BORDER_EXTRA};
+use crate::models::{
+    Color, HintStyle, LabelState, LeaderLine, LogicalPoint, LogicalRect, SceneNode, SceneOp,
+    Screen, ScreenLayout,
+};
+use crate::services::hint_scene::HintScene;
+use crate::services::text_measure::{FontMeasure, TextMeasure};
+use crate::services::window_manager::OverlayRenderer;
+use crate::Result;
+
+const LEADER_WIDTH: f32 = 1.0;
+
+// Factor de los puntos de control de una curva cubica que aproxima un
+// cuarto de circulo
+const ARC_KAPPA: f64 = 0.552_284_75;
+
+/// Dibuja nodos de escena en un buffer RGBA con la CPU.
+///
+/// Reproduce lo que dibuja `MacOverlay` (caja redondeada con borde, texto
+/// con la fuente empaquetada, lineas guia, prefijo atenuado y match unico)
+/// sin ningun sistema de ventanas, para tests de imagen y para los
+/// renderers que pintan sobre memoria compartida.
+pub struct Rasterizer {
+    font: FontMeasure,
+}
+
+impl Rasterizer {
+    pub fn new() -> Self {
+        Self {
+            font: FontMeasure::new(),
+        }
+    }
+
+    /// Dibuja los nodos visibles sobre el buffer de `screen` (en pixeles,
+    /// origen en su esquina superior izquierda). Lo que cae fuera de la
+    /// pantalla se recorta.
+    pub fn draw(&self, pixmap: &mut Pixmap, nodes: &[SceneNode], screen: &Screen) {
+        // Global logico -> pixeles fisicos: escala y el origen global cae
+        // donde `Screen::to_physical` lo coloca
+        let scale = screen.scale_factor as f32;
+        let origin = screen.to_physical(LogicalPoint::new(0.0, 0.0));
+        let transform =
+            Transform::from_row(scale, 0.0, 0.0, scale, origin.x as f32, origin.y as f32);
+        let visible: Vec<&SceneNode> = nodes
+            .iter()
+            .filter(|node| node.state != LabelState::Hidden)
+            .collect();
+
+        // Lineas guia debajo de las cajas y el match unico por encima
+        for node in &visible {
+            if let Some(leader) = node.label_box.leader {
+                self.draw_leader(pixmap, &leader, &node.label_box.style, transform);
+            }
+        }
+        let (unique, rest): (Vec<&SceneNode>, Vec<&SceneNode>) = visible
+            .into_iter()
+            .partition(|node| matches!(node.state, LabelState::Unique { .. }));
+        for node in rest.into_iter().chain(unique) {
+            self.draw_box(pixmap, node, transform);
+        }
+    }
+
+    fn draw_leader(
+        &self,
+        pixmap: &mut Pixmap,
+        leader: &LeaderLine,
+        style: &HintStyle,
+        transform: Transform,
+    ) {
+        let mut builder = PathBuilder::new();
+        builder.move_to(leader.from.x as f32, leader.from.y as f32);
+        builder.line_to(leader.to.x as f32, leader.to.y as f32);
+        let Some(path) = builder.finish() else {
+            return;
+        };
+
+        let stroke = Stroke {
+            width: LEADER_WIDTH,
+            ..Stroke::default()
+        };
+        pixmap.stroke_path(
+            &path,
+            &paint(style.border_color, style.opacity),
+            &stroke,
+            transform,
+            None,
+        );
+    }
+
+    fn draw_box(&self, pixmap: &mut Pixmap, node: &SceneNode, transform: Transform) {
+        let label_box = &node.label_box;
+        let style = &label_box.style;
+
+        // La opacidad del estilo se aplica a cada parte por separado
+        if let Some(path) = rounded_rect(&label_box.rect, style.corner_radius) {
+            pixmap.fill_path(
+                &path,
+                &paint(style.background, style.opacity),
+                FillRule::Winding,
+                transform,
+                None,
+            );
+        }
+
+        let border_width = match node.state {
+            LabelState::Unique { .. } => style.border_width + UNIQUE_BORDER_EXTRA,
+            _ => style.border_width,
+        };
+        if border_width > 0.0 {
+            // Como en CALayer, el borde queda por dentro de la caja
+            let half = border_width / 2.0;
+            let rect = label_box.rect;
+            let inset = LogicalRect::new(
+                rect.x + half,
+                rect.y + half,
+                rect.width - border_width,
+                rect.height - border_width,
+            );
+            if let Some(path) = rounded_rect(&inset, (style.corner_radius - half).max(0.0)) {
+                let stroke = Stroke {
+                    width: border_width as f32,
+                    ..Stroke::default()
+                };
+                pixmap.stroke_path(
+                    &path,
+                    &paint(style.border_color, style.opacity),
+                    &stroke,
+                    transform,
+                    None,
+                );
+            }
+        }
+
+        let typed = match node.state {
+            LabelState::Matching { typed } | LabelState::Unique { typed } => typed,
+            LabelState::Idle | LabelState::Hidden => 0,
+        };
+        self.draw_text(pixmap, node, typed, transform);
+    }
+
+    /// Texto centrado en la caja; los `typed` primeros caracteres atenuados
+    fn draw_text(&self, pixmap: &mut Pixmap, node: &SceneNode, typed: usize, transform: Transform) {
+        let label_box = &node.label_box;
+        let style = &label_box.style;
+        let rect = label_box.rect;
+        let metrics = self.font.measure(&label_box.label, label_box.font_size);
+        let scale = self.font.scale(label_box.font_size);
+
+        let mut pen_x = rect.x + (rect.width - metrics.width) / 2.0;
+        let baseline = rect.y + (rect.height - metrics.line_height) / 2.0 + metrics.ascent;
+
+        let mut typed_glyphs = GlyphOutline::new(scale, baseline);
+        let mut glyphs = GlyphOutline::new(scale, baseline);
+        for (index, ch) in label_box.label.chars().enumerate() {
+            let outline = if index < typed {
+                &mut typed_glyphs
+            } else {
+                &mut glyphs
+            };
+            outline.pen_x = pen_x;
+            self.font.face().outline_glyph(self.font.glyph(ch), outline);
+            pen_x += self.font.advance(ch) * scale;
+        }
+
+        for (outline, alpha) in [
+            (typed_glyphs, style.opacity * TYPED_ALPHA),
+            (glyphs, style.opacity),
+        ] {
+            if let Some(path) = outline.builder.finish() {
+                pixmap.fill_path(
+                    &path,
+                    &paint(style.foreground, alpha),
+                    FillRule::Winding,
+                    transform,
+                    None,
+                );
+            }
+        }
+    }
+}
+
+impl Default for Rasterizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renderer de overlay en memoria: retiene la escena y la rasteriza bajo
+/// demanda, con un buffer por pantalla. Lo usan los tests de imagen y sirve
+/// de base a renderers que pintan un buffer (X11/Wayland con memoria
+/// compartida).
+pub struct RasterRenderer {
+    rasterizer: Rasterizer,
+    scene: HintScene,
+    layout: ScreenLayout,
+    visible: bool,
+}
+
+impl RasterRenderer {
+    pub fn new(layout: ScreenLayout) -> Self {
+        Self {
+            rasterizer: Rasterizer::new(),
+            scene: HintScene::new(),
+            layout,
+            visible: false,
+        }
+    }
+
+    /// Un frame por cada pantalla donde cae algun hint visible; ninguno si
+    /// el overlay esta oculto
+    pub fn frames(&self) -> Result<Vec<(Screen, Pixmap)>> {
+        if !self.visible {
+            return Ok(Vec::new());
+        }
+
+        let used: HashSet<u32> = self
+            .scene
+            .nodes()
+            .iter()
+            .filter(|node| node.state != LabelState::Hidden)
+            .filter_map(|node| self.layout.screen_for(&node.label_box.rect))
+            .map(|screen| screen.id)
+            .collect();
+
+        self.layout
+            .screens()
+            .iter()
+            .filter(|screen| used.contains(&screen.id))
+            .map(|screen| Ok((*screen, self.frame(screen)?)))
+            .collect()
+    }
+
+    /// Frame de una pantalla; transparente si el overlay esta oculto
+    pub fn frame(&self, screen: &Screen) -> Result<Pixmap> {
+        let size = screen.physical_size();
+        let mut pixmap = Pixmap::new(size.width, size.height).ok_or_else(|| {
+            AppError::Overlay(format!(
+                "Invalid raster size {}x{} for screen {}",
+                size.width, size.height, screen.id
+            ))
+        })?;
+
+        if self.visible {
+            self.rasterizer
+                .draw(&mut pixmap, self.scene.nodes(), screen);
+        }
+
+        Ok(pixmap)
+    }
+
+    /// Escribe `screen-<id>.png` en `dir` por cada frame
+    pub fn save_pngs(&self, dir: &Path) -> Result<()> {
+        for (screen, pixmap) in self.frames()? {
+            let path = dir.join(format!("screen-{}.png", screen.id));
+            pixmap.save_png(&path).map_err(|e| {
+                AppError::Overlay(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl OverlayRenderer for RasterRenderer {
+    fn init(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn apply(&mut self, ops: &[SceneOp]) -> Result<()> {
+        for op in ops {
+            self.scene.apply(op);
+        }
+        Ok(())
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+
+    fn hide(&mut self) -> Result<()> {
+        self.visible = false;
+        self.scene = HintScene::new();
+        Ok(())
+    }
+
+    fn teardown(&mut self) -> Result<()> {
+        self.hide()
+    }
+}
+
+/// Convierte los contornos de la fuente (unidades de fuente, Y hacia
+/// arriba) a un path en pixeles (Y hacia abajo)
+struct GlyphOutline {
+    builder: PathBuilder,
+    scale: f64,
+    baseline: f64,
+    pen_x: f64,
+}
+
+impl GlyphOutline {
+    fn new(scale: f64, baseline: f64) -> Self {
+        Self {
+            builder: PathBuilder::new(),
+            scale,
+            baseline,
+            pen_x: 0.0,
+        }
+    }
+
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (self.pen_x + x as f64 * self.scale) as f32,
+            (self.baseline - y as f64 * self.scale) as f32,
+        )
+    }
+}
+
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// Pintura de un color del tema con la opacidad extra `alpha` (0..1)
+fn paint(color: Color, alpha: f64) -> Paint<'static> {
+    let mut paint = Paint::default();
+    let alpha = (color.a as f64 * alpha.clamp(0.0, 1.0)).round() as u8;
+    paint.set_color_rgba8(color.r, color.g, color.b, alpha);
+    paint.anti_alias = true;
+    paint
+}
+
+/// Rectangulo con esquinas redondeadas (radio limitado a media caja)
+fn rounded_rect(rect: &LogicalRect, radius: f64) -> Option<tiny_skia::Path> {
+    if rect.is_empty() {
+        return None;
+    }
+
+    let r = radius.min(rect.width / 2.0).min(rect.height / 2.0).max(0.0);
+    let c = r * ARC_KAPPA;
+    let (left, top, right, bottom) = (rect.x, rect.y, rect.right(), rect.bottom());
+
+    let mut builder = PathBuilder::new();
+    let point = |x: f64, y: f64| (x as f32, y as f32);
+    let cubic = |builder: &mut PathBuilder, points: [(f64, f64); 3]| {
+        let [(x1, y1), (x2, y2), (x, y)] = points.map(|(x, y)| point(x, y));
+        builder.cubic_to(x1, y1, x2, y2, x, y);
+    };
+
+    let (x, y) = point(left + r, top);
+    builder.move_to(x, y);
+    let (x, y) = point(right - r, top);
+    builder.line_to(x, y);
+    cubic(
+        &mut builder,
+        [(right - r + c, top), (right, top + r - c), (right, top + r)],
+    );
+    let (x, y) = point(right, bottom - r);
+    builder.line_to(x, y);
+    cubic(
+        &mut builder,
+        [
+            (right, bottom - r + c),
+            (right - r + c, bottom),
+            (right - r, bottom),
+        ],
+    );
+    let (x, y) = point(left + r, bottom);
+    builder.line_to(x, y);
+    cubic(
+        &mut builder,
+        [
+            (left + r - c, bottom),
+            (left, bottom - r + c),
+            (left, bottom - r),
+        ],
+    );
+    let (x, y) = point(left, top + r);
+    builder.line_to(x, y);
+    cubic(
+        &mut builder,
+        [(left, top + r - c), (left + r - c, top), (left + r, top)],
+    );
+    builder.close();
+
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Hint, HintMode, HintTheme, Role};
+    use crate::services::hint_layout::HintLayout;
+
+    const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/golden");
+
+    // Diferencia maxima por canal tolerada (antialiasing con o sin SIMD)
+    const CHANNEL_TOLERANCE: u8 = 2;
+
+    fn hint(label: &str, x: f64, y: f64) -> Hint {
+        Hint::new(
+            label.to_string(),
+            LogicalPoint::new(x, y),
+            format!("el-{}", label),
+        )
+    }
+
+    /// Renderiza los hints como lo haria `WindowManager`: layout, diff
+    /// contra una escena vacia y `apply`
+    fn renderer(
+        hints: &[Hint],
+        mode: HintMode,
+        prefix: &str,
+        layout: ScreenLayout,
+    ) -> RasterRenderer {
+        let labels = HintLayout::new().layout(hints, &HintTheme::default(), mode);
+        let scene = HintScene::from_labels(labels).with_prefix(prefix);
+
+        let mut renderer = RasterRenderer::new(layout);
+        renderer.init().unwrap();
+        renderer.apply(&HintScene::new().diff(&scene)).unwrap();
+        renderer.show().unwrap();
+        renderer
+    }
+
+    /// Frame de una sola pantalla sin escalado que cubre `bounds`
+    fn render(hints: &[Hint], mode: HintMode, prefix: &str, bounds: LogicalRect) -> Pixmap {
+        let screen = Screen::new(1, bounds, 1.0);
+        renderer(hints, mode, prefix, ScreenLayout::new(vec![screen]))
+            .frame(&screen)
+            .unwrap()
+    }
+
+    /// Compara con `tests/fixtures/golden/<name>.png`. Con UPDATE_GOLDEN=1
+    /// se reescribe la imagen de referencia.
+    fn assert_golden(name: &str, pixmap: &Pixmap) {
+        let path = Path::new(GOLDEN_DIR).join(format!("{}.png", name));
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(GOLDEN_DIR).unwrap();
+            pixmap.save_png(&path).unwrap();
+            return;
+        }
+
+        let golden = Pixmap::load_png(&path).unwrap_or_else(|e| {
+            panic!(
+                "missing golden {} ({}); run with UPDATE_GOLDEN=1",
+                path.display(),
+                e
+            )
+        });
+        assert_eq!(
+            (golden.width(), golden.height()),
+            (pixmap.width(), pixmap.height()),
+            "{} size changed",
+            name
+        );
+
+        let differing = golden
+            .data()
+            .chunks(4)
+            .zip(pixmap.data().chunks(4))
+            .filter(|(expected, actual)| {
+                expected
+                    .iter()
+                    .zip(actual.iter())
+                    .any(|(e, a)| e.abs_diff(*a) > CHANNEL_TOLERANCE)
+            })
+            .count();
+
+        if differing > 0 {
+            let actual = std::env::temp_dir().join(format!("{}.actual.png", name));
+            pixmap.save_png(&actual).unwrap();
+            panic!(
+                "{} differs from golden in {} pixels; actual frame written to {}",
+                name,
+                differing,
+                actual.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_golden_isolated_hints() {
+        let hints = [
+            hint("a", 30.0, 30.0),
+            hint("sd", 90.0, 30.0),
+            hint("fg", 150.0, 30.0).with_role(Role::TextField),
+        ];
+        let frame = render(
+            &hints,
+            HintMode::Left,
+            "",
+            LogicalRect::new(0.0, 0.0, 180.0, 60.0),
+        );
+        assert_golden("isolated_hints", &frame);
+    }
+
+    #[test]
+    fn test_golden_crowded_toolbar() {
+        // Botones de 16px: parte de los labels se desplaza con linea guia
+        let hints: Vec<Hint> = ["a", "s", "d", "f", "g", "h"]
+            .iter()
+            .enumerate()
+            .map(|(i, label)| hint(label, 60.0 + i as f64 * 16.0, 70.0))
+            .collect();
+        let frame = render(
+            &hints,
+            HintMode::Left,
+            "",
+            LogicalRect::new(0.0, 0.0, 200.0, 100.0),
+        );
+        assert_golden("crowded_toolbar", &frame);
+    }
+
+    #[test]
+    fn test_golden_typed_prefix() {
+        let hints = [
+            hint("as", 30.0, 30.0),
+            hint("ad", 90.0, 30.0),
+            hint("sa", 150.0, 30.0),
+        ];
+        let bounds = LogicalRect::new(0.0, 0.0, 180.0, 60.0);
+
+        // "sa" se oculta y la "a" de los otros dos queda atenuada
+        let typed = render(&hints, HintMode::Right, "a", bounds);
+        assert_golden("typed_prefix", &typed);
+
+        // Solo queda "ad", con el borde del match unico
+        let unique = render(&hints, HintMode::Right, "ad", bounds);
+        assert_golden("unique_match", &unique);
+    }
+
+    #[test]
+    fn test_one_frame_per_screen_with_hints() {
+        // Principal a 1x y una Retina a su izquierda (origen negativo); una
+        // tercera pantalla sin hints no genera frame
+        let layout = ScreenLayout::new(vec![
+            Screen::new(1, LogicalRect::new(0.0, 0.0, 60.0, 60.0), 1.0),
+            Screen::new(2, LogicalRect::new(-60.0, 0.0, 60.0, 60.0), 2.0),
+            Screen::new(3, LogicalRect::new(60.0, 0.0, 60.0, 60.0), 1.0),
+        ]);
+        let hints = [hint("a", -30.0, 30.0), hint("s", 30.0, 30.0)];
+
+        let renderer = renderer(&hints, HintMode::Left, "", layout);
+        let frames = renderer.frames().unwrap();
+        let ids: Vec<u32> = frames.iter().map(|(screen, _)| screen.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        // En la Retina el hint queda en el centro de un buffer de 120x120
+        let (_, retina) = &frames[1];
+        assert_eq!((retina.width(), retina.height()), (120, 120));
+        assert!(retina.pixel(60, 60).unwrap().alpha() > 0);
+        assert_eq!(retina.pixel(4, 4).unwrap().alpha(), 0);
+
+        let dir = std::env::temp_dir().join(format!("raster-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        renderer.save_pngs(&dir).unwrap();
+        let saved = Pixmap::load_png(dir.join("screen-2.png")).unwrap();
+        assert_eq!((saved.width(), saved.height()), (120, 120));
+        assert!(!dir.join("screen-3.png").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_hidden_overlay_is_transparent() {
+        let screen = Screen::new(1, LogicalRect::new(0.0, 0.0, 40.0, 40.0), 1.0);
+        let mut renderer = renderer(
+            &[hint("a", 20.0, 20.0)],
+            HintMode::Left,
+            "",
+            ScreenLayout::new(vec![screen]),
+        );
+        renderer.hide().unwrap();
+
+        assert!(renderer.frames().unwrap().is_empty());
+        let frame = renderer.frame(&screen).unwrap();
+        assert!(frame.pixels().iter().all(|pixel| pixel.alpha() == 0));
+    }
+}
@@ -0,0 +1,208 @@
+use std::time::{Duration, Instant};
+
+use crate::models::HotkeyAction;
+
+/// Tiempo para pulsar el siguiente paso de una secuencia
+pub const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Resultado de pulsar una tecla
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceOutcome<K> {
+    // Secuencia completa
+    Dispatch(HotkeyAction),
+    // Leader o paso intermedio: se espera una de estas teclas
+    Armed(Vec<K>),
+    // Habia una secuencia a medias y la tecla no la continua
+    Cancelled,
+    // La tecla no empieza ninguna secuencia
+    Ignored,
+}
+
+/// Maquina de estados de las secuencias con leader ("Cmd+J F").
+///
+/// Pura: no registra nada en el sistema ni lee el reloj, el tiempo llega
+/// en cada llamada. `K` es la tecla tal como la identifica quien la usa
+/// (el `HotKey` en el servicio).
+pub struct SequenceMatcher<K> {
+    sequences: Vec<(Vec<K>, HotkeyAction)>,
+    timeout: Duration,
+    // Pasos ya pulsados de la secuencia en curso
+    pressed: Vec<K>,
+    deadline: Option<Instant>,
+}
+
+impl<K: Clone + PartialEq> SequenceMatcher<K> {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            sequences: Vec::new(),
+            timeout,
+            pressed: Vec::new(),
+            deadline: None,
+        }
+    }
+
+    /// Reemplaza las secuencias y descarta la que estuviera a medias. Se
+    /// asume que ninguna es prefijo de otra (el servicio lo impide).
+    pub fn set_sequences(&mut self, sequences: Vec<(Vec<K>, HotkeyAction)>) {
+        self.sequences = sequences;
+        self.reset();
+    }
+
+    /// Procesa una tecla pulsada en `now`
+    pub fn press(&mut self, key: K, now: Instant) -> SequenceOutcome<K> {
+        self.expire(now);
+
+        let armed = !self.pressed.is_empty();
+        let mut candidate = self.pressed.clone();
+        candidate.push(key.clone());
+
+        match self.advance(candidate, now) {
+            SequenceOutcome::Ignored if armed => {
+                // La tecla puede ser el leader de otra secuencia
+                match self.advance(vec![key], now) {
+                    SequenceOutcome::Ignored => SequenceOutcome::Cancelled,
+                    outcome => outcome,
+                }
+            }
+            outcome => outcome,
+        }
+    }
+
+    /// Descarta la secuencia en curso si se paso el plazo. Devuelve true si
+    /// habia una.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        match self.deadline {
+            Some(deadline) if now >= deadline => {
+                self.reset();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Plazo para el siguiente paso, si hay una secuencia a medias
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn advance(&mut self, candidate: Vec<K>, now: Instant) -> SequenceOutcome<K> {
+        if let Some((_, action)) = self.sequences.iter().find(|(keys, _)| *keys == candidate) {
+            let action = *action;
+            self.reset();
+            return SequenceOutcome::Dispatch(action);
+        }
+
+        let mut expecting: Vec<K> = Vec::new();
+        for (keys, _) in &self.sequences {
+            if keys.len() > candidate.len() && keys.starts_with(&candidate) {
+                let next = &keys[candidate.len()];
+                if !expecting.contains(next) {
+                    expecting.push(next.clone());
+                }
+            }
+        }
+
+        if expecting.is_empty() {
+            self.reset();
+            return SequenceOutcome::Ignored;
+        }
+
+        self.pressed = candidate;
+        self.deadline = Some(now + self.timeout);
+        SequenceOutcome::Armed(expecting)
+    }
+
+    fn reset(&mut self) {
+        self.pressed.clear();
+        self.deadline = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher() -> SequenceMatcher<&'static str> {
+        let mut matcher = SequenceMatcher::new(SEQUENCE_TIMEOUT);
+        matcher.set_sequences(vec![
+            (vec!["cmd+j", "f"], HotkeyAction::ActivateHints),
+            (vec!["cmd+j", "r"], HotkeyAction::RightClickHints),
+            (vec!["cmd+k"], HotkeyAction::ScrollMode),
+        ]);
+        matcher
+    }
+
+    #[test]
+    fn test_leader_then_key_dispatches() {
+        let mut matcher = matcher();
+        let now = Instant::now();
+
+        assert_eq!(
+            matcher.press("cmd+j", now),
+            SequenceOutcome::Armed(vec!["f", "r"])
+        );
+        assert_eq!(matcher.deadline(), Some(now + SEQUENCE_TIMEOUT));
+
+        assert_eq!(
+            matcher.press("r", now + Duration::from_millis(300)),
+            SequenceOutcome::Dispatch(HotkeyAction::RightClickHints)
+        );
+        assert_eq!(matcher.deadline(), None);
+    }
+
+    #[test]
+    fn test_single_step_dispatches_immediately() {
+        let mut matcher = matcher();
+        assert_eq!(
+            matcher.press("cmd+k", Instant::now()),
+            SequenceOutcome::Dispatch(HotkeyAction::ScrollMode)
+        );
+    }
+
+    #[test]
+    fn test_timeout_disarms() {
+        let mut matcher = matcher();
+        let now = Instant::now();
+        matcher.press("cmd+j", now);
+
+        assert!(!matcher.expire(now + Duration::from_millis(999)));
+        assert!(matcher.expire(now + SEQUENCE_TIMEOUT));
+        assert!(matcher.deadline().is_none());
+
+        // Pasado el plazo la tecla ya no completa la secuencia
+        matcher.press("cmd+j", now);
+        assert_eq!(
+            matcher.press("f", now + Duration::from_secs(2)),
+            SequenceOutcome::Ignored
+        );
+    }
+
+    #[test]
+    fn test_unexpected_key_cancels() {
+        let mut matcher = matcher();
+        let now = Instant::now();
+        matcher.press("cmd+j", now);
+
+        assert_eq!(matcher.press("x", now), SequenceOutcome::Cancelled);
+        assert!(matcher.deadline().is_none());
+        assert_eq!(matcher.press("f", now), SequenceOutcome::Ignored);
+    }
+
+    #[test]
+    fn test_other_leader_restarts_sequence() {
+        let mut matcher = matcher();
+        let now = Instant::now();
+        matcher.press("cmd+j", now);
+
+        assert_eq!(
+            matcher.press("cmd+k", now),
+            SequenceOutcome::Dispatch(HotkeyAction::ScrollMode)
+        );
+
+        matcher.press("cmd+j", now);
+        assert_eq!(
+            matcher.press("cmd+j", now),
+            SequenceOutcome::Armed(vec!["f", "r"])
+        );
+    }
+}
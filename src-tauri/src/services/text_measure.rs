@@ -0,0 +1,133 @@
+use ttf_parser::{Face, GlyphId};
+
+use crate::error::AppError;
+use crate::Result;
+
+/// Fuente de los labels, empaquetada con la app para que la medida y el
+/// dibujo coincidan en todas las plataformas
+pub const HINT_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSansMono-Bold.ttf");
+
+/// Medidas de un texto en pixeles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMetrics {
+    // Suma de los avances de los glifos
+    pub width: f64,
+    // Distancia de la linea base al borde superior de la linea
+    pub ascent: f64,
+    // Distancia de la linea base al borde inferior (positiva)
+    pub descent: f64,
+    // Alto de linea incluyendo el interlineado de la fuente
+    pub line_height: f64,
+}
+
+/// Mide texto para dimensionar las cajas de los hints
+pub trait TextMeasure: Send + Sync {
+    /// Medidas de `text` con el tamaño de fuente dado
+    fn measure(&self, text: &str, font_size: f64) -> TextMetrics;
+}
+
+/// Medida exacta a partir de las tablas de la fuente
+pub struct FontMeasure {
+    face: Face<'static>,
+}
+
+impl FontMeasure {
+    /// Medidor con la fuente empaquetada
+    pub fn new() -> Self {
+        Self::from_font(HINT_FONT).expect("bundled hint font should parse")
+    }
+
+    pub fn from_font(data: &'static [u8]) -> Result<Self> {
+        let face = Face::parse(data, 0)
+            .map_err(|e| AppError::Internal(format!("Invalid font data: {}", e)))?;
+        Ok(Self { face })
+    }
+
+    /// Fuente ya parseada, para quien tenga que dibujar los glifos
+    #[cfg(test)]
+    pub fn face(&self) -> &Face<'static> {
+        &self.face
+    }
+
+    /// Pixeles por unidad de fuente
+    pub fn scale(&self, font_size: f64) -> f64 {
+        font_size / self.face.units_per_em() as f64
+    }
+
+    /// Glifo de un caracter; los caracteres sin glifo usan .notdef, que es
+    /// lo que acabara dibujandose
+    pub fn glyph(&self, ch: char) -> GlyphId {
+        self.face.glyph_index(ch).unwrap_or(GlyphId(0))
+    }
+
+    /// Avance de un caracter en unidades de fuente
+    pub fn advance(&self, ch: char) -> f64 {
+        self.face.glyph_hor_advance(self.glyph(ch)).unwrap_or(0) as f64
+    }
+}
+
+impl Default for FontMeasure {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextMeasure for FontMeasure {
+    fn measure(&self, text: &str, font_size: f64) -> TextMetrics {
+        let scale = self.scale(font_size);
+        let width: f64 = text.chars().map(|ch| self.advance(ch)).sum();
+
+        let ascent = self.face.ascender() as f64;
+        let descent = -(self.face.descender() as f64);
+        let line_gap = self.face.line_gap() as f64;
+
+        TextMetrics {
+            width: width * scale,
+            ascent: ascent * scale,
+            descent: descent * scale,
+            line_height: (ascent + descent + line_gap) * scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_counts_characters_not_bytes() {
+        let measure = FontMeasure::new();
+
+        // Fuente monoespaciada: "ñ" (2 bytes) mide lo mismo que "n"
+        let ascii = measure.measure("an", 14.0);
+        let accented = measure.measure("añ", 14.0);
+        assert_eq!(ascii.width, accented.width);
+
+        let single = measure.measure("a", 14.0);
+        assert!((ascii.width - 2.0 * single.width).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_metrics_scale_with_font_size() {
+        let measure = FontMeasure::new();
+        let small = measure.measure("asdf", 10.0);
+        let large = measure.measure("asdf", 20.0);
+
+        assert!((large.width - 2.0 * small.width).abs() < 1e-9);
+        assert!((large.line_height - 2.0 * small.line_height).abs() < 1e-9);
+        assert!(small.ascent > 0.0 && small.descent > 0.0);
+        assert!(small.line_height >= small.ascent + small.descent);
+    }
+
+    #[test]
+    fn test_empty_text_has_line_height() {
+        let metrics = FontMeasure::new().measure("", 14.0);
+        assert_eq!(metrics.width, 0.0);
+        assert!(metrics.line_height > 0.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_font() {
+        assert!(FontMeasure::from_font(b"not a font").is_err());
+    }
+}
@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::models::{UIElement, UsageEntry};
+use crate::{AppError, Result};
+
+/// Vida media de la puntuacion: un uso de hace dos semanas vale la mitad
+const HALF_LIFE_SECS: f64 = 14.0 * 24.0 * 3600.0;
+/// Por debajo de esta puntuacion el elemento se olvida
+const MIN_SCORE: f64 = 0.05;
+/// Elementos recordados por app; se olvidan los de menor puntuacion
+const MAX_ELEMENTS_PER_APP: usize = 200;
+/// Apps recordadas; se olvidan las usadas hace mas tiempo
+const MAX_APPS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ElementUsage {
+    // Puntuacion en `last_used`; decae con el tiempo
+    score: f64,
+    count: u32,
+    last_used: u64,
+}
+
+impl ElementUsage {
+    fn score_at(&self, now: u64) -> f64 {
+        let elapsed = now.saturating_sub(self.last_used) as f64;
+        self.score * 0.5f64.powf(elapsed / HALF_LIFE_SECS)
+    }
+}
+
+/// Contenido del fichero: app -> clave estable -> uso
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageData {
+    apps: HashMap<String, HashMap<String, ElementUsage>>,
+}
+
+/// Hints de la ultima activacion: traduce el `element_id` elegido a su
+/// clave estable
+struct Session {
+    app: String,
+    keys: HashMap<String, String>,
+}
+
+/// Frecuencia de uso de los elementos por app, para dar labels cortos a
+/// los mas usados.
+///
+/// Se guarda como JSON en el directorio de datos de la app y no sale de la
+/// maquina. Cada uso suma 1 a una puntuacion que decae con vida media de
+/// 14 dias; los limites por app y de apps lo mantienen acotado.
+pub struct UsageStore {
+    // None = solo en memoria
+    path: Option<PathBuf>,
+    data: UsageData,
+    session: Option<Session>,
+}
+
+impl UsageStore {
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            data: UsageData::default(),
+            session: None,
+        }
+    }
+
+    /// Carga el fichero `path` (vacio si no existe o esta corrupto) y
+    /// guarda ahi los cambios
+    pub fn open(path: PathBuf) -> Self {
+        let data = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt usage stats {}: {}", path.display(), e);
+                UsageData::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => UsageData::default(),
+            Err(e) => {
+                tracing::warn!("Failed to read usage stats {}: {}", path.display(), e);
+                UsageData::default()
+            }
+        };
+
+        Self {
+            path: Some(path),
+            data,
+            session: None,
+        }
+    }
+
+    /// Puntuacion actual de cada elemento en `app` (0 si nunca se uso)
+    pub fn scores(&self, app: &str, elements: &[UIElement], now: u64) -> Vec<f64> {
+        let usage = self.data.apps.get(app);
+        elements
+            .iter()
+            .map(|element| {
+                usage
+                    .and_then(|usage| usage.get(&element.stable_key()))
+                    .map_or(0.0, |entry| entry.score_at(now))
+            })
+            .collect()
+    }
+
+    /// Recuerda los elementos con hint de esta activacion para
+    /// `record_selection`
+    pub fn begin_session(&mut self, app: String, elements: &[UIElement]) {
+        let keys = elements
+            .iter()
+            .map(|element| (element.id.clone(), element.stable_key()))
+            .collect();
+        self.session = Some(Session { app, keys });
+    }
+
+    /// Anota que se eligio el hint de `element_id` en la ultima activacion
+    /// y la cierra: cada activacion cuenta como mucho un uso. Devuelve false
+    /// si el elemento no era de esa activacion o ya se anoto otro.
+    pub fn record_selection(&mut self, element_id: &str, now: u64) -> Result<bool> {
+        let Some((app, key)) = self.session.as_ref().and_then(|session| {
+            let key = session.keys.get(element_id)?;
+            Some((session.app.clone(), key.clone()))
+        }) else {
+            tracing::debug!("Selection of {} outside the current session", element_id);
+            return Ok(false);
+        };
+
+        self.session = None;
+        self.record(&app, &key, now);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Suma un uso a un elemento
+    pub fn record(&mut self, app: &str, key: &str, now: u64) {
+        let usage = self.data.apps.entry(app.to_string()).or_default();
+        let entry = usage.entry(key.to_string()).or_insert(ElementUsage {
+            score: 0.0,
+            count: 0,
+            last_used: now,
+        });
+        entry.score = entry.score_at(now) + 1.0;
+        entry.count = entry.count.saturating_add(1);
+        entry.last_used = now;
+
+        self.prune(now);
+    }
+
+    /// Uso de cada elemento (de una app o de todas), de mas a menos usado
+    pub fn entries(&self, app: Option<&str>, now: u64) -> Vec<UsageEntry> {
+        let mut entries: Vec<UsageEntry> = self
+            .data
+            .apps
+            .iter()
+            .filter(|(name, _)| app.is_none_or(|app| app == name.as_str()))
+            .flat_map(|(name, usage)| {
+                usage.iter().map(move |(key, entry)| UsageEntry {
+                    app: name.clone(),
+                    key: key.clone(),
+                    score: entry.score_at(now),
+                    count: entry.count,
+                    last_used: entry.last_used,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+        entries
+    }
+
+    /// Borra el uso de una app, o de todas con `None`
+    pub fn reset(&mut self, app: Option<&str>) -> Result<()> {
+        match app {
+            Some(app) => {
+                self.data.apps.remove(app);
+            }
+            None => self.data.apps.clear(),
+        }
+        self.save()
+    }
+
+    /// Olvida lo que ya casi no cuenta y aplica los limites de tamaño
+    fn prune(&mut self, now: u64) {
+        for usage in self.data.apps.values_mut() {
+            usage.retain(|_, entry| entry.score_at(now) >= MIN_SCORE);
+
+            if usage.len() > MAX_ELEMENTS_PER_APP {
+                // A igual puntuacion se queda el usado mas recientemente
+                let mut ranked: Vec<(String, f64, u64)> = usage
+                    .iter()
+                    .map(|(key, entry)| (key.clone(), entry.score_at(now), entry.last_used))
+                    .collect();
+                ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.2.cmp(&a.2)));
+                for (key, _, _) in ranked.drain(MAX_ELEMENTS_PER_APP..) {
+                    usage.remove(&key);
+                }
+            }
+        }
+        self.data.apps.retain(|_, usage| !usage.is_empty());
+
+        while self.data.apps.len() > MAX_APPS {
+            let oldest = self
+                .data
+                .apps
+                .iter()
+                .min_by_key(|(_, usage)| usage.values().map(|entry| entry.last_used).max())
+                .map(|(name, _)| name.clone());
+            match oldest {
+                Some(name) => self.data.apps.remove(&name),
+                None => break,
+            };
+        }
+    }
+
+    /// Escribe a un fichero temporal y lo renombra para no dejar un JSON a
+    /// medias si la app se cierra
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_vec(&self.data)
+            .map_err(|e| AppError::Internal(format!("Failed to encode usage stats: {}", e)))?;
+        let tmp = path.with_extension("json.tmp");
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&tmp, json))
+            .and_then(|_| fs::rename(&tmp, path));
+
+        written.map_err(|e| {
+            AppError::Internal(format!(
+                "Failed to save usage stats to {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// Segundos Unix actuales, el reloj de `UsageStore`
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LogicalRect, Role};
+
+    const DAY: u64 = 24 * 3600;
+
+    fn element(id: &str, title: &str) -> UIElement {
+        UIElement::new(
+            id.to_string(),
+            Role::Button,
+            Some(title.to_string()),
+            LogicalRect::new(0.0, 0.0, 10.0, 10.0),
+        )
+    }
+
+    #[test]
+    fn test_selection_is_recorded_by_stable_key() {
+        let mut store = UsageStore::in_memory();
+        let now = 1_000_000;
+        let scan = vec![
+            element("ax-1-button", "Send"),
+            element("ax-2-button", "Cancel"),
+        ];
+
+        store.begin_session("Mail".to_string(), &scan);
+        assert!(!store.record_selection("ax-9-button", now).unwrap());
+        assert!(store.record_selection("ax-1-button", now).unwrap());
+        // La activacion ya se cerro: repetir no suma otro uso
+        assert!(!store.record_selection("ax-1-button", now).unwrap());
+        assert!(!store.record_selection("ax-2-button", now).unwrap());
+
+        // En el siguiente escaneo cambian los ids, no las claves
+        let rescan = vec![
+            element("ax-7-button", "Cancel"),
+            element("ax-8-button", "Send"),
+        ];
+        assert_eq!(store.scores("Mail", &rescan, now), vec![0.0, 1.0]);
+        assert_eq!(store.scores("Notes", &rescan, now), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_selected_hint_gets_single_key_label() {
+        use crate::models::{ElementTree, Hint};
+        use crate::services::HintGenerator;
+
+        let mut store = UsageStore::in_memory();
+        let generator = HintGenerator::with_charset("asdfghjkl");
+        let now = 1_000_000;
+
+        // Cada escaneo da ids nuevos a los mismos 20 botones de la toolbar
+        let scan = |round: usize| {
+            let mut tree = ElementTree::new();
+            let window = tree.add_node(
+                UIElement::new(
+                    format!("ax-{}-window", round),
+                    Role::Window,
+                    None,
+                    LogicalRect::new(0.0, 0.0, 800.0, 600.0),
+                ),
+                None,
+            );
+            for i in 0..20 {
+                let title = format!("Action {}", i);
+                tree.add_node(element(&format!("ax-{}-{}", round, i), &title), Some(window));
+            }
+            tree.nodes()[1..]
+                .iter()
+                .map(|node| node.element.clone())
+                .collect::<Vec<_>>()
+        };
+        // Lo que hace `activate_navigation` con el uso aprendido
+        let activate = |store: &mut UsageStore, round: usize| -> Vec<Hint> {
+            let elements = scan(round);
+            let scores = store.scores("Mail", &elements, now);
+            store.begin_session("Mail".to_string(), &elements);
+            generator.generate_with_usage(&elements, &scores)
+        };
+        let label_of = |hints: &[Hint], id: &str| {
+            hints
+                .iter()
+                .find(|hint| hint.element_id == id)
+                .map(|hint| hint.label.clone())
+                .unwrap()
+        };
+
+        let hints = activate(&mut store, 0);
+        assert_eq!(label_of(&hints, "ax-0-13").len(), 2);
+        assert!(store.record_selection("ax-0-13", now).unwrap());
+
+        // Un uso todavia no basta para una tecla
+        let hints = activate(&mut store, 1);
+        assert_eq!(label_of(&hints, "ax-1-13").len(), 2);
+        assert!(store.record_selection("ax-1-13", now).unwrap());
+
+        let hints = activate(&mut store, 2);
+        assert_eq!(label_of(&hints, "ax-2-13").len(), 1);
+        assert!(hints
+            .iter()
+            .filter(|hint| hint.element_id != "ax-2-13")
+            .all(|hint| hint.label.len() == 2));
+    }
+
+    #[test]
+    fn test_scores_decay() {
+        let mut store = UsageStore::in_memory();
+        let now = 1_000_000;
+        let send = element("ax-1-button", "Send");
+        store.record("Mail", &send.stable_key(), now);
+        store.record("Mail", &send.stable_key(), now);
+
+        let later = now + 14 * DAY;
+        assert_eq!(
+            store.scores("Mail", std::slice::from_ref(&send), later),
+            vec![1.0]
+        );
+
+        // Con el tiempo el elemento se olvida del todo
+        store.record("Mail", "other", now + 400 * DAY);
+        let entries = store.entries(Some("Mail"), now + 400 * DAY);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "other");
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let mut store = UsageStore::in_memory();
+        let now = 1_000_000;
+
+        store.record("Mail", "favourite", now);
+        store.record("Mail", "favourite", now);
+        for i in 0..MAX_ELEMENTS_PER_APP + 10 {
+            store.record("Mail", &format!("element-{}", i), now);
+        }
+        let entries = store.entries(Some("Mail"), now);
+        assert_eq!(entries.len(), MAX_ELEMENTS_PER_APP);
+        assert_eq!(entries[0].key, "favourite");
+
+        for i in 0..MAX_APPS + 5 {
+            store.record(&format!("app-{}", i), "button", now + 1 + i as u64);
+        }
+        assert_eq!(store.data.apps.len(), MAX_APPS);
+        assert!(store
+            .data
+            .apps
+            .contains_key(&format!("app-{}", MAX_APPS + 4)));
+        assert!(!store.data.apps.contains_key("Mail"));
+    }
+
+    #[test]
+    fn test_persists_and_resets() {
+        let dir = std::env::temp_dir().join(format!("usage-store-test-{}", std::process::id()));
+        let path = dir.join("usage.json");
+        let _ = fs::remove_dir_all(&dir);
+        let now = 1_000_000;
+        let send = element("ax-1-button", "Send");
+
+        let mut store = UsageStore::open(path.clone());
+        store.begin_session("Mail".to_string(), std::slice::from_ref(&send));
+        store.record_selection("ax-1-button", now).unwrap();
+
+        let reopened = UsageStore::open(path.clone());
+        assert_eq!(
+            reopened.scores("Mail", std::slice::from_ref(&send), now),
+            vec![1.0]
+        );
+
+        store.reset(Some("Mail")).unwrap();
+        let reopened = UsageStore::open(path.clone());
+        assert!(reopened.entries(None, now).is_empty());
+
+        fs::write(&path, b"{not json").unwrap();
+        assert!(UsageStore::open(path).entries(None, now).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::models::{ElementTree, FilterDecision, FilterExplanation, LogicalRect, Role};
+
+/// Recorta los elementos del arbol a la parte realmente visible.
+///
+/// El frame de cada elemento se intersecta con el de sus ancestros que
+/// recortan contenido (scroll areas y la propia ventana) y con las
+/// pantallas. Los elementos que quedan totalmente ocultos se descartan; los
+/// parcialmente visibles guardan el rectangulo recortado en `visible_rect`.
+pub struct VisibilityFilter {
+    // Frames de las pantallas activas (vacio = no recortar por pantalla)
+    screens: Vec<LogicalRect>,
+}
+
+impl VisibilityFilter {
+    pub fn new(screens: Vec<LogicalRect>) -> Self {
+        Self { screens }
+    }
+
+    /// Decision de visibilidad para cada nodo clickable del arbol, en orden
+    /// BFS; los visibles llevan `visible_rect` calculado
+    pub fn explain(&self, tree: &ElementTree) -> Vec<FilterExplanation> {
+        tree.clickable()
+            .map(|(index, node)| {
+                let mut element = node.element.clone();
+                let decision = match self.visible_rect(tree, index) {
+                    Ok(visible) => {
+                        element.visible_rect = Some(visible);
+                        FilterDecision::Kept
+                    }
+                    Err(reason) => FilterDecision::dropped("visibility", reason),
+                };
+                FilterExplanation { element, decision }
+            })
+            .collect()
+    }
+
+    /// Parte visible del nodo `index`, o la razon por la que esta oculto
+    pub fn visible_rect(&self, tree: &ElementTree, index: usize) -> Result<LogicalRect, String> {
+        let Some(node) = tree.node(index) else {
+            return Err(format!("node {} not in tree", index));
+        };
+        let mut visible = node.element.frame;
+
+        for ancestor in tree.ancestors(index) {
+            let Some(ancestor) = tree.node(ancestor) else {
+                continue;
+            };
+            let clip = ancestor.element.frame;
+            // Los nodos sin frame (placeholders de lecturas fallidas) no recortan
+            if !clips_children(&ancestor.element.role) || clip.is_empty() {
+                continue;
+            }
+
+            visible = visible.intersection(&clip).ok_or_else(|| {
+                format!("outside {} {}", ancestor.element.role, ancestor.element.id)
+            })?;
+        }
+
+        if self.screens.is_empty() {
+            return Ok(visible);
+        }
+
+        // Si el elemento cruza varias pantallas se queda con la que mas cubre
+        self.screens
+            .iter()
+            .filter_map(|screen| visible.intersection(screen))
+            .max_by(|a, b| a.area().total_cmp(&b.area()))
+            .ok_or_else(|| "off screen".to_string())
+    }
+}
+
+/// Roles cuyo contenido no se dibuja fuera de su frame
+fn clips_children(role: &Role) -> bool {
+    matches!(role, Role::Window | Role::ScrollArea)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UIElement;
+    use crate::services::clickable_stages::split_kept;
+
+    fn make_element(id: &str, role: Role, x: f64, y: f64, w: f64, h: f64) -> UIElement {
+        UIElement::new(id.to_string(), role, None, LogicalRect::new(x, y, w, h))
+    }
+
+    // window (0,0 800x600)
+    // └── scroll (0,100 400x200)
+    //     ├── visible   (10,110 100x20)
+    //     ├── partial   (10,290 100x20)
+    //     └── scrolled  (10,400 100x20)
+    fn sample_tree() -> ElementTree {
+        let mut tree = ElementTree::new();
+        let window = tree.add_node(
+            make_element("window", Role::Window, 0.0, 0.0, 800.0, 600.0),
+            None,
+        );
+        let scroll = tree.add_node(
+            make_element("scroll", Role::ScrollArea, 0.0, 100.0, 400.0, 200.0),
+            Some(window),
+        );
+        for (id, y) in [("visible", 110.0), ("partial", 290.0), ("scrolled", 400.0)] {
+            tree.add_node(
+                make_element(id, Role::Button, 10.0, y, 100.0, 20.0),
+                Some(scroll),
+            );
+        }
+        tree
+    }
+
+    fn visible_clickable(tree: &ElementTree) -> Vec<UIElement> {
+        split_kept(VisibilityFilter::new(Vec::new()).explain(tree)).0
+    }
+
+    #[test]
+    fn test_clips_to_scroll_area() {
+        let tree = sample_tree();
+        let elements = visible_clickable(&tree);
+
+        let ids: Vec<&str> = elements.iter().map(|el| el.id.as_str()).collect();
+        assert_eq!(ids, vec!["visible", "partial"]);
+
+        assert_eq!(elements[0].visible_frame(), elements[0].frame);
+        assert_eq!(
+            elements[1].visible_rect,
+            Some(LogicalRect::new(10.0, 290.0, 100.0, 10.0))
+        );
+        // El hint va al centro de la parte visible
+        assert_eq!(elements[1].visible_frame().center().y, 295.0);
+    }
+
+    #[test]
+    fn test_explains_hidden_elements() {
+        let tree = sample_tree();
+        let explanations = VisibilityFilter::new(Vec::new()).explain(&tree);
+
+        assert_eq!(
+            explanations[2].decision,
+            FilterDecision::dropped("visibility", "outside scrollarea scroll")
+        );
+    }
+
+    #[test]
+    fn test_clips_to_screens() {
+        let mut tree = ElementTree::new();
+        let window = tree.add_node(
+            make_element("window", Role::Window, -100.0, 0.0, 2000.0, 600.0),
+            None,
+        );
+        tree.add_node(
+            make_element("left", Role::Button, -50.0, 10.0, 100.0, 20.0),
+            Some(window),
+        );
+        tree.add_node(
+            make_element("gone", Role::Button, -90.0, 10.0, 30.0, 20.0),
+            Some(window),
+        );
+
+        let screens = vec![
+            LogicalRect::new(0.0, 0.0, 1440.0, 900.0),
+            LogicalRect::new(1440.0, 0.0, 1920.0, 1080.0),
+        ];
+        let explanations = VisibilityFilter::new(screens).explain(&tree);
+
+        assert_eq!(
+            explanations[0].element.visible_rect,
+            Some(LogicalRect::new(0.0, 10.0, 50.0, 20.0))
+        );
+        assert_eq!(
+            explanations[1].decision,
+            FilterDecision::dropped("visibility", "off screen")
+        );
+    }
+
+    #[test]
+    fn test_ignores_ancestors_without_frame() {
+        let mut tree = ElementTree::new();
+        let window = tree.add_node(
+            make_element("window", Role::Window, 0.0, 0.0, 800.0, 600.0),
+            None,
+        );
+        let placeholder = tree.add_node(
+            make_element("ax-1-unknown", Role::ScrollArea, 0.0, 0.0, 0.0, 0.0),
+            Some(window),
+        );
+        tree.add_node(
+            make_element("btn", Role::Button, 10.0, 10.0, 20.0, 20.0),
+            Some(placeholder),
+        );
+
+        let elements = visible_clickable(&tree);
+        assert_eq!(elements.len(), 1);
+    }
+}
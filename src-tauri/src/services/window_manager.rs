@@ -1,4 +1,7 @@
-use crate::models::Hint;
+use crate::models::{Hint, HintMode, HintTheme, SceneOp};
+use crate::services::hint_layout::HintLayout;
+use crate::services::hint_scene::HintScene;
+use crate::AppError;
 use crate::Result;
 
 /// Trait para renderizadores de overlay específicos por plataforma
@@ -6,8 +9,10 @@ pub trait OverlayRenderer: Send {
     /// Inicializa el renderer y crea la ventana nativa
     fn init(&mut self) -> Result<()>;
 
-    /// Dibuja los hints en el overlay
-    fn draw_hints(&mut self, hints: &[Hint]) -> Result<()>;
+    /// Aplica sobre lo ya dibujado los cambios de la escena: crea, mueve,
+    /// actualiza (texto, estilo, prefijo resaltado, match unico u oculto) o
+    /// elimina las primitivas de cada hint
+    fn apply(&mut self, ops: &[SceneOp]) -> Result<()>;
 
     /// Muestra el overlay
     fn show(&mut self) -> Result<()>;
@@ -30,10 +35,10 @@ impl OverlayRenderer for NoopRenderer {
         Ok(())
     }
 
-    fn draw_hints(&mut self, hints: &[Hint]) -> Result<()> {
+    fn apply(&mut self, ops: &[SceneOp]) -> Result<()> {
         tracing::warn!(
-            "NoopRenderer: draw_hints() with {} hints - not rendere find",
-            hints.len()
+            "NoopRenderer: apply() with {} scene ops - not rendered",
+            ops.len()
         );
         Ok(())
     }
@@ -64,6 +69,11 @@ impl NoopRenderer {
 /// Gestor de ventanas y overlay
 pub struct WindowManager {
     renderer: Box<dyn OverlayRenderer>,
+    layout: HintLayout,
+    theme: HintTheme,
+    // Escena que tiene dibujada el renderer; cada frame nuevo se compara
+    // con ella para mandar solo los cambios
+    scene: HintScene,
     is_overlay_visible: bool,
 }
 
@@ -75,6 +85,9 @@ impl WindowManager {
         tracing::debug!("Initializing WindowManager with MacOverlay");
         Self {
             renderer: Box::new(MacOverlay::new()),
+            layout: HintLayout::new(),
+            theme: HintTheme::default(),
+            scene: HintScene::new(),
             is_overlay_visible: false,
         }
     }
@@ -84,6 +97,9 @@ impl WindowManager {
         tracing::debug!("Initializing WindowManager with NoopRenderer");
         Self {
             renderer: Box::new(NoopRenderer::new()),
+            layout: HintLayout::new(),
+            theme: HintTheme::default(),
+            scene: HintScene::new(),
             is_overlay_visible: false,
         }
     }
@@ -95,17 +111,28 @@ impl WindowManager {
         self.renderer.init()
     }
 
+    /// Cambia el tema; se aplica en el siguiente dibujado
+    pub fn set_theme(&mut self, theme: HintTheme) {
+        self.theme = theme;
+    }
+
     /// Muestra el overlay con los hints proporcionados
-    pub async fn show_overlay(&mut self, hints: &[Hint]) -> Result<()> {
-        tracing::info!("WindowManager: showing overlay with {} hints", hints.len());
+    pub async fn show_overlay(&mut self, hints: &[Hint], mode: HintMode) -> Result<()> {
+        tracing::info!(
+            "WindowManager: showing overlay with {} hints ({:?})",
+            hints.len(),
+            mode
+        );
 
         // Inicializar si es necesario
         if !self.is_overlay_visible {
             self.renderer.init()?;
         }
 
-        // Dibujar hints
-        self.renderer.draw_hints(hints)?;
+        // Colocar labels sin solapes y dibujar solo lo que cambia respecto
+        // a la escena actual (todo si el overlay estaba oculto)
+        let next = HintScene::from_labels(self.layout.layout(hints, &self.theme, mode));
+        self.present(next)?;
 
         // Mostrar
         self.renderer.show()?;
@@ -122,7 +149,39 @@ impl WindowManager {
             self.renderer.hide()?;
             self.is_overlay_visible = false;
         }
+        // El renderer suelta sus primitivas al ocultarse
+        self.scene = HintScene::new();
+
+        Ok(())
+    }
 
+    /// Filtra los labels dibujados por el prefijo escrito. Devuelve los
+    /// `element_id` que siguen coincidiendo.
+    pub async fn update_overlay(&mut self, prefix: &str) -> Result<Vec<String>> {
+        if !self.is_overlay_visible {
+            return Err(AppError::Overlay("Overlay is not visible".to_string()));
+        }
+
+        let next = self.scene.with_prefix(prefix);
+        let visible = next.visible_ids();
+        self.present(next)?;
+
+        Ok(visible)
+    }
+
+    /// Manda al renderer la diferencia con la escena actual y la reemplaza
+    fn present(&mut self, next: HintScene) -> Result<()> {
+        let ops = self.scene.diff(&next);
+        tracing::debug!(
+            "WindowManager: {} scene ops for {} hints",
+            ops.len(),
+            next.len()
+        );
+
+        if !ops.is_empty() {
+            self.renderer.apply(&ops)?;
+        }
+        self.scene = next;
         Ok(())
     }
 
@@ -153,7 +212,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_show_hide_overlay() {
-        let mut wm = WindowManager::new();
+        let wm = WindowManager::new();
         assert!(!wm.is_visible());
 
         // Note: En tests sin entorno gráfico, estos pueden fallar
@@ -1,4 +1,8 @@
-use crate::services::{AccessibilityService, ClickService, HintGenerator, HotkeyService, WindowManager};
+use crate::models::config::AppConfig;
+use crate::models::Hint;
+use crate::services::{
+    AccessibilityService, ClickService, HintGenerator, HotkeyService, UsageStore, WindowManager,
+};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -9,21 +13,33 @@ pub struct AppState {
     #[allow(dead_code)]
     pub hint_generator: HintGenerator,
     pub window_manager: Arc<Mutex<WindowManager>>,
+    pub config: Arc<Mutex<AppConfig>>,
+    // En memoria hasta que `load_usage_store` abre el fichero en `setup`
+    pub usage_store: Arc<Mutex<UsageStore>>,
+    // Hints de la activacion en curso, para resolver la seleccion
+    pub active_hints: Arc<Mutex<Vec<Hint>>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
         tracing::info!("Initializing AppState...");
 
+        let config = AppConfig::default();
         let hotkeys = HotkeyService::new();
-        let window_manager = WindowManager::new();
+        let mut window_manager = WindowManager::new();
+        window_manager.set_theme(config.hint_theme.clone());
+        let accessibility_service = AccessibilityService::new();
+        accessibility_service.set_click_anchors(config.click_anchors.clone());
 
         Self {
-            accessibility_service: AccessibilityService::new(),
+            accessibility_service,
             hotkey_service: Arc::new(Mutex::new(hotkeys)),
             click_service: ClickService::new(),
             hint_generator: HintGenerator::new(),
             window_manager: Arc::new(Mutex::new(window_manager)),
+            config: Arc::new(Mutex::new(config)),
+            usage_store: Arc::new(Mutex::new(UsageStore::in_memory())),
+            active_hints: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -1,9 +1,35 @@
-import type { Hint, UIElement } from '$lib/types';
+import type {
+    AccessibilityRole,
+    ClickAnchor,
+    FilterExplanation,
+    Hint,
+    HintCharset,
+    HintMode,
+    HintTheme,
+    HotkeyAction,
+    HotkeyBinding,
+    HotkeyStatus,
+    InspectedTree,
+    KeyboardLayout,
+    LabelStrategy,
+    LogicalPoint,
+    ScanOptions,
+    UIElement,
+    UsageEntry,
+} from '$lib/types';
 import { invoke } from '@tauri-apps/api/core';
 
 export class TauriCommands {
-    static async scanElements(): Promise<UIElement[]> {
-        return invoke('scan_elements');
+    static async scanElements(options?: ScanOptions): Promise<UIElement[]> {
+        return invoke('scan_elements', { options });
+    }
+
+    static async inspectTree(save?: boolean): Promise<InspectedTree> {
+        return invoke('inspect_tree', { save });
+    }
+
+    static async explainFilter(): Promise<FilterExplanation[]> {
+        return invoke('explain_filter');
     }
 
     static async checkPermissions(): Promise<boolean> {
@@ -18,23 +44,98 @@ export class TauriCommands {
         return invoke('open_accessibility_settings');
     }
 
-    static async registerHotkey(keyCombo: string): Promise<void> {
-        return invoke('register_hotkey', { keyCombo });
+    // Devuelve la asignacion con el combo canonico
+    static async bindHotkey(action: HotkeyAction, keyCombo: string): Promise<HotkeyBinding> {
+        return invoke('bind_hotkey', { action, keyCombo });
+    }
+
+    static async unbindHotkey(action: HotkeyAction): Promise<void> {
+        return invoke('unbind_hotkey', { action });
+    }
+
+    // Estado de cada accion; si no esta registrada, `conflict` dice por que
+    static async listHotkeys(): Promise<HotkeyStatus[]> {
+        return invoke('list_hotkeys');
+    }
+
+    // Emite `hotkey-registration-failed` por cada hotkey que fallo al
+    // arrancar; llamar tras suscribirse al evento (solo avisa una vez)
+    static async reportStartupHotkeys(): Promise<void> {
+        return invoke('report_startup_hotkeys');
     }
     
-    static async performClick(x: number, y: number) : Promise<void> {
-        return invoke('perform_click', { x, y });
+    static async performClick(point: LogicalPoint) : Promise<void> {
+        return invoke('perform_click', { point });
+    }
+
+    static async showHints(hints: Hint[], mode?: HintMode): Promise<void> {
+        return invoke('show_hints', { hints, mode });
     }
 
-    static async showHints(hints: Hint[]): Promise<void> {
-        return invoke('show_hints', { hints });
+    // Devuelve los element_id que siguen coincidiendo con el prefijo; si
+    // queda uno de la activacion en curso, el backend hace click en el
+    static async updateHints(prefix: string): Promise<string[]> {
+        return invoke<string[]>('update_hints', { prefix });
     }
 
-    static async activateNavigation(): Promise<Hint[]> {
-        return invoke<Hint[]>("activate_navigation");
+    static async activateNavigation(mode?: HintMode): Promise<Hint[]> {
+        return invoke<Hint[]>("activate_navigation", { mode });
+    }
+
+    // Como activateNavigation pero con elementos ya conocidos, sin escanear
+    // (en X11 no hay escaneo de accesibilidad)
+    static async navigateElements(elements: UIElement[], mode?: HintMode): Promise<Hint[]> {
+        return invoke<Hint[]>("navigate_elements", { elements, mode });
     }
 
     static async deactivateNavigation(): Promise<void> {
         return invoke("deactivate_navigation");
     }
+
+    // Oculta el overlay, hace click en el hint y anota el uso
+    static async selectHint(elementId: string): Promise<void> {
+        return invoke('select_hint', { elementId });
+    }
+
+    static async getHintTheme(): Promise<HintTheme> {
+        return invoke('get_hint_theme');
+    }
+
+    static async setHintTheme(theme: HintTheme): Promise<void> {
+        return invoke('set_hint_theme', { theme });
+    }
+
+    // Los roles que falten usan el centro
+    static async setClickAnchors(
+        anchors: Partial<Record<AccessibilityRole, ClickAnchor>>,
+    ): Promise<void> {
+        return invoke('set_click_anchors', { anchors });
+    }
+
+    // null si no se pudo detectar
+    static async detectKeyboardLayout(): Promise<KeyboardLayout | null> {
+        return invoke('detect_keyboard_layout');
+    }
+
+    // layout null = detectar; devuelve el charset resultante
+    static async setHintCharset(
+        charset: HintCharset,
+        layout: KeyboardLayout | null,
+        hintChars?: string,
+    ): Promise<string> {
+        return invoke('set_hint_charset', { charset, layout, hintChars });
+    }
+
+    static async setLabelStrategy(strategy: LabelStrategy): Promise<void> {
+        return invoke('set_label_strategy', { strategy });
+    }
+
+    // app null = todas las apps
+    static async getUsageStats(app: string | null = null): Promise<UsageEntry[]> {
+        return invoke('get_usage_stats', { app });
+    }
+
+    static async resetUsageStats(app: string | null = null): Promise<void> {
+        return invoke('reset_usage_stats', { app });
+    }
 }
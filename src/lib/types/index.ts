@@ -1,31 +1,198 @@
-interface Rect {
+// Espacio global del escritorio en puntos lógicos (origen arriba/izquierda
+// de la pantalla principal), el de AX y los clicks
+export interface LogicalPoint {
+  x: number;
+  y: number;
+}
+
+export interface LogicalRect {
   x: number;
   y: number;
   width: number;
   height: number;
 }
 
+// Espejo de `Role` en Rust. Los roles nativos sin equivalente
+// normalizado llegan tal cual (ej: "AXSplitter").
 export type AccessibilityRole =
   | "button"
   | "link"
   | "textfield"
+  | "textarea"
   | "checkbox"
+  | "radiobutton"
   | "menuitem"
-  | "other";
+  | "menubutton"
+  | "popupbutton"
+  | "combobox"
+  | "tab"
+  | "tabgroup"
+  | "slider"
+  | "incrementor"
+  | "colorwell"
+  | "disclosuretriangle"
+  | "row"
+  | "cell"
+  | "window"
+  | "group"
+  | "scrollarea"
+  | "toolbar"
+  | "list"
+  | "table"
+  | "statictext"
+  | "image"
+  | (string & {});
   
 export interface UIElement {
   id: string;
-  role: string;
+  role: AccessibilityRole;
+  raw_role: string;
   title: string | null;
-  x: number;
-  y: number;
-  width: number;
-  height: number;
+  frame: LogicalRect;
+  // Atributos opcionales: null salvo que se pidan en ScanOptions
+  value: string | null;
+  description: string | null;
+  enabled: boolean | null;
+  focused: boolean | null;
+  selected: boolean | null;
+  actions: string[] | null;
+  pid: number | null;
+  window_id: number | null;
+  // Parte visible tras recortar con ventana, scroll areas y pantallas
+  visible_rect: LogicalRect | null;
+  // Punto de click dentro de la parte visible (null = su centro)
+  click_point: LogicalPoint | null;
+  // Ruta en el arbol ("0/3/1")
+  path: string | null;
+  // Roles de los ancestros desde la ventana ("AXWindow/AXToolbar")
+  role_path: string | null;
+  // Identificador que la app da al elemento (AXIdentifier)
+  identifier: string | null;
+}
+
+export interface ScanOptions {
+  value?: boolean;
+  description?: boolean;
+  enabled?: boolean;
+  focused?: boolean;
+  selected?: boolean;
+  actions?: boolean;
+  pid?: boolean;
+  window?: boolean;
 }
 
 export interface Hint {
   label: string;
-  x: number;
-  y: number;
+  // Anclaje del label
+  anchor: LogicalPoint;
+  // Punto donde se hace click al elegir el hint
+  click_point: LogicalPoint;
   element_id: string;
+  // Rol del elemento, para el estilo por rol del tema
+  role: AccessibilityRole | null;
+}
+
+// Colores como "#RRGGBB" o "#RRGGBBAA"
+export interface HintStyle {
+  background: string;
+  foreground: string;
+  border_color: string;
+  border_width: number;
+  font_family: string;
+  font_size: number;
+  padding: number;
+  corner_radius: number;
+  opacity: number;
+}
+
+export type HintStyleOverride = Partial<HintStyle>;
+
+export type HintMode = "left" | "right";
+
+// base_n: labels en orden; ergonomic: los mas faciles de teclear primero
+export type LabelStrategy = "base_n" | "ergonomic";
+
+export interface HintTheme {
+  base: HintStyle;
+  modes: Partial<Record<HintMode, HintStyleOverride>>;
+  roles: Partial<Record<AccessibilityRole, HintStyleOverride>>;
+}
+
+// Punto preferido de click dentro de un elemento, por rol
+export type ClickAnchor = "center" | "left" | "right" | "top" | "bottom";
+
+export type KeyboardLayout =
+  | "qwerty"
+  | "qwertz"
+  | "azerty"
+  | "dvorak"
+  | "colemak"
+  | "colemak_dh"
+  | "workman"
+  | "bepo";
+
+// home_row: fila central del layout; custom: hint_chars tal cual;
+// physical: hint_chars como posiciones QWERTY traducidas al layout
+export type HintCharset = "home_row" | "custom" | "physical";
+
+export type HotkeyAction = "activate_hints" | "right_click_hints" | "scroll_mode";
+
+// combo en forma canonica: un hotkey ("Cmd+Shift+J") o una secuencia con leader ("Cmd+J F")
+export interface HotkeyBinding {
+  action: HotkeyAction;
+  combo: string;
+}
+
+export type HotkeyConflict =
+  | { kind: "already_bound"; action: HotkeyAction }
+  | { kind: "reserved"; reason: string }
+  | { kind: "rejected_by_os"; message: string };
+
+// conflict explica por que fallo rejected_combo; si la accion conservo su
+// combo anterior, registered sigue a true
+export interface HotkeyStatus {
+  action: HotkeyAction;
+  combo: string | null;
+  registered: boolean;
+  conflict: HotkeyConflict | null;
+  rejected_combo: string | null;
+}
+
+// Error de bind_hotkey cuando el combo choca; el resto de errores llegan como texto
+export interface HotkeyConflictError {
+  message: string;
+  combo: string;
+  conflict: HotkeyConflict;
+}
+
+// Uso aprendido de un elemento; key es su clave estable (rol|titulo|ruta)
+export interface UsageEntry {
+  app: string;
+  key: string;
+  score: number;
+  count: number;
+  last_used: number;
+}
+
+export type FilterDecision =
+  | { decision: "kept" }
+  | { decision: "dropped"; rule: string; reason: string };
+
+export interface FilterExplanation {
+  element: UIElement;
+  decision: FilterDecision;
+}
+
+export interface InspectedNode {
+  element: UIElement;
+  parent: number | null;
+  children: number[];
+  depth: number;
+  path: string;
+  attributes: Record<string, string | null>;
+  decision: FilterDecision;
+}
+
+export interface InspectedTree {
+  nodes: InspectedNode[];
 }